@@ -1,5 +1,11 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use mun_memory::{
+    diff::myers,
+    gc::{Event, GcRuntime, MarkSweep, NoopObserver},
+    HasStaticType, StructTypeBuilder,
+};
 use mun_runtime::StructRef;
+use std::time::{Duration, Instant};
 use wasmer::Store;
 
 mod util;
@@ -295,11 +301,88 @@ pub fn set_struct_field_benchmark(c: &mut Criterion) {
     group.finish();
 }
 
+/// A benchmark test that compares allocating objects one by one to allocating them in a single
+/// batch, which only acquires the garbage collector's locks once.
+pub fn gc_alloc_benchmark(c: &mut Criterion) {
+    let ty = i64::type_info();
+
+    /// Measures the time it takes `alloc` to allocate objects. Objects are collected outside of the
+    /// measurement, as the garbage collector does not free its objects when dropped.
+    fn bench_alloc(
+        b: &mut criterion::Bencher<'_>,
+        alloc: impl Fn(&MarkSweep<NoopObserver<Event>>),
+    ) {
+        b.iter_custom(|iters| {
+            let gc = MarkSweep::<NoopObserver<Event>>::default();
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iters {
+                let start = Instant::now();
+                alloc(&gc);
+                elapsed += start.elapsed();
+                gc.collect();
+            }
+            elapsed
+        })
+    }
+
+    let mut group = c.benchmark_group("gc_alloc");
+
+    for count in [100usize, 1000, 10000].iter() {
+        // Allocate objects one by one
+        group.bench_with_input(BenchmarkId::new("alloc", count), count, |b, count| {
+            bench_alloc(b, |gc| {
+                for _ in 0..*count {
+                    black_box(gc.alloc(ty));
+                }
+            })
+        });
+
+        // Allocate objects in a single batch
+        group.bench_with_input(BenchmarkId::new("batch_alloc", count), count, |b, count| {
+            bench_alloc(b, |gc| {
+                black_box(gc.batch_alloc(ty, *count));
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// A benchmark test that compares diffing two lists of 1000 types with a single change in between
+/// them, by diffing the full lists or only the range that changed.
+pub fn type_diff_benchmark(c: &mut Criterion) {
+    let old: Vec<_> = (0..1000)
+        .map(|i| {
+            StructTypeBuilder::new(format!("Foo{i}"))
+                .add_field("a", i64::type_info().clone())
+                .finish()
+        })
+        .collect();
+    let mut new = old.clone();
+    new[500] = StructTypeBuilder::new("Foo500")
+        .add_field("a", i64::type_info().clone())
+        .add_field("b", f32::type_info().clone())
+        .finish();
+
+    let mut group = c.benchmark_group("type_diff");
+
+    group.bench_function("full", |b| {
+        b.iter(|| myers::compute_full_diff(black_box(&old), black_box(&new)))
+    });
+    group.bench_function("incremental", |b| {
+        b.iter(|| myers::compute_diff(black_box(&old), black_box(&new)))
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     fibonacci_benchmark,
     empty_benchmark,
     get_struct_field_benchmark,
-    set_struct_field_benchmark
+    set_struct_field_benchmark,
+    gc_alloc_benchmark,
+    type_diff_benchmark
 );
 criterion_main!(benches);
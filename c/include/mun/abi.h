@@ -6,7 +6,7 @@
 /**
  * Defines the current ABI version
  */
-#define MUN_ABI_VERSION 300
+#define MUN_ABI_VERSION 10000
 
 /**
  * Represents the kind of memory management a struct uses.
@@ -159,6 +159,11 @@ typedef struct MunFunctionDefinition {
      * Function pointer
      */
     const void *fn_ptr;
+    /**
+     * Whether the function is implemented outside of Mun and linked by the
+     * host
+     */
+    bool is_extern;
 } MunFunctionDefinition;
 
 /**
@@ -251,6 +256,20 @@ typedef struct MunTypeDefinition {
     union MunTypeDefinitionData data;
 } MunTypeDefinition;
 
+/**
+ * Represents a dependency of a module on another assembly.
+ */
+typedef struct MunAssemblyDependency {
+    /**
+     * The GUID of the assembly that is depended upon
+     */
+    struct MunGuid guid;
+    /**
+     * The minimum ABI version the dependency must support
+     */
+    uint32_t min_abi_version;
+} MunAssemblyDependency;
+
 /**
  * Represents a module declaration.
  */
@@ -267,6 +286,10 @@ typedef struct MunModuleInfo {
      * Module types
      */
     const struct MunTypeDefinition *types;
+    /**
+     * Assemblies referenced by the module's types
+     */
+    const struct MunAssemblyDependency *dependencies;
     /**
      * Number of module functions
      */
@@ -275,6 +298,10 @@ typedef struct MunModuleInfo {
      * Number of module types
      */
     uint32_t num_types;
+    /**
+     * Number of assembly dependencies
+     */
+    uint32_t num_dependencies;
 } MunModuleInfo;
 
 /**
@@ -302,6 +329,11 @@ typedef struct MunDispatchTable {
  * linking.
  *
  * Type IDs and handles are stored separately for cache efficiency.
+ *
+ * [`TypeLut::binary_search`] expects the type IDs to be sorted in ascending
+ * order (see [`TypeId`]'s `Ord` implementation). This is not guaranteed for
+ * all assemblies. Debug builds assert that the type IDs are sorted, release
+ * builds fall back to a linear search for unsorted tables.
  */
 typedef struct MunTypeLut {
     /**
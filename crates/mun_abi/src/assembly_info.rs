@@ -1,6 +1,15 @@
-use std::{ffi::CStr, os::raw::c_char, slice, str};
+use std::{
+    collections::HashSet,
+    ffi::{c_void, CStr},
+    fmt,
+    os::raw::c_char,
+    slice, str,
+};
 
-use crate::{DispatchTable, ModuleInfo, TypeLut};
+use crate::{
+    DispatchTable, Guid, ModuleInfo, PrimitiveType, StructMemoryKind, TypeDefinition,
+    TypeDefinitionData, TypeId, TypeLut,
+};
 
 /// Represents an assembly declaration.
 #[repr(C)]
@@ -30,8 +39,374 @@ impl AssemblyInfo<'_> {
             .iter()
             .map(|d| unsafe { str::from_utf8_unchecked(CStr::from_ptr(*d).to_bytes()) })
     }
+
+    /// Validates the internal consistency of the assembly.
+    ///
+    /// This checks that all tables that claim to contain entries are backed by
+    /// non-null pointers, that every function defined by the assembly has a
+    /// function pointer, that the type lookup table does not contain
+    /// duplicate entries, and that the fields of every struct fit within the
+    /// struct without overlapping.
+    ///
+    /// Note that the function pointers stored in the dispatch table are
+    /// expected to be null until the runtime links the assembly, so only the
+    /// existence of the dispatch table itself is validated.
+    ///
+    /// Returns all the violations that were found.
+    pub fn validate(&self) -> Result<(), Vec<AssemblyError>> {
+        let mut errors = Vec::new();
+
+        let tables_are_valid = [
+            (
+                "dependencies",
+                self.num_dependencies,
+                self.dependencies.is_null(),
+            ),
+            (
+                "symbols.functions",
+                self.symbols.num_functions,
+                self.symbols.functions.is_null(),
+            ),
+            (
+                "symbols.types",
+                self.symbols.num_types,
+                self.symbols.types.is_null(),
+            ),
+            (
+                "symbols.dependencies",
+                self.symbols.num_dependencies,
+                self.symbols.dependencies.is_null(),
+            ),
+            (
+                "dispatch_table.prototypes",
+                self.dispatch_table.num_entries,
+                self.dispatch_table.prototypes.is_null(),
+            ),
+            (
+                "dispatch_table.fn_ptrs",
+                self.dispatch_table.num_entries,
+                self.dispatch_table.fn_ptrs.is_null(),
+            ),
+            (
+                "type_lut.type_ids",
+                self.type_lut.num_entries,
+                self.type_lut.type_ids.is_null(),
+            ),
+            (
+                "type_lut.type_handles",
+                self.type_lut.num_entries,
+                self.type_lut.type_handles.is_null(),
+            ),
+            (
+                "type_lut.type_names",
+                self.type_lut.num_entries,
+                self.type_lut.type_names.is_null(),
+            ),
+        ]
+        .into_iter()
+        .fold(true, |valid, (table, num_entries, is_null)| {
+            if num_entries > 0 && is_null {
+                errors.push(AssemblyError::NullTable { table });
+                false
+            } else {
+                valid
+            }
+        });
+
+        // Without valid tables none of the other checks can safely be performed
+        if !tables_are_valid {
+            return Err(errors);
+        }
+
+        for function in self.symbols.functions() {
+            if !function.is_extern && function.fn_ptr.is_null() {
+                errors.push(AssemblyError::NullFunctionPointer {
+                    function: function.prototype.name().to_owned(),
+                });
+            }
+        }
+
+        let mut type_ids = HashSet::new();
+        for type_id in self.type_lut.type_ids() {
+            if !type_ids.insert(type_id) {
+                errors.push(AssemblyError::DuplicateTypeId {
+                    type_id: type_id.to_string(),
+                });
+            }
+        }
+
+        for type_def in self.symbols.types() {
+            validate_struct_layout(type_def, self.symbols.types(), &mut errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl fmt::Debug for AssemblyInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssemblyInfo")
+            .field("path", &checked_str(self.symbols.path))
+            .field("num_functions", &self.symbols.num_functions)
+            .field("num_types", &self.symbols.num_types)
+            .field("num_dependencies", &self.num_dependencies)
+            .finish()
+    }
+}
+
+/// Prints a summary of the assembly's symbols, similar to an `nm` dump.
+///
+/// Every symbol is printed on a separate line, prefixed by its address and a
+/// single letter that describes its kind:
+///
+/// * `T` - a function that is defined by the assembly
+/// * `U` - an extern function that is linked by the host
+/// * `S` - a type that is defined by the assembly
+///
+/// Symbols without an address are printed with blank space instead. Tables
+/// that are null are treated as empty, so malformed assemblies can safely be
+/// printed.
+impl fmt::Display for AssemblyInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const ADDRESS_WIDTH: usize = 2 * std::mem::size_of::<*const c_void>();
+
+        writeln!(f, "assembly `{}`", checked_str(self.symbols.path))?;
+
+        let functions = checked_slice(self.symbols.functions, self.symbols.num_functions);
+        for function in functions {
+            let name = checked_str(function.prototype.name);
+            if function.is_extern {
+                writeln!(f, "{:ADDRESS_WIDTH$} U {name}", "")?;
+            } else {
+                writeln!(f, "{:0ADDRESS_WIDTH$x} T {name}", function.fn_ptr as usize)?;
+            }
+        }
+
+        for type_def in checked_slice(self.symbols.types, self.symbols.num_types) {
+            writeln!(f, "{:ADDRESS_WIDTH$} S {}", "", checked_str(type_def.name))?;
+        }
+
+        for dependency in checked_slice(self.dependencies, self.num_dependencies) {
+            writeln!(f, "dependency `{}`", checked_str(*dependency))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the `len` elements starting at `ptr`, or an empty slice if `ptr` is
+/// null.
+fn checked_slice<'s, T>(ptr: *const T, len: u32) -> &'s [T] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(ptr, len as usize) }
+    }
+}
+
+/// Returns the string pointed to by `ptr`, or `"<null>"` if `ptr` is null.
+fn checked_str<'s>(ptr: *const c_char) -> &'s str {
+    if ptr.is_null() {
+        "<null>"
+    } else {
+        unsafe { str::from_utf8_unchecked(CStr::from_ptr(ptr).to_bytes()) }
+    }
+}
+
+/// Validates that the fields of the struct described by `type_def` fit within
+/// the struct and do not overlap.
+fn validate_struct_layout(
+    type_def: &TypeDefinition<'_>,
+    types: &[TypeDefinition<'_>],
+    errors: &mut Vec<AssemblyError>,
+) {
+    let TypeDefinitionData::Struct(s) = &type_def.data;
+    if s.num_fields == 0 {
+        return;
+    }
+
+    if s.field_names.is_null() || s.field_types.is_null() || s.field_offsets.is_null() {
+        errors.push(AssemblyError::NullTable {
+            table: "struct fields",
+        });
+        return;
+    }
+
+    let struct_size = type_def.size_in_bytes();
+    let mut fields = s
+        .iter_fields()
+        .map(|field| {
+            (
+                field.name,
+                usize::from(field.offset),
+                field_size(field.type_id, types),
+            )
+        })
+        .collect::<Vec<_>>();
+    fields.sort_by_key(|(_, offset, _)| *offset);
+
+    for (name, offset, size) in fields.iter() {
+        if offset + size.unwrap_or(0) > struct_size {
+            errors.push(AssemblyError::FieldOutOfBounds {
+                struct_name: type_def.name().to_owned(),
+                field_name: (*name).to_owned(),
+                offset: *offset,
+                struct_size,
+            });
+        }
+    }
+
+    for pair in fields.windows(2) {
+        let (first, first_offset, first_size) = pair[0];
+        let (second, second_offset, _) = pair[1];
+        if let Some(first_size) = first_size {
+            if first_offset + first_size > second_offset {
+                errors.push(AssemblyError::OverlappingFields {
+                    struct_name: type_def.name().to_owned(),
+                    first: first.to_owned(),
+                    second: second.to_owned(),
+                });
+            }
+        }
+    }
+}
+
+/// Returns the size in bytes that a field of type `type_id` occupies inside a
+/// struct, or `None` if the size cannot be determined from this assembly
+/// alone.
+fn field_size(type_id: &TypeId<'_>, types: &[TypeDefinition<'_>]) -> Option<usize> {
+    const POINTER_SIZE: usize = std::mem::size_of::<*const c_void>();
+
+    match type_id {
+        TypeId::Concrete(guid) => primitive_size(guid).or_else(|| {
+            types
+                .iter()
+                .find(|ty| ty.is_instance_of(type_id))
+                .map(|ty| match &ty.data {
+                    TypeDefinitionData::Struct(s) => match s.memory_kind {
+                        StructMemoryKind::Gc => POINTER_SIZE,
+                        StructMemoryKind::Value => ty.size_in_bytes(),
+                    },
+                })
+        }),
+        TypeId::Pointer(_) | TypeId::Array(_) => Some(POINTER_SIZE),
+    }
 }
 
+/// Returns the size in bytes of the primitive type with the specified `guid`.
+fn primitive_size(guid: &Guid) -> Option<usize> {
+    macro_rules! primitive_sizes {
+        ($($ty:ty),*) => {
+            $(
+                if guid == <$ty>::guid() {
+                    return Some(<$ty>::size_of());
+                }
+            )*
+        };
+    }
+
+    primitive_sizes!(
+        i8,
+        i16,
+        i32,
+        i64,
+        i128,
+        u8,
+        u16,
+        u32,
+        u64,
+        u128,
+        f32,
+        f64,
+        bool,
+        ()
+    );
+    None
+}
+
+/// A violation of the internal consistency of an [`AssemblyInfo`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssemblyError {
+    /// A table contains entries but its pointer is null.
+    NullTable {
+        /// The name of the table
+        table: &'static str,
+    },
+    /// A function defined by the assembly does not have a function pointer.
+    NullFunctionPointer {
+        /// The name of the function
+        function: String,
+    },
+    /// The type lookup table contains the same type more than once.
+    DuplicateTypeId {
+        /// The duplicated type
+        type_id: String,
+    },
+    /// A struct field extends beyond the size of its struct.
+    FieldOutOfBounds {
+        /// The name of the struct
+        struct_name: String,
+        /// The name of the field
+        field_name: String,
+        /// The offset of the field in bytes
+        offset: usize,
+        /// The size of the struct in bytes
+        struct_size: usize,
+    },
+    /// Two struct fields occupy the same memory.
+    OverlappingFields {
+        /// The name of the struct
+        struct_name: String,
+        /// The name of the field with the lower offset
+        first: String,
+        /// The name of the field with the higher offset
+        second: String,
+    },
+}
+
+impl fmt::Display for AssemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssemblyError::NullTable { table } => {
+                write!(f, "table `{table}` contains entries but is null")
+            }
+            AssemblyError::NullFunctionPointer { function } => {
+                write!(f, "function `{function}` has a null function pointer")
+            }
+            AssemblyError::DuplicateTypeId { type_id } => {
+                write!(
+                    f,
+                    "type `{type_id}` occurs more than once in the type lookup table"
+                )
+            }
+            AssemblyError::FieldOutOfBounds {
+                struct_name,
+                field_name,
+                offset,
+                struct_size,
+            } => write!(
+                f,
+                "field `{struct_name}::{field_name}` at offset {offset} does not fit within the \
+                 struct's size of {struct_size} bytes"
+            ),
+            AssemblyError::OverlappingFields {
+                struct_name,
+                first,
+                second,
+            } => write!(
+                f,
+                "fields `{struct_name}::{first}` and `{struct_name}::{second}` overlap"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AssemblyError {}
+
 unsafe impl Send for AssemblyInfo<'_> {}
 unsafe impl Sync for AssemblyInfo<'_> {}
 
@@ -55,11 +430,17 @@ impl serde::Serialize for AssemblyInfo<'_> {
 
 #[cfg(test)]
 mod tests {
-    use std::ffi::CString;
+    use std::{ffi::CString, ptr};
 
-    use crate::test_utils::{
-        fake_assembly_info, fake_dispatch_table, fake_module_info, fake_type_lut, FAKE_DEPENDENCY,
-        FAKE_MODULE_PATH,
+    use super::AssemblyError;
+    use crate::{
+        test_utils::{
+            fake_assembly_info, fake_dispatch_table, fake_fn_prototype, fake_module_info,
+            fake_struct_definition, fake_type_definition, fake_type_lut, FAKE_DEPENDENCY,
+            FAKE_FIELD_NAME, FAKE_FN_NAME, FAKE_MODULE_PATH, FAKE_STRUCT_NAME, FAKE_TYPE_ID,
+            FAKE_TYPE_NAME,
+        },
+        FunctionDefinition, HasStaticTypeId, StructMemoryKind, TypeDefinitionData,
     };
 
     #[test]
@@ -68,15 +449,246 @@ mod tests {
         let module = fake_module_info(&module_path, &[], &[]);
 
         let dispatch_table = fake_dispatch_table(&[], &mut []);
-        let type_lut = fake_type_lut(&[], &mut [], &[]);
+        let type_lut = unsafe { fake_type_lut(&[], &mut [], &[]) };
 
         let dependency = CString::new(FAKE_DEPENDENCY).expect("Invalid fake dependency.");
         let dependencies = &[dependency.as_ptr()];
-        let assembly = fake_assembly_info(module, dispatch_table, type_lut, dependencies);
+        let assembly =
+            unsafe { fake_assembly_info(module, dispatch_table, type_lut, dependencies) };
 
         assert_eq!(assembly.dependencies().count(), dependencies.len());
         for (lhs, rhs) in assembly.dependencies().zip([FAKE_DEPENDENCY].iter()) {
             assert_eq!(lhs, *rhs);
         }
     }
+
+    #[test]
+    fn test_assembly_info_fmt() {
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let extern_fn_name = CString::new("extern_fn").expect("Invalid fake fn name.");
+        let functions = [
+            FunctionDefinition {
+                prototype: fake_fn_prototype(&fn_name, &[], None),
+                fn_ptr: 0x10 as *const _,
+                is_extern: false,
+            },
+            FunctionDefinition {
+                prototype: fake_fn_prototype(&extern_fn_name, &[], None),
+                fn_ptr: ptr::null(),
+                is_extern: true,
+            },
+        ];
+        let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name.");
+        let types = [fake_type_definition(
+            &struct_name,
+            0,
+            1,
+            TypeDefinitionData::Struct(unsafe {
+                fake_struct_definition(&struct_name, &[], &[], &[], StructMemoryKind::Value)
+            }),
+        )];
+        let module = fake_module_info(&module_path, &functions, &types);
+
+        let dispatch_table = fake_dispatch_table(&[], &mut []);
+        let type_lut = unsafe { fake_type_lut(&[], &mut [], &[]) };
+
+        let dependency = CString::new(FAKE_DEPENDENCY).expect("Invalid fake dependency.");
+        let dependencies = &[dependency.as_ptr()];
+        let assembly =
+            unsafe { fake_assembly_info(module, dispatch_table, type_lut, dependencies) };
+
+        assert_eq!(
+            format!("{assembly:?}"),
+            format!(
+                "AssemblyInfo {{ path: \"{FAKE_MODULE_PATH}\", num_functions: 2, num_types: 1, \
+                 num_dependencies: 1 }}"
+            )
+        );
+
+        let width = 2 * std::mem::size_of::<usize>();
+        assert_eq!(
+            assembly.to_string(),
+            format!(
+                "assembly `{FAKE_MODULE_PATH}`\n\
+                 {:0width$x} T {FAKE_FN_NAME}\n\
+                 {:width$} U extern_fn\n\
+                 {:width$} S {FAKE_STRUCT_NAME}\n\
+                 dependency `{FAKE_DEPENDENCY}`\n",
+                0x10, "", ""
+            )
+        );
+    }
+
+    #[test]
+    fn test_assembly_info_fmt_null_tables() {
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let mut module = fake_module_info(&module_path, &[], &[]);
+        module.path = ptr::null();
+        module.functions = ptr::null();
+        module.num_functions = 1;
+        module.types = ptr::null();
+        module.num_types = 1;
+
+        let dispatch_table = fake_dispatch_table(&[], &mut []);
+        let type_lut = unsafe { fake_type_lut(&[], &mut [], &[]) };
+
+        let assembly = unsafe { fake_assembly_info(module, dispatch_table, type_lut, &[]) };
+        assert!(format!("{assembly:?}").contains("path: \"<null>\""));
+        assert_eq!(assembly.to_string(), "assembly `<null>`\n");
+    }
+
+    #[test]
+    fn test_assembly_info_validate() {
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let functions = [FunctionDefinition {
+            prototype: fake_fn_prototype(&fn_name, &[], None),
+            fn_ptr: 1 as *const _,
+            is_extern: false,
+        }];
+
+        let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name.");
+        let field_name = CString::new(FAKE_FIELD_NAME).expect("Invalid fake field name.");
+        let field_names = &[field_name.as_ptr(), field_name.as_ptr()];
+        let field_types = &[i32::type_id().clone(), f32::type_id().clone()];
+        let types = [fake_type_definition(
+            &struct_name,
+            64,
+            4,
+            TypeDefinitionData::Struct(unsafe {
+                fake_struct_definition(
+                    &struct_name,
+                    field_names,
+                    field_types,
+                    &[0, 4],
+                    StructMemoryKind::Value,
+                )
+            }),
+        )];
+        let module = fake_module_info(&module_path, &functions, &types);
+
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_ptrs = &mut [ptr::null()];
+        let type_names = &[type_name.as_ptr()];
+        let type_lut = unsafe { fake_type_lut(&[FAKE_TYPE_ID], type_ptrs, type_names) };
+
+        let prototypes = &[fake_fn_prototype(&fn_name, &[], None)];
+        let fn_ptrs = &mut [ptr::null()];
+        let dispatch_table = fake_dispatch_table(prototypes, fn_ptrs);
+
+        let assembly = unsafe { fake_assembly_info(module, dispatch_table, type_lut, &[]) };
+        assert_eq!(assembly.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_assembly_info_validate_null_table() {
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let mut module = fake_module_info(&module_path, &[], &[]);
+        module.functions = ptr::null();
+        module.num_functions = 1;
+
+        let dispatch_table = fake_dispatch_table(&[], &mut []);
+        let type_lut = unsafe { fake_type_lut(&[], &mut [], &[]) };
+
+        let assembly = unsafe { fake_assembly_info(module, dispatch_table, type_lut, &[]) };
+        assert_eq!(
+            assembly.validate(),
+            Err(vec![AssemblyError::NullTable {
+                table: "symbols.functions"
+            }])
+        );
+    }
+
+    #[test]
+    fn test_assembly_info_validate_null_fn_ptr() {
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let functions = [FunctionDefinition {
+            prototype: fake_fn_prototype(&fn_name, &[], None),
+            fn_ptr: ptr::null(),
+            is_extern: false,
+        }];
+        let module = fake_module_info(&module_path, &functions, &[]);
+
+        let dispatch_table = fake_dispatch_table(&[], &mut []);
+        let type_lut = unsafe { fake_type_lut(&[], &mut [], &[]) };
+
+        let assembly = unsafe { fake_assembly_info(module, dispatch_table, type_lut, &[]) };
+        assert_eq!(
+            assembly.validate(),
+            Err(vec![AssemblyError::NullFunctionPointer {
+                function: FAKE_FN_NAME.to_owned()
+            }])
+        );
+    }
+
+    #[test]
+    fn test_assembly_info_validate_duplicate_type_id() {
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let module = fake_module_info(&module_path, &[], &[]);
+
+        let dispatch_table = fake_dispatch_table(&[], &mut []);
+
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_ptrs = &mut [ptr::null(), ptr::null()];
+        let type_names = &[type_name.as_ptr(), type_name.as_ptr()];
+        let type_lut =
+            unsafe { fake_type_lut(&[FAKE_TYPE_ID, FAKE_TYPE_ID], type_ptrs, type_names) };
+
+        let assembly = unsafe { fake_assembly_info(module, dispatch_table, type_lut, &[]) };
+        assert_eq!(
+            assembly.validate(),
+            Err(vec![AssemblyError::DuplicateTypeId {
+                type_id: FAKE_TYPE_ID.to_string()
+            }])
+        );
+    }
+
+    #[test]
+    fn test_assembly_info_validate_struct_layout() {
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+
+        let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name.");
+        let first_name = CString::new("a").expect("Invalid fake field name.");
+        let second_name = CString::new("b").expect("Invalid fake field name.");
+        let field_names = &[first_name.as_ptr(), second_name.as_ptr()];
+        let field_types = &[i64::type_id().clone(), i32::type_id().clone()];
+        let types = [fake_type_definition(
+            &struct_name,
+            64,
+            8,
+            TypeDefinitionData::Struct(unsafe {
+                fake_struct_definition(
+                    &struct_name,
+                    field_names,
+                    field_types,
+                    &[0, 6],
+                    StructMemoryKind::Value,
+                )
+            }),
+        )];
+        let module = fake_module_info(&module_path, &[], &types);
+
+        let dispatch_table = fake_dispatch_table(&[], &mut []);
+        let type_lut = unsafe { fake_type_lut(&[], &mut [], &[]) };
+
+        let assembly = unsafe { fake_assembly_info(module, dispatch_table, type_lut, &[]) };
+        assert_eq!(
+            assembly.validate(),
+            Err(vec![
+                AssemblyError::FieldOutOfBounds {
+                    struct_name: FAKE_STRUCT_NAME.to_owned(),
+                    field_name: "b".to_owned(),
+                    offset: 6,
+                    struct_size: 8,
+                },
+                AssemblyError::OverlappingFields {
+                    struct_name: FAKE_STRUCT_NAME.to_owned(),
+                    first: "a".to_owned(),
+                    second: "b".to_owned(),
+                }
+            ])
+        );
+    }
 }
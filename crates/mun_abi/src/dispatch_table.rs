@@ -1,6 +1,6 @@
 use std::{ffi::c_void, slice};
 
-use crate::FunctionPrototype;
+use crate::{ExternFnPointer, FunctionPrototype};
 
 /// Represents a function dispatch table. This is used for runtime linking.
 ///
@@ -113,6 +113,35 @@ impl<'a> DispatchTable<'a> {
             None
         }
     }
+
+    /// Returns the function pointer of the function called `name` as the
+    /// typed function pointer `F` (e.g. `extern "C" fn(i32, f64) -> bool`), or
+    /// `None` if no such function exists or its pointer has not been set.
+    ///
+    /// In debug builds, this asserts that the signature of `F` matches the
+    /// function's prototype.
+    ///
+    /// # Safety
+    ///
+    /// The signature of `F` must match that of the function, otherwise calling
+    /// the returned function pointer is _undefined behavior_.
+    pub unsafe fn get_fn_ptr<F: ExternFnPointer>(&self, name: &str) -> Option<F> {
+        let (fn_ptr, prototype) = self
+            .iter()
+            .find(|(_, prototype)| prototype.name() == name)?;
+        if fn_ptr.is_null() {
+            return None;
+        }
+
+        debug_assert!(
+            prototype.matches_rust_fn::<F>(),
+            "the signature of function `{name}` does not match the requested function pointer type"
+        );
+
+        // Safety: `F` is an `extern "C"` function pointer, which has the same size as
+        // `fn_ptr`. The caller guarantees that its signature matches.
+        Some(std::mem::transmute_copy::<*const c_void, F>(fn_ptr))
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -131,7 +160,10 @@ impl serde::Serialize for DispatchTable<'_> {
 
 #[cfg(test)]
 mod tests {
-    use std::{ffi::CString, ptr};
+    use std::{
+        ffi::{c_void, CString},
+        ptr,
+    };
 
     use crate::{
         test_utils::{fake_dispatch_table, fake_fn_prototype, FAKE_FN_NAME},
@@ -144,8 +176,7 @@ mod tests {
         let fn_ptrs = &mut [];
         let mut dispatch_table = fake_dispatch_table(signatures, fn_ptrs);
 
-        let iter = fn_ptrs.iter_mut().zip(signatures.iter());
-        assert_eq!(dispatch_table.iter_mut().count(), iter.count());
+        assert_eq!(dispatch_table.iter_mut().count(), 0);
     }
 
     #[test]
@@ -157,17 +188,24 @@ mod tests {
         let fn_prototype = fake_fn_prototype(&fn_name, &[], return_type);
 
         let prototypes = &[fn_prototype];
-        let fn_ptrs = &mut [ptr::null()];
+        let expected_ptrs = [ptr::null()];
+        let fn_ptrs = &mut expected_ptrs.clone();
         let mut dispatch_table = fake_dispatch_table(prototypes, fn_ptrs);
 
-        let iter = fn_ptrs.iter_mut().zip(prototypes.iter());
-        assert_eq!(dispatch_table.iter_mut().count(), iter.len());
+        assert_eq!(dispatch_table.iter_mut().count(), prototypes.len());
 
+        let iter = expected_ptrs.iter().zip(prototypes.iter());
         for (lhs, rhs) in dispatch_table.iter_mut().zip(iter) {
             assert_eq!(lhs.0, rhs.0);
             assert_eq!(lhs.1.name(), rhs.1.name());
-            assert_eq!(lhs.1.signature.arg_types(), rhs.1.signature.arg_types());
-            assert_eq!(lhs.1.signature.return_type, rhs.1.signature.return_type);
+            assert_eq!(
+                lhs.1.signature.arg_type_ids(),
+                rhs.1.signature.arg_type_ids()
+            );
+            assert_eq!(
+                lhs.1.signature.return_type_id(),
+                rhs.1.signature.return_type_id()
+            );
         }
     }
 
@@ -189,12 +227,13 @@ mod tests {
         let fn_prototype = fake_fn_prototype(&fn_name, &[], return_type);
 
         let prototypes = &[fn_prototype];
-        let fn_ptrs = &mut [ptr::null()];
+        let expected_ptrs = [ptr::null()];
+        let fn_ptrs = &mut expected_ptrs.clone();
         let mut dispatch_table = fake_dispatch_table(prototypes, fn_ptrs);
 
         let result = dispatch_table.ptrs_mut();
-        assert_eq!(result.len(), fn_ptrs.len());
-        for (lhs, rhs) in result.iter().zip(fn_ptrs.iter()) {
+        assert_eq!(result.len(), expected_ptrs.len());
+        for (lhs, rhs) in result.iter().zip(expected_ptrs.iter()) {
             assert_eq!(lhs, rhs);
         }
     }
@@ -224,8 +263,11 @@ mod tests {
         assert_eq!(result.len(), prototypes.len());
         for (lhs, rhs) in result.iter().zip(prototypes.iter()) {
             assert_eq!(lhs.name(), rhs.name());
-            assert_eq!(lhs.signature.arg_types(), rhs.signature.arg_types());
-            assert_eq!(lhs.signature.return_type, rhs.signature.return_type);
+            assert_eq!(lhs.signature.arg_type_ids(), rhs.signature.arg_type_ids());
+            assert_eq!(
+                lhs.signature.return_type_id(),
+                rhs.signature.return_type_id()
+            );
         }
     }
 
@@ -280,12 +322,13 @@ mod tests {
 
         let prototypes = &[fn_prototype];
         let fn_ptrs = &mut [ptr::null()];
+        let fn_ptr_addr: *const *const c_void = &fn_ptrs[0];
 
         let mut dispatch_table = fake_dispatch_table(prototypes, fn_ptrs);
-        assert_eq!(
+        assert!(ptr::eq(
             unsafe { dispatch_table.get_ptr_unchecked_mut(0) },
-            &mut fn_ptrs[0]
-        );
+            fn_ptr_addr
+        ));
     }
 
     #[test]
@@ -311,8 +354,46 @@ mod tests {
 
         let prototypes = &[fn_prototype];
         let fn_ptrs = &mut [ptr::null()];
+        let fn_ptr_addr: *const *const c_void = &fn_ptrs[0];
 
         let mut dispatch_table = fake_dispatch_table(prototypes, fn_ptrs);
-        assert_eq!(dispatch_table.get_ptr_mut(0), Some(&mut fn_ptrs[0]));
+        let fn_ptr = dispatch_table.get_ptr_mut(0).expect("entry must exist");
+        assert!(ptr::eq(fn_ptr, fn_ptr_addr));
+    }
+
+    extern "C" fn fake_fn(a: i32) -> i32 {
+        a + 1
+    }
+
+    #[test]
+    fn test_dispatch_table_get_fn_ptr() {
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let arg_types = &[i32::type_id().clone()];
+        let fn_prototype = fake_fn_prototype(&fn_name, arg_types, Some(i32::type_id().clone()));
+
+        let prototypes = &[fn_prototype];
+        let fn_ptrs = &mut [fake_fn as *const c_void];
+        let dispatch_table = fake_dispatch_table(prototypes, fn_ptrs);
+
+        let fn_ptr =
+            unsafe { dispatch_table.get_fn_ptr::<extern "C" fn(i32) -> i32>(FAKE_FN_NAME) }
+                .expect("function must exist");
+        assert_eq!(fn_ptr(1), 2);
+
+        assert!(
+            unsafe { dispatch_table.get_fn_ptr::<extern "C" fn(i32) -> i32>("unknown") }.is_none()
+        );
+    }
+
+    #[test]
+    fn test_dispatch_table_get_fn_ptr_null() {
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let fn_prototype = fake_fn_prototype(&fn_name, &[], None);
+
+        let prototypes = &[fn_prototype];
+        let fn_ptrs = &mut [ptr::null()];
+        let dispatch_table = fake_dispatch_table(prototypes, fn_ptrs);
+
+        assert!(unsafe { dispatch_table.get_fn_ptr::<extern "C" fn()>(FAKE_FN_NAME) }.is_none());
     }
 }
@@ -1,5 +1,7 @@
 use std::{
     ffi::{c_void, CStr},
+    fmt,
+    hash::{Hash, Hasher},
     os::raw::c_char,
     slice, str,
 };
@@ -17,6 +19,9 @@ pub struct FunctionDefinition<'a> {
     pub prototype: FunctionPrototype<'a>,
     /// Function pointer
     pub fn_ptr: *const c_void,
+    /// Whether the function is implemented outside of Mun and linked by the
+    /// host
+    pub is_extern: bool,
 }
 
 /// Represents a function prototype. A function prototype contains the name,
@@ -45,26 +50,198 @@ pub struct FunctionSignature<'a> {
 unsafe impl Send for FunctionDefinition<'_> {}
 unsafe impl Sync for FunctionDefinition<'_> {}
 
+impl FunctionDefinition<'_> {
+    /// Returns the function pointer as the typed function pointer `F` (e.g.
+    /// `extern "C" fn(i32, f64) -> bool`), after verifying that the argument
+    /// and return types of `F` match the function's signature.
+    ///
+    /// # Safety
+    ///
+    /// Only the function's prototype is checked against `F`. The function
+    /// pointer must point to a function that actually has this prototype,
+    /// otherwise calling the returned function pointer is _undefined
+    /// behavior_.
+    pub unsafe fn downcast<F: ExternFnPointer>(&self) -> Result<F, DowncastError> {
+        if !self.prototype.matches_rust_fn::<F>() {
+            return Err(DowncastError::SignatureMismatch {
+                function: self.prototype.name().to_owned(),
+                expected: format_signature(F::arg_type_ids(), F::return_type_id()),
+                actual: format_signature(
+                    self.prototype.signature.arg_type_ids(),
+                    self.prototype.signature.return_type_id(),
+                ),
+            });
+        }
+
+        if self.fn_ptr.is_null() {
+            return Err(DowncastError::NullFunctionPointer {
+                function: self.prototype.name().to_owned(),
+            });
+        }
+
+        // Safety: `F` is an `extern "C"` function pointer and its signature was
+        // verified above. The caller guarantees that the prototype is correct.
+        Ok(self.to_function_pointer())
+    }
+
+    /// Returns the function pointer as the function pointer type `F`, without
+    /// any checks. Prefer [`FunctionDefinition::downcast`] where possible.
+    ///
+    /// In debug builds, this asserts that `F` has the size of a pointer.
+    ///
+    /// # Safety
+    ///
+    /// `F` must be a function pointer type (e.g. `extern "C" fn(i32) -> f64`)
+    /// whose calling convention, argument types, and return type match those
+    /// of the function. The function pointer must not be null. Violating
+    /// either requirement makes calling the returned function pointer
+    /// _undefined behavior_.
+    pub unsafe fn to_function_pointer<F: Copy>(&self) -> F {
+        debug_assert_eq!(
+            std::mem::size_of::<F>(),
+            std::mem::size_of::<*const c_void>(),
+            "`{}` is not a function pointer type",
+            std::any::type_name::<F>()
+        );
+        debug_assert!(
+            !self.fn_ptr.is_null(),
+            "the function pointer of `{}` is null",
+            self.prototype.name()
+        );
+        std::mem::transmute_copy::<*const c_void, F>(&self.fn_ptr)
+    }
+}
+
+/// Formats a function signature for diagnostic output.
+fn format_signature<'t>(
+    arg_types: impl IntoIterator<Item = &'t TypeId<'t>>,
+    return_type: &TypeId<'_>,
+) -> String {
+    let args = arg_types
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if return_type == <()>::type_id() {
+        format!("fn({args})")
+    } else {
+        format!("fn({args}) -> {return_type}")
+    }
+}
+
+/// An error that can occur when downcasting a [`FunctionDefinition`] to a
+/// typed function pointer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DowncastError {
+    /// The signature of the function does not match the requested type.
+    SignatureMismatch {
+        /// The name of the function
+        function: String,
+        /// The signature of the requested function pointer type
+        expected: String,
+        /// The signature of the function
+        actual: String,
+    },
+    /// The function does not have a function pointer.
+    NullFunctionPointer {
+        /// The name of the function
+        function: String,
+    },
+}
+
+impl fmt::Display for DowncastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DowncastError::SignatureMismatch {
+                function,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "function `{function}` has signature `{actual}`, which does not match `{expected}`"
+            ),
+            DowncastError::NullFunctionPointer { function } => {
+                write!(f, "function `{function}` has a null function pointer")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DowncastError {}
+
 impl FunctionPrototype<'_> {
     /// Returns the function's name.
     pub fn name(&self) -> &str {
         unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.name).to_bytes()) }
     }
+
+    /// Returns the name of the function's symbol as it appears in the symbol
+    /// table of an object file on the current platform. Apple platforms prefix
+    /// C symbols with an underscore, other platforms use the name as is.
+    ///
+    /// Note that `dlsym` expects the undecorated name on all platforms, so use
+    /// [`FunctionPrototype::demangled_name`] for dynamic symbol lookups.
+    pub fn mangled_name(&self) -> String {
+        if cfg!(target_vendor = "apple") {
+            format!("_{}", self.name())
+        } else {
+            self.name().to_owned()
+        }
+    }
+
+    /// Returns the logical name of the function, as written in Mun source
+    /// code. This is the same as [`FunctionPrototype::name`].
+    pub fn demangled_name(&self) -> &str {
+        self.name()
+    }
+
+    /// Returns true if the function's signature is compatible with the Rust
+    /// function type `F` (e.g. `fn(i32, f64) -> bool`).
+    pub fn matches_rust_fn<F: HasStaticFnSignature>(&self) -> bool {
+        self.signature.matches_rust_fn::<F>()
+    }
+}
+
+impl PartialEq for FunctionPrototype<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name() && self.signature == other.signature
+    }
+}
+
+impl Eq for FunctionPrototype<'_> {}
+
+impl Hash for FunctionPrototype<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().hash(state);
+        self.signature.hash(state);
+    }
 }
 
 unsafe impl Send for FunctionPrototype<'_> {}
 unsafe impl Sync for FunctionPrototype<'_> {}
 
 impl<'a> FunctionSignature<'a> {
-    /// Returns the function's arguments' types.
-    pub fn arg_types(&self) -> &[TypeId<'a>] {
-        if self.num_arg_types == 0 {
+    /// Returns the [`TypeId`]s of the function's arguments.
+    pub fn arg_type_ids(&self) -> &[TypeId<'a>] {
+        if self.num_arg_types == 0 || self.arg_types.is_null() {
             &[]
         } else {
+            // Safety: `arg_types` is non-null and points to `num_arg_types` elements.
             unsafe { slice::from_raw_parts(self.arg_types, self.num_arg_types as usize) }
         }
     }
 
+    /// Returns the [`TypeId`] of the function's return type. For functions
+    /// without a return value this is the [`TypeId`] of `()`.
+    pub fn return_type_id(&self) -> &TypeId<'a> {
+        &self.return_type
+    }
+
+    /// Returns the function's arguments' types.
+    pub fn arg_types(&self) -> &[TypeId<'a>] {
+        self.arg_type_ids()
+    }
+
     /// Returns the function's return type.
     pub fn return_type(&self) -> Option<TypeId<'a>> {
         if <()>::type_id() == &self.return_type {
@@ -73,8 +250,81 @@ impl<'a> FunctionSignature<'a> {
             Some(self.return_type.clone())
         }
     }
+
+    /// Returns true if the signature is compatible with the Rust function type
+    /// `F` (e.g. `fn(i32, f64) -> bool`), comparing the [`TypeId`] of every
+    /// argument and the return type.
+    pub fn matches_rust_fn<F: HasStaticFnSignature>(&self) -> bool {
+        self.return_type_id() == F::return_type_id()
+            && self.arg_type_ids().len() == F::arg_type_ids().len()
+            && self
+                .arg_type_ids()
+                .iter()
+                .zip(F::arg_type_ids())
+                .all(|(a, b)| a == b)
+    }
+}
+
+/// A trait that defines that for a Rust function pointer type we can
+/// statically return the [`TypeId`]s of its signature.
+pub trait HasStaticFnSignature {
+    /// Returns the [`TypeId`]s of the function's arguments
+    fn arg_type_ids() -> Vec<&'static TypeId<'static>>;
+
+    /// Returns the [`TypeId`] of the function's return type
+    fn return_type_id() -> &'static TypeId<'static>;
+}
+
+/// A marker trait for `extern "C"` function pointer types, which can be called
+/// through the function pointers stored in a [`FunctionDefinition`].
+///
+/// This trait is sealed, so it is only implemented for function pointer types.
+pub trait ExternFnPointer: HasStaticFnSignature + Copy + sealed::Sealed {}
+
+mod sealed {
+    /// Prevents [`ExternFnPointer`](super::ExternFnPointer) from being
+    /// implemented outside of this crate.
+    pub trait Sealed {}
+}
+
+macro_rules! impl_has_static_fn_signature {
+    ($($arg:ident),*) => {
+        impl<$($arg: HasStaticTypeId,)* R: HasStaticTypeId> HasStaticFnSignature for fn($($arg),*) -> R {
+            fn arg_type_ids() -> Vec<&'static TypeId<'static>> {
+                vec![$($arg::type_id()),*]
+            }
+
+            fn return_type_id() -> &'static TypeId<'static> {
+                R::type_id()
+            }
+        }
+
+        impl<$($arg: HasStaticTypeId,)* R: HasStaticTypeId> HasStaticFnSignature for extern "C" fn($($arg),*) -> R {
+            fn arg_type_ids() -> Vec<&'static TypeId<'static>> {
+                vec![$($arg::type_id()),*]
+            }
+
+            fn return_type_id() -> &'static TypeId<'static> {
+                R::type_id()
+            }
+        }
+
+        impl<$($arg: HasStaticTypeId,)* R: HasStaticTypeId> sealed::Sealed for extern "C" fn($($arg),*) -> R {}
+
+        impl<$($arg: HasStaticTypeId,)* R: HasStaticTypeId> ExternFnPointer for extern "C" fn($($arg),*) -> R {}
+    };
 }
 
+impl_has_static_fn_signature!();
+impl_has_static_fn_signature!(A);
+impl_has_static_fn_signature!(A, B);
+impl_has_static_fn_signature!(A, B, C);
+impl_has_static_fn_signature!(A, B, C, D);
+impl_has_static_fn_signature!(A, B, C, D, E);
+impl_has_static_fn_signature!(A, B, C, D, E, F);
+impl_has_static_fn_signature!(A, B, C, D, E, F, G);
+impl_has_static_fn_signature!(A, B, C, D, E, F, G, H);
+
 impl PartialEq for FunctionSignature<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.return_type() == other.return_type() && self.arg_types().eq(other.arg_types())
@@ -83,6 +333,13 @@ impl PartialEq for FunctionSignature<'_> {
 
 impl Eq for FunctionSignature<'_> {}
 
+impl Hash for FunctionSignature<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.return_type().hash(state);
+        self.arg_types().hash(state);
+    }
+}
+
 unsafe impl Send for FunctionSignature<'_> {}
 unsafe impl Sync for FunctionSignature<'_> {}
 
@@ -94,9 +351,10 @@ impl serde::Serialize for FunctionDefinition<'_> {
     {
         use serde::ser::SerializeStruct;
 
-        let mut s = serializer.serialize_struct("FunctionDefinition", 1)?;
+        let mut s = serializer.serialize_struct("FunctionDefinition", 2)?;
         s.serialize_field("prototype", &self.prototype)?;
         s.skip_field("fn_ptr")?;
+        s.serialize_field("is_extern", &self.is_extern)?;
         s.end()
     }
 }
@@ -133,13 +391,32 @@ impl serde::Serialize for FunctionSignature<'_> {
 
 #[cfg(test)]
 mod tests {
-    use std::ffi::CString;
+    use std::{
+        collections::{hash_map::DefaultHasher, HashSet},
+        ffi::{c_void, CString},
+        hash::{Hash, Hasher},
+        ptr,
+    };
 
+    use super::{DowncastError, FunctionDefinition, FunctionPrototype};
     use crate::{
         test_utils::{fake_fn_prototype, fake_fn_signature, FAKE_FN_NAME},
         type_id::HasStaticTypeId,
     };
 
+    #[test]
+    fn test_fn_prototype_mangled_name() {
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let fn_prototype = fake_fn_prototype(&fn_name, &[], None);
+
+        assert_eq!(fn_prototype.demangled_name(), FAKE_FN_NAME);
+        if cfg!(target_vendor = "apple") {
+            assert_eq!(fn_prototype.mangled_name(), format!("_{FAKE_FN_NAME}"));
+        } else {
+            assert_eq!(fn_prototype.mangled_name(), FAKE_FN_NAME);
+        }
+    }
+
     #[test]
     fn test_fn_prototype_name() {
         let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
@@ -183,4 +460,139 @@ mod tests {
 
         assert_eq!(fn_signature.return_type(), return_type);
     }
+
+    #[test]
+    fn test_fn_signature_type_ids() {
+        let arg_types = &[i32::type_id().clone(), f64::type_id().clone()];
+        let fn_signature = fake_fn_signature(arg_types, Some(bool::type_id().clone()));
+
+        assert_eq!(fn_signature.arg_type_ids(), arg_types);
+        assert_eq!(fn_signature.return_type_id(), bool::type_id());
+
+        let fn_signature = fake_fn_signature(&[], None);
+        assert!(fn_signature.arg_type_ids().is_empty());
+        assert_eq!(fn_signature.return_type_id(), <()>::type_id());
+    }
+
+    #[test]
+    fn test_fn_signature_arg_type_ids_null() {
+        let mut fn_signature = fake_fn_signature(&[], None);
+        fn_signature.arg_types = std::ptr::null();
+        fn_signature.num_arg_types = 2;
+
+        assert!(fn_signature.arg_type_ids().is_empty());
+    }
+
+    #[test]
+    fn test_fn_prototype_matches_rust_fn() {
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let arg_types = &[i32::type_id().clone(), f64::type_id().clone()];
+        let fn_prototype = fake_fn_prototype(&fn_name, arg_types, Some(bool::type_id().clone()));
+
+        assert!(fn_prototype.matches_rust_fn::<fn(i32, f64) -> bool>());
+        assert!(fn_prototype.matches_rust_fn::<extern "C" fn(i32, f64) -> bool>());
+        assert!(!fn_prototype.matches_rust_fn::<fn(i32, f64)>());
+        assert!(!fn_prototype.matches_rust_fn::<fn(i32) -> bool>());
+        assert!(!fn_prototype.matches_rust_fn::<fn(f64, i32) -> bool>());
+        assert!(!fn_prototype.matches_rust_fn::<fn(i32, f64, i32) -> bool>());
+    }
+
+    #[test]
+    fn test_fn_prototype_hash_eq() {
+        fn hash(prototype: &FunctionPrototype<'_>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            prototype.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        // Construct both prototypes from separate allocations
+        let lhs_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let lhs_args = [i32::type_id().clone(), f64::type_id().clone()];
+        let lhs = fake_fn_prototype(&lhs_name, &lhs_args, None);
+
+        let rhs_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let rhs_args = [i32::type_id().clone(), f64::type_id().clone()];
+        let rhs = fake_fn_prototype(&rhs_name, &rhs_args, None);
+
+        assert!(lhs == rhs);
+        assert_eq!(hash(&lhs), hash(&rhs));
+        assert_eq!(
+            HashSet::from([lhs.clone(), rhs.clone()]).len(),
+            1,
+            "equal prototypes should be deduplicated"
+        );
+
+        let other_name = CString::new("other_fn").expect("Invalid fake fn name.");
+        let other = fake_fn_prototype(&other_name, &rhs_args, None);
+        assert!(lhs != other);
+
+        let other_args = [i32::type_id().clone()];
+        let other = fake_fn_prototype(&rhs_name, &other_args, None);
+        assert!(lhs != other);
+    }
+
+    #[test]
+    fn test_fn_definition_downcast() {
+        extern "C" fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let arg_types = &[i32::type_id().clone(), i32::type_id().clone()];
+        let fn_definition = FunctionDefinition {
+            prototype: fake_fn_prototype(&fn_name, arg_types, Some(i32::type_id().clone())),
+            fn_ptr: add as *const c_void,
+            is_extern: false,
+        };
+
+        let add_fn = unsafe { fn_definition.downcast::<extern "C" fn(i32, i32) -> i32>() }
+            .expect("signature should match");
+        assert_eq!(add_fn(1, 2), 3);
+
+        assert!(matches!(
+            unsafe { fn_definition.downcast::<extern "C" fn(i32, f64) -> i32>() },
+            Err(DowncastError::SignatureMismatch { .. })
+        ));
+        assert!(matches!(
+            unsafe { fn_definition.downcast::<extern "C" fn(i32, i32)>() },
+            Err(DowncastError::SignatureMismatch { .. })
+        ));
+
+        let null_definition = FunctionDefinition {
+            fn_ptr: ptr::null(),
+            ..fn_definition
+        };
+        assert_eq!(
+            unsafe { null_definition.downcast::<extern "C" fn(i32, i32) -> i32>() },
+            Err(DowncastError::NullFunctionPointer {
+                function: FAKE_FN_NAME.to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_fn_definition_to_function_pointer() {
+        extern "C" fn negate(a: f64) -> f64 {
+            -a
+        }
+
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let arg_types = &[f64::type_id().clone()];
+        let fn_definition = FunctionDefinition {
+            prototype: fake_fn_prototype(&fn_name, arg_types, Some(f64::type_id().clone())),
+            fn_ptr: negate as *const c_void,
+            is_extern: false,
+        };
+
+        let negate_fn = unsafe { fn_definition.to_function_pointer::<extern "C" fn(f64) -> f64>() };
+        assert_eq!(negate_fn(2.0), -2.0);
+    }
+
+    #[test]
+    fn test_fn_signature_matches_rust_fn_no_return() {
+        let fn_signature = fake_fn_signature(&[], None);
+
+        assert!(fn_signature.matches_rust_fn::<fn()>());
+        assert!(!fn_signature.matches_rust_fn::<fn() -> i32>());
+    }
 }
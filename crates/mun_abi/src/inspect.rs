@@ -0,0 +1,178 @@
+//! Utilities to inspect the contents of an assembly without linking it.
+
+use std::io;
+
+use crate::{AssemblyInfo, TypeDefinitionData, TypeId};
+
+/// Serializes the complete `info`, including all function and type metadata,
+/// to a JSON string.
+pub fn to_json(info: &AssemblyInfo<'_>) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(info)
+}
+
+/// Writes a compact, human-readable summary of `info` to `writer`.
+pub fn print_assembly_summary(
+    info: &AssemblyInfo<'_>,
+    writer: &mut dyn io::Write,
+) -> io::Result<()> {
+    // Prefer the debug names stored in the type lookup table over raw type IDs
+    let type_name = |type_id: &TypeId<'_>| {
+        info.type_lut
+            .iter()
+            .find(|(id, _, _)| *id == type_id)
+            .map_or_else(|| type_id.to_string(), |(_, _, name)| name.to_owned())
+    };
+
+    writeln!(writer, "assembly `{}`", info.symbols.path())?;
+
+    let dependencies: Vec<&str> = info.dependencies().collect();
+    writeln!(writer, "  dependencies ({}):", dependencies.len())?;
+    for dependency in dependencies {
+        writeln!(writer, "    {dependency}")?;
+    }
+
+    let functions = info.symbols.functions();
+    writeln!(writer, "  functions ({}):", functions.len())?;
+    for function in functions {
+        let signature = &function.prototype.signature;
+        let arg_types = signature
+            .arg_types()
+            .iter()
+            .map(type_name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(writer, "    fn {}({arg_types})", function.prototype.name())?;
+        if let Some(return_type) = signature.return_type() {
+            write!(writer, " -> {}", type_name(&return_type))?;
+        }
+        writeln!(writer)?;
+    }
+
+    let types = info.symbols.types();
+    writeln!(writer, "  types ({}):", types.len())?;
+    for ty in types {
+        match &ty.data {
+            TypeDefinitionData::Struct(s) => {
+                writeln!(
+                    writer,
+                    "    struct {} ({} bytes, align {}, {:?})",
+                    ty.name(),
+                    ty.size_in_bytes(),
+                    ty.alignment(),
+                    s.memory_kind
+                )?;
+                for ((name, field_type), offset) in
+                    s.field_names().zip(s.field_types()).zip(s.field_offsets())
+                {
+                    writeln!(writer, "      {name}: {} @ {offset}", type_name(field_type))?;
+                }
+            }
+        }
+    }
+
+    writeln!(
+        writer,
+        "  dispatch table: {} entries",
+        info.dispatch_table.num_entries
+    )?;
+    writeln!(writer, "  type lut: {} entries", info.type_lut.num_entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::CString, ptr};
+
+    use super::{print_assembly_summary, to_json};
+    use crate::{
+        test_utils::{
+            fake_assembly_info, fake_dispatch_table, fake_fn_prototype, fake_module_info,
+            fake_struct_definition, fake_type_definition, fake_type_lut, FAKE_DEPENDENCY,
+            FAKE_FIELD_NAME, FAKE_FN_NAME, FAKE_MODULE_PATH, FAKE_STRUCT_NAME,
+        },
+        type_id::HasStaticTypeId,
+        FunctionDefinition, OwnedStructDefinition, StructMemoryKind, TypeDefinitionData,
+    };
+
+    #[test]
+    fn test_assembly_info_inspect() {
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let arg_types = [i32::type_id().clone()];
+        let functions = [FunctionDefinition {
+            prototype: fake_fn_prototype(&fn_name, &arg_types, None),
+            fn_ptr: ptr::null(),
+            is_extern: false,
+        }];
+
+        let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name.");
+        let field_name = CString::new(FAKE_FIELD_NAME).expect("Invalid fake field name.");
+        let field_names = [field_name.as_ptr()];
+        let field_types = [i32::type_id().clone()];
+        let types = [fake_type_definition(
+            &struct_name,
+            32,
+            4,
+            TypeDefinitionData::Struct(unsafe {
+                fake_struct_definition(
+                    &struct_name,
+                    &field_names,
+                    &field_types,
+                    &[0],
+                    StructMemoryKind::Value,
+                )
+            }),
+        )];
+        let module = fake_module_info(&module_path, &functions, &types);
+
+        let i32_name = CString::new("core::i32").expect("Invalid fake type name.");
+        let type_ids = [i32::type_id().clone()];
+        let mut type_ptrs = [ptr::null()];
+        let type_names = [i32_name.as_ptr()];
+        let type_lut = unsafe { fake_type_lut(&type_ids, &mut type_ptrs, &type_names) };
+        let dispatch_table = fake_dispatch_table(&[], &mut []);
+
+        let dependency = CString::new(FAKE_DEPENDENCY).expect("Invalid fake dependency.");
+        let dependencies = [dependency.as_ptr()];
+        let assembly =
+            unsafe { fake_assembly_info(module, dispatch_table, type_lut, &dependencies) };
+
+        let json = to_json(&assembly).expect("failed to serialize assembly");
+        let value: serde_json::Value = serde_json::from_str(&json).expect("invalid JSON");
+        assert_eq!(value["symbols"]["path"], FAKE_MODULE_PATH);
+        assert_eq!(
+            value["symbols"]["functions"][0]["prototype"]["name"],
+            FAKE_FN_NAME
+        );
+        assert_eq!(value["symbols"]["types"][0]["name"], FAKE_STRUCT_NAME);
+        assert_eq!(value["type_lut"][0]["name"], "core::i32");
+        assert_eq!(value["dependencies"][0], FAKE_DEPENDENCY);
+
+        // Struct definitions can be read back from the JSON
+        let struct_info: OwnedStructDefinition =
+            serde_json::from_value(value["symbols"]["types"][0]["data"]["Struct"].clone())
+                .expect("failed to deserialize struct definition");
+        let struct_info = struct_info.as_struct_definition();
+        let expected = types[0].as_struct().unwrap();
+        assert_eq!(struct_info.guid, expected.guid);
+        assert!(struct_info.iter_fields().eq(expected.iter_fields()));
+
+        let mut summary = Vec::new();
+        print_assembly_summary(&assembly, &mut summary).expect("failed to write summary");
+        assert_eq!(
+            String::from_utf8(summary).unwrap(),
+            format!(
+                "assembly `{FAKE_MODULE_PATH}`
+  dependencies (1):
+    {FAKE_DEPENDENCY}
+  functions (1):
+    fn {FAKE_FN_NAME}(core::i32)
+  types (1):
+    struct {FAKE_STRUCT_NAME} (4 bytes, align 4, Value)
+      {FAKE_FIELD_NAME}: core::i32 @ 0
+  dispatch table: 0 entries
+  type lut: 1 entries
+"
+            )
+        );
+    }
+}
@@ -6,20 +6,29 @@
 
 use std::{ffi::CStr, fmt};
 
-pub use assembly_info::AssemblyInfo;
+pub use assembly_info::{AssemblyError, AssemblyInfo};
 pub use dispatch_table::DispatchTable;
-pub use function_info::{FunctionDefinition, FunctionPrototype, FunctionSignature};
-pub use module_info::ModuleInfo;
+pub use function_info::{
+    DowncastError, ExternFnPointer, FunctionDefinition, FunctionPrototype, FunctionSignature,
+    HasStaticFnSignature,
+};
+pub use module_info::{AssemblyDependency, ModuleInfo};
 pub use primitive::PrimitiveType;
-pub use struct_info::{StructDefinition, StructMemoryKind};
+#[cfg(feature = "serde")]
+pub use struct_info::OwnedStructDefinition;
+pub use struct_info::{FieldInfo, StructDefinition, StructMemoryKind};
 pub use type_id::{ArrayTypeId, HasStaticTypeId, PointerTypeId, TypeId};
 pub use type_info::{HasStaticTypeName, TypeDefinition, TypeDefinitionData};
-pub use type_lut::TypeLut;
+pub use type_lut::{OwnedTypeLut, TypeLut, TypeLutMergeError};
+pub use version::{check_abi_version, AbiVersionError};
 
-// C bindings can be manually generated by running `cargo gen-abi`.
+// C bindings can be manually generated by running `cargo gen-abi`. The
+// `abi_is_fresh` test of the `tools` crate fails if they are out of date.
 mod assembly_info;
 mod dispatch_table;
 mod function_info;
+#[cfg(feature = "inspect")]
+pub mod inspect;
 mod module_info;
 mod primitive;
 pub mod static_type_map;
@@ -27,13 +36,15 @@ mod struct_info;
 mod type_id;
 mod type_info;
 mod type_lut;
+mod verify_layout;
+pub mod version;
 
-#[cfg(test)]
-mod test_utils;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 
 /// Defines the current ABI version
 #[allow(clippy::zero_prefixed_literal)]
-pub const ABI_VERSION: u32 = 00_03_00;
+pub const ABI_VERSION: u32 = 01_00_00;
 /// Defines the name for the `get_info` function
 pub const GET_INFO_FN_NAME: &str = "get_info";
 /// Defines the name for the `get_version` function
@@ -56,6 +67,159 @@ impl Guid {
     pub fn from_cstr(str: &CStr) -> Guid {
         Guid(extendhash::md5::compute_hash(str.to_bytes()))
     }
+
+    /// Combines two GUIDs into a new GUID by XOR-ing their bytes.
+    pub const fn xor_combine(a: Guid, b: Guid) -> Guid {
+        let mut result = [0; 16];
+        let mut i = 0;
+        while i < 16 {
+            result[i] = a.0[i] ^ b.0[i];
+            i += 1;
+        }
+        Guid(result)
+    }
+
+    /// Create a GUID for `name` within the namespace identified by
+    /// `namespace_guid`, as specified for version 5 UUIDs in RFC 4122.
+    pub const fn namespace(namespace_guid: Guid, name: &str) -> Guid {
+        let hash = sha1_with_prefix(&namespace_guid.0, name.as_bytes());
+
+        let mut result = [0; 16];
+        let mut i = 0;
+        while i < 16 {
+            result[i] = hash[i];
+            i += 1;
+        }
+
+        // Set the version (5) and the variant (RFC 4122)
+        result[6] = (result[6] & 0x0f) | 0x50;
+        result[8] = (result[8] & 0x3f) | 0x80;
+        Guid(result)
+    }
+
+    /// Create a random GUID, as specified for version 4 UUIDs in RFC 4122.
+    #[cfg(feature = "rand")]
+    pub fn random() -> Guid {
+        use rand::Rng;
+
+        let mut result: [u8; 16] = rand::thread_rng().gen();
+
+        // Set the version (4) and the variant (RFC 4122)
+        result[6] = (result[6] & 0x0f) | 0x40;
+        result[8] = (result[8] & 0x3f) | 0x80;
+        Guid(result)
+    }
+
+    /// Returns the UUID version of the GUID, stored in the four most
+    /// significant bits of byte 6.
+    ///
+    /// GUIDs created with [`Guid::namespace`] report version 5. GUIDs created
+    /// from a plain MD5 hash, like those returned by [`Guid::from_str`], do
+    /// not set the version bits, so their version is arbitrary.
+    pub const fn version(&self) -> u8 {
+        self.0[6] >> 4
+    }
+
+    /// Returns the UUID variant of the GUID, stored in the three most
+    /// significant bits of byte 8.
+    ///
+    /// RFC 4122 GUIDs have a variant of `0b10x`. Only GUIDs created with
+    /// [`Guid::namespace`] are guaranteed to conform to this; other Mun GUIDs
+    /// are raw hashes that do not set the variant bits.
+    pub const fn variant(&self) -> u8 {
+        self.0[8] >> 5
+    }
+}
+
+/// Computes the SHA-1 hash of the concatenation of `prefix` and `data`.
+///
+/// Const functions cannot allocate, so the padded message is never
+/// materialized; instead every byte is computed on demand.
+const fn sha1_with_prefix(prefix: &[u8; 16], data: &[u8]) -> [u8; 20] {
+    const fn message_byte(prefix: &[u8; 16], data: &[u8], padded_len: usize, i: usize) -> u8 {
+        let len = prefix.len() + data.len();
+        if i < prefix.len() {
+            prefix[i]
+        } else if i < len {
+            data[i - prefix.len()]
+        } else if i == len {
+            0x80
+        } else if i >= padded_len - 8 {
+            let bit_len = (len as u64) * 8;
+            (bit_len >> ((padded_len - 1 - i) * 8)) as u8
+        } else {
+            0
+        }
+    }
+
+    let len = prefix.len() + data.len();
+    let padded_len = (len + 8) / 64 * 64 + 64;
+
+    let mut state: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+    let mut block = 0;
+    while block < padded_len {
+        let mut w = [0u32; 80];
+        let mut t = 0;
+        while t < 16 {
+            let base = block + t * 4;
+            w[t] = u32::from_be_bytes([
+                message_byte(prefix, data, padded_len, base),
+                message_byte(prefix, data, padded_len, base + 1),
+                message_byte(prefix, data, padded_len, base + 2),
+                message_byte(prefix, data, padded_len, base + 3),
+            ]);
+            t += 1;
+        }
+        while t < 80 {
+            w[t] = (w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16]).rotate_left(1);
+            t += 1;
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        t = 0;
+        while t < 80 {
+            let (f, k) = if t < 20 {
+                ((b & c) | (!b & d), 0x5a827999)
+            } else if t < 40 {
+                (b ^ c ^ d, 0x6ed9eba1)
+            } else if t < 60 {
+                ((b & c) | (b & d) | (c & d), 0x8f1bbcdc)
+            } else {
+                (b ^ c ^ d, 0xca62c1d6)
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(w[t]);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+            t += 1;
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        state[4] = state[4].wrapping_add(e);
+        block += 64;
+    }
+
+    let mut hash = [0; 20];
+    let mut i = 0;
+    while i < 5 {
+        let bytes = state[i].to_be_bytes();
+        hash[i * 4] = bytes[0];
+        hash[i * 4 + 1] = bytes[1];
+        hash[i * 4 + 2] = bytes[2];
+        hash[i * 4 + 3] = bytes[3];
+        i += 1;
+    }
+    hash
 }
 
 impl fmt::Display for Guid {
@@ -110,10 +274,29 @@ impl serde::Serialize for Guid {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Guid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let hyphenated = String::deserialize(deserializer)?;
+        let digits: String = hyphenated.chars().filter(|c| *c != '-').collect();
+        if digits.len() != 32 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(D::Error::custom(format!("invalid GUID `{hyphenated}`")));
+        }
+
+        let value = u128::from_str_radix(&digits, 16).map_err(D::Error::custom)?;
+        Ok(Guid(value.to_be_bytes()))
+    }
+}
+
 /// Represents the privacy level of modules, functions, or variables.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Privacy {
     /// Publicly (and privately) accessible
     Public = 0,
@@ -121,5 +304,162 @@ pub enum Privacy {
     Private = 1,
 }
 
+impl Privacy {
+    /// Returns true if an item with this privacy level can be accessed from a
+    /// context with the `caller` privacy level. Public items are always
+    /// accessible, private items are only accessible from within the same
+    /// module.
+    pub fn is_accessible_from(self, caller: Privacy) -> bool {
+        match self {
+            Privacy::Public => true,
+            Privacy::Private => caller == Privacy::Private,
+        }
+    }
+
+    /// Returns the most restrictive of the two privacy levels.
+    pub fn most_restrictive(a: Privacy, b: Privacy) -> Privacy {
+        if a == Privacy::Private || b == Privacy::Private {
+            Privacy::Private
+        } else {
+            Privacy::Public
+        }
+    }
+
+    /// Returns the least restrictive of the two privacy levels.
+    pub fn least_restrictive(a: Privacy, b: Privacy) -> Privacy {
+        if a == Privacy::Public || b == Privacy::Public {
+            Privacy::Public
+        } else {
+            Privacy::Private
+        }
+    }
+}
+
 // TODO: Fix leakage of pointer types in struct fields due to integration tests
 // and test utils
+
+#[cfg(test)]
+mod send_sync {
+    use static_assertions::{assert_impl_all, assert_not_impl_any};
+
+    use crate::{
+        AssemblyInfo, DispatchTable, FunctionDefinition, FunctionPrototype, FunctionSignature,
+        Guid, ModuleInfo, StructDefinition, TypeDefinition, TypeId, TypeLut,
+    };
+
+    // Assembly information is immutable once loaded and is shared between the
+    // watcher thread and the main thread.
+    assert_impl_all!(Guid: Send, Sync);
+    assert_impl_all!(TypeId<'static>: Send, Sync);
+    assert_impl_all!(AssemblyInfo<'static>: Send, Sync);
+    assert_impl_all!(ModuleInfo<'static>: Send, Sync);
+    assert_impl_all!(FunctionDefinition<'static>: Send, Sync);
+    assert_impl_all!(FunctionPrototype<'static>: Send, Sync);
+    assert_impl_all!(FunctionSignature<'static>: Send, Sync);
+    assert_impl_all!(TypeDefinition<'static>: Send, Sync);
+
+    // The function pointers and type handles of dispatch tables and type LUTs
+    // are written by the runtime while linking, so they must not be shared
+    // between threads.
+    assert_not_impl_any!(DispatchTable<'static>: Send, Sync);
+    assert_not_impl_any!(TypeLut<'static>: Send, Sync);
+
+    // A struct definition is only ever accessed through its owning
+    // `TypeDefinition`, which is `Send` and `Sync`.
+    assert_not_impl_any!(StructDefinition<'static>: Send, Sync);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Guid, Privacy};
+
+    /// The namespace for fully-qualified domain names, as defined in RFC 4122
+    const NAMESPACE_DNS: Guid = Guid([
+        0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
+        0xc8,
+    ]);
+
+    /// The namespace for URLs, as defined in RFC 4122
+    const NAMESPACE_URL: Guid = Guid([
+        0x6b, 0xa7, 0xb8, 0x11, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
+        0xc8,
+    ]);
+
+    #[test]
+    fn test_guid_xor_combine() {
+        let a = Guid::from_str("foo");
+        let b = Guid::from_str("bar");
+        let combined = Guid::xor_combine(a, b);
+
+        assert_eq!(combined, Guid::xor_combine(b, a));
+        assert_eq!(Guid::xor_combine(combined, b), a);
+        assert_eq!(Guid::xor_combine(a, a), Guid([0; 16]));
+    }
+
+    #[test]
+    fn test_guid_namespace() {
+        const PYTHON_ORG: Guid = Guid::namespace(NAMESPACE_DNS, "python.org");
+        assert_eq!(
+            PYTHON_ORG.to_string(),
+            "886313e1-3b8a-5372-9b90-0c9aee199e5d"
+        );
+        assert_eq!(
+            Guid::namespace(NAMESPACE_DNS, "www.example.com").to_string(),
+            "2ed6657d-e927-568b-95e1-2665a8aea6a2"
+        );
+        assert_eq!(
+            Guid::namespace(NAMESPACE_URL, "").to_string(),
+            "1b4db7eb-4057-5ddf-91e0-36dec72071f5"
+        );
+
+        // A name spanning multiple SHA-1 blocks
+        assert_eq!(
+            Guid::namespace(NAMESPACE_DNS, &"a".repeat(100)).to_string(),
+            "56596f37-716c-57a9-a735-2561f8608390"
+        );
+    }
+
+    #[test]
+    fn test_guid_version_and_variant() {
+        let guid = Guid::namespace(NAMESPACE_DNS, "python.org");
+        assert_eq!(guid.version(), 5);
+        assert_eq!(guid.variant() >> 1, 0b10);
+
+        assert_eq!(NAMESPACE_DNS.version(), 1);
+        assert_eq!(NAMESPACE_DNS.variant(), 0b100);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_guid_random() {
+        let a = Guid::random();
+        let b = Guid::random();
+        assert_ne!(a, b);
+
+        assert_eq!(a.version(), 4);
+        assert_eq!(a.variant() >> 1, 0b10);
+    }
+
+    #[test]
+    fn test_privacy_is_accessible_from() {
+        assert!(Privacy::Public.is_accessible_from(Privacy::Public));
+        assert!(Privacy::Public.is_accessible_from(Privacy::Private));
+        assert!(!Privacy::Private.is_accessible_from(Privacy::Public));
+        assert!(Privacy::Private.is_accessible_from(Privacy::Private));
+    }
+
+    #[test]
+    fn test_privacy_restrictiveness() {
+        use Privacy::{Private, Public};
+
+        assert_eq!(Privacy::most_restrictive(Public, Public), Public);
+        assert_eq!(Privacy::most_restrictive(Public, Private), Private);
+        assert_eq!(Privacy::most_restrictive(Private, Public), Private);
+        assert_eq!(Privacy::most_restrictive(Private, Private), Private);
+
+        assert_eq!(Privacy::least_restrictive(Public, Public), Public);
+        assert_eq!(Privacy::least_restrictive(Public, Private), Public);
+        assert_eq!(Privacy::least_restrictive(Private, Public), Public);
+        assert_eq!(Privacy::least_restrictive(Private, Private), Private);
+    }
+}
@@ -1,6 +1,17 @@
 use std::{ffi::CStr, os::raw::c_char, slice, str};
 
-use crate::{FunctionDefinition, TypeDefinition};
+use crate::{FunctionDefinition, Guid, TypeDefinition};
+
+/// Represents a dependency of a module on another assembly.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AssemblyDependency {
+    /// The GUID of the assembly that is depended upon
+    pub guid: Guid,
+    /// The minimum ABI version the dependency must support
+    pub min_abi_version: u32,
+}
 
 /// Represents a module declaration.
 #[repr(C)]
@@ -11,10 +22,14 @@ pub struct ModuleInfo<'a> {
     pub(crate) functions: *const FunctionDefinition<'a>,
     /// Module types
     pub(crate) types: *const TypeDefinition<'a>,
+    /// Assemblies referenced by the module's types
+    pub(crate) dependencies: *const AssemblyDependency,
     /// Number of module functions
     pub num_functions: u32,
     /// Number of module types
     pub num_types: u32,
+    /// Number of assembly dependencies
+    pub num_dependencies: u32,
 }
 
 impl<'a> ModuleInfo<'a> {
@@ -55,6 +70,22 @@ impl<'a> ModuleInfo<'a> {
             unsafe { slice::from_raw_parts(self.types, self.num_types as usize) }
         }
     }
+
+    /// Returns the type with the specified `name`, if the module contains it.
+    ///
+    /// This performs a linear search through the module's types.
+    pub fn type_by_name(&self, name: &str) -> Option<&TypeDefinition<'a>> {
+        self.types().iter().find(|ty| ty.name() == name)
+    }
+
+    /// Returns the assemblies that the module's types reference.
+    pub fn dependencies(&self) -> &[AssemblyDependency] {
+        if self.num_dependencies == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.dependencies, self.num_dependencies as usize) }
+        }
+    }
 }
 
 unsafe impl Send for ModuleInfo<'_> {}
@@ -68,10 +99,11 @@ impl serde::Serialize for ModuleInfo<'_> {
     {
         use serde::ser::SerializeStruct;
 
-        let mut s = serializer.serialize_struct("ModuleInfo", 3)?;
+        let mut s = serializer.serialize_struct("ModuleInfo", 4)?;
         s.serialize_field("path", self.path())?;
         s.serialize_field("functions", self.functions())?;
         s.serialize_field("types", self.types())?;
+        s.serialize_field("dependencies", self.dependencies())?;
         s.end()
     }
 }
@@ -86,7 +118,8 @@ mod tests {
             FAKE_FN_NAME, FAKE_MODULE_PATH, FAKE_STRUCT_NAME,
         },
         type_id::HasStaticTypeId,
-        FunctionDefinition, StructMemoryKind, TypeDefinition, TypeDefinitionData,
+        AssemblyDependency, FunctionDefinition, Guid, StructMemoryKind, TypeDefinition,
+        TypeDefinitionData, ABI_VERSION,
     };
 
     #[test]
@@ -106,6 +139,62 @@ mod tests {
 
         assert_eq!(module.functions().len(), functions.len());
         assert_eq!(module.types().len(), types.len());
+        assert!(module.dependencies().is_empty());
+    }
+
+    #[test]
+    fn test_module_info_dependencies() {
+        let dependencies = [
+            AssemblyDependency {
+                guid: Guid::from_str("foo"),
+                min_abi_version: ABI_VERSION,
+            },
+            AssemblyDependency {
+                guid: Guid::from_str("bar"),
+                min_abi_version: 0,
+            },
+        ];
+
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let mut module = fake_module_info(&module_path, &[], &[]);
+        module.dependencies = dependencies.as_ptr();
+        module.num_dependencies = dependencies.len() as u32;
+
+        assert_eq!(module.dependencies(), &dependencies);
+    }
+
+    #[test]
+    fn test_module_info_type_by_name() {
+        const NUM_TYPES: usize = 500;
+
+        let type_names: Vec<CString> = (0..NUM_TYPES)
+            .map(|i| {
+                CString::new(format!("{FAKE_STRUCT_NAME}{i}")).expect("Invalid fake struct name")
+            })
+            .collect();
+        let types: Vec<TypeDefinition<'_>> = type_names
+            .iter()
+            .map(|name| {
+                let struct_info = unsafe {
+                    fake_struct_definition(name, &[], &[], &[], StructMemoryKind::default())
+                };
+                fake_type_definition(name, 1, 1, TypeDefinitionData::Struct(struct_info))
+            })
+            .collect();
+
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let module = fake_module_info(&module_path, &[], &types);
+
+        for name in [0, 1, NUM_TYPES / 2, NUM_TYPES - 1].map(|i| format!("{FAKE_STRUCT_NAME}{i}")) {
+            let ty = module.type_by_name(&name).expect("type should exist");
+            assert_eq!(ty.name(), name);
+            assert_eq!(ty.as_concrete(), &Guid::from_str(&name));
+        }
+
+        assert!(module
+            .type_by_name(&format!("{FAKE_STRUCT_NAME}{NUM_TYPES}"))
+            .is_none());
+        assert!(module.type_by_name("").is_none());
     }
 
     #[test]
@@ -117,12 +206,14 @@ mod tests {
         let fn_info = FunctionDefinition {
             prototype: fn_prototype,
             fn_ptr: ptr::null(),
+            is_extern: false,
         };
         let functions = &[fn_info];
 
         let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name");
-        let struct_info =
-            fake_struct_definition(&struct_name, &[], &[], &[], StructMemoryKind::default());
+        let struct_info = unsafe {
+            fake_struct_definition(&struct_name, &[], &[], &[], StructMemoryKind::default())
+        };
         let type_info =
             fake_type_definition(&struct_name, 1, 1, TypeDefinitionData::Struct(struct_info));
         let types = [type_info];
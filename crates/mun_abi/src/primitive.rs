@@ -3,12 +3,39 @@
 use crate::{Guid, HasStaticTypeId, TypeId};
 
 /// Defines functions for built-in types like f32, i32, etc.
-pub trait PrimitiveType: HasStaticTypeId {
+///
+/// Trait methods cannot be `const fn`, so the layout and Rust type name are
+/// also exposed as associated constants for use in `const` contexts.
+pub trait PrimitiveType: HasStaticTypeId + Sized {
+    /// The size of the type in bytes
+    const SIZE: usize = std::mem::size_of::<Self>();
+
+    /// The alignment of the type in bytes
+    const ALIGNMENT: usize = std::mem::align_of::<Self>();
+
+    /// The name of the equivalent Rust type
+    const RUST_TYPE_NAME: &'static str;
+
     /// Returns the name of the type
     fn name() -> &'static str;
 
     /// Returns the GUID of the type
     fn guid() -> &'static Guid;
+
+    /// Returns the name of the equivalent Rust type
+    fn to_rust_type_name() -> &'static str {
+        Self::RUST_TYPE_NAME
+    }
+
+    /// Returns the size of the type in bytes
+    fn size_of() -> usize {
+        Self::SIZE
+    }
+
+    /// Returns the alignment of the type in bytes
+    fn align_of() -> usize {
+        Self::ALIGNMENT
+    }
 }
 
 macro_rules! define_primitives {
@@ -16,12 +43,14 @@ macro_rules! define_primitives {
         $(
             impl HasStaticTypeId for $ty {
                 fn type_id() -> &'static $crate::TypeId<'static> {
-                    const TYPE_ID: $crate::TypeId<'static> = $crate::TypeId::Concrete(Guid::from_str($name));
+                    const TYPE_ID: $crate::TypeId<'static> = $crate::TypeId::from_type_name($name);
                     &TYPE_ID
                 }
             }
 
             impl PrimitiveType for $ty {
+                const RUST_TYPE_NAME: &'static str = stringify!($ty);
+
                 fn name() -> &'static str {
                     const TYPE_NAME: &str = $name;
                     TYPE_NAME
@@ -56,6 +85,8 @@ define_primitives! {
 
 #[cfg(target_pointer_width = "64")]
 impl PrimitiveType for usize {
+    const RUST_TYPE_NAME: &'static str = "usize";
+
     fn name() -> &'static str {
         u64::name()
     }
@@ -73,6 +104,8 @@ impl HasStaticTypeId for usize {
 
 #[cfg(target_pointer_width = "64")]
 impl PrimitiveType for isize {
+    const RUST_TYPE_NAME: &'static str = "isize";
+
     fn name() -> &'static str {
         i64::name()
     }
@@ -90,6 +123,8 @@ impl HasStaticTypeId for isize {
 
 #[cfg(target_pointer_width = "32")]
 impl PrimitiveType for usize {
+    const RUST_TYPE_NAME: &'static str = "usize";
+
     fn name() -> &'static str {
         u32::name()
     }
@@ -107,6 +142,8 @@ impl HasStaticTypeId for usize {
 
 #[cfg(target_pointer_width = "32")]
 impl PrimitiveType for isize {
+    const RUST_TYPE_NAME: &'static str = "isize";
+
     fn name() -> &'static str {
         i32::name()
     }
@@ -121,3 +158,52 @@ impl HasStaticTypeId for isize {
         i32::type_id()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PrimitiveType;
+
+    #[test]
+    fn layout() {
+        macro_rules! assert_layout {
+            ($($ty:ty),*) => {
+                $(
+                    assert_eq!(<$ty>::size_of(), std::mem::size_of::<$ty>());
+                    assert_eq!(<$ty>::align_of(), std::mem::align_of::<$ty>());
+                    assert_eq!(<$ty>::to_rust_type_name(), stringify!($ty));
+                )*
+            };
+        }
+
+        assert_layout!(
+            i8,
+            i16,
+            i32,
+            i64,
+            i128,
+            u8,
+            u16,
+            u32,
+            u64,
+            u128,
+            f32,
+            f64,
+            bool,
+            (),
+            usize,
+            isize
+        );
+        assert_eq!(std::ffi::c_void::to_rust_type_name(), "std::ffi::c_void");
+    }
+
+    #[test]
+    fn const_layout() {
+        const I64_SIZE: usize = <i64 as PrimitiveType>::SIZE;
+        const F32_ALIGNMENT: usize = <f32 as PrimitiveType>::ALIGNMENT;
+        const USIZE_NAME: &str = <usize as PrimitiveType>::RUST_TYPE_NAME;
+
+        assert_eq!(I64_SIZE, std::mem::size_of::<i64>());
+        assert_eq!(F32_ALIGNMENT, std::mem::align_of::<f32>());
+        assert_eq!(USIZE_NAME, "usize");
+    }
+}
@@ -26,10 +26,23 @@ impl<T: 'static> StaticTypeMap<T> {
     where
         Type: 'static,
         Init: FnOnce() -> T,
+    {
+        self.get_or_insert_with(TypeId::of::<Type>(), f)
+    }
+
+    /// Returns the static value corresponding to the provided `key`, or
+    /// initializes it with `f` if no value exists yet.
+    ///
+    /// Even when called concurrently from multiple threads, `f` is only called
+    /// once for every `key`. Like [`StaticTypeMap::call_once`], the
+    /// initialized value stays on the heap until the program terminates.
+    pub fn get_or_insert_with<Init>(&'static self, key: TypeId, f: Init) -> &'static T
+    where
+        Init: FnOnce() -> T,
     {
         // If already initialized, just return stored value
         let map = self.map.lock();
-        if let Some(r) = map.borrow().get(&TypeId::of::<Type>()) {
+        if let Some(r) = map.borrow().get(&key) {
             return r;
         }
 
@@ -37,7 +50,7 @@ impl<T: 'static> StaticTypeMap<T> {
         let reference = Box::leak(Box::new(f()));
 
         // Insert the value into the map
-        let old = map.borrow_mut().insert(TypeId::of::<Type>(), reference);
+        let old = map.borrow_mut().insert(key, reference);
         assert!(
             old.is_none(),
             "StaticTypeMap value was reinitialized. This is a bug."
@@ -45,3 +58,51 @@ impl<T: 'static> StaticTypeMap<T> {
         reference
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        any::TypeId,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Barrier,
+        },
+        thread,
+    };
+
+    use once_cell::sync::Lazy;
+
+    use super::StaticTypeMap;
+
+    #[test]
+    fn get_or_insert_with_concurrent() {
+        const NUM_THREADS: usize = 8;
+
+        static MAP: Lazy<StaticTypeMap<usize>> = Lazy::new(StaticTypeMap::default);
+        static NUM_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        let barrier = Barrier::new(NUM_THREADS);
+        let values = thread::scope(|s| {
+            let handles = (0..NUM_THREADS)
+                .map(|_| {
+                    s.spawn(|| {
+                        barrier.wait();
+                        MAP.get_or_insert_with(TypeId::of::<u32>(), || {
+                            NUM_CALLS.fetch_add(1, Ordering::SeqCst);
+                            42
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("thread panicked"))
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(NUM_CALLS.load(Ordering::SeqCst), 1);
+        assert!(values.windows(2).all(|pair| std::ptr::eq(pair[0], pair[1])));
+        assert_eq!(*MAP.get_or_insert_with(TypeId::of::<u32>(), || 0), 42);
+    }
+}
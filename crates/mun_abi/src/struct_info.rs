@@ -1,6 +1,10 @@
+#[cfg(feature = "serde")]
+use std::ffi::CString;
 use std::{ffi::CStr, os::raw::c_char, slice, str};
 
 use crate::{type_id::TypeId, Guid};
+#[cfg(feature = "serde")]
+use crate::{ArrayTypeId, PointerTypeId};
 
 /// Represents a struct declaration.
 #[repr(C)]
@@ -23,10 +27,21 @@ pub struct StructDefinition<'a> {
     pub memory_kind: StructMemoryKind,
 }
 
+/// Describes a single field of a [`StructDefinition`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldInfo<'s, 'a> {
+    /// The name of the field
+    pub name: &'s str,
+    /// The type of the field
+    pub type_id: &'s TypeId<'a>,
+    /// The offset of the field in bytes from the start of the struct
+    pub offset: u16,
+}
+
 /// Represents the kind of memory management a struct uses.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StructMemoryKind {
     /// A garbage collected struct is allocated on the heap and uses reference
     /// semantics when passed around.
@@ -78,6 +93,51 @@ impl<'a> StructDefinition<'a> {
     pub fn num_fields(&self) -> usize {
         self.num_fields.into()
     }
+
+    /// Returns an iterator over information about the struct's fields, in
+    /// declaration order.
+    pub fn iter_fields(&self) -> impl Iterator<Item = FieldInfo<'_, 'a>> {
+        self.field_names()
+            .zip(self.field_types())
+            .zip(self.field_offsets())
+            .map(|((name, type_id), offset)| FieldInfo {
+                name,
+                type_id,
+                offset: *offset,
+            })
+    }
+
+    /// Returns information about the field at the specified `index`, or `None`
+    /// if `index` exceeds the number of fields.
+    pub fn field_at(&self, index: usize) -> Option<FieldInfo<'_, 'a>> {
+        if index >= self.num_fields() {
+            return None;
+        }
+
+        let name = unsafe { CStr::from_ptr(*self.field_names.add(index)) };
+        Some(FieldInfo {
+            name: unsafe { str::from_utf8_unchecked(name.to_bytes()) },
+            type_id: &self.field_types()[index],
+            offset: self.field_offsets()[index],
+        })
+    }
+
+    /// Returns information about the field with the specified `name`, if it
+    /// exists.
+    pub fn field_by_name(&self, name: &str) -> Option<FieldInfo<'_, 'a>> {
+        self.iter_fields().find(|field| field.name == name)
+    }
+
+    /// Returns information about the field with the specified `name`, without
+    /// checking whether it exists.
+    ///
+    /// # Safety
+    ///
+    /// The struct must contain a field with the specified `name`, otherwise
+    /// the behavior is undefined.
+    pub unsafe fn field_by_name_unchecked(&self, name: &str) -> FieldInfo<'_, 'a> {
+        self.field_by_name(name).unwrap_unchecked()
+    }
 }
 
 impl PartialEq for StructDefinition<'_> {
@@ -125,6 +185,144 @@ impl serde::Serialize for StructDefinition<'_> {
     }
 }
 
+/// A [`StructDefinition`] that owns the memory of its fields, e.g. the result
+/// of deserializing a [`StructDefinition`].
+///
+/// The field names, types, and offsets are stored by value; use
+/// [`OwnedStructDefinition::as_struct_definition`] to obtain a
+/// [`StructDefinition`] that references them.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub struct OwnedStructDefinition {
+    guid: Guid,
+    field_names: Vec<CString>,
+    field_name_ptrs: Vec<*const c_char>,
+    field_types: Vec<TypeId<'static>>,
+    field_offsets: Vec<u16>,
+    memory_kind: StructMemoryKind,
+    /// Heap allocations of the pointee and element types that are referenced
+    /// by `field_types`. They are freed when the definition is dropped.
+    nested_type_ids: Vec<*mut TypeId<'static>>,
+}
+
+#[cfg(feature = "serde")]
+impl OwnedStructDefinition {
+    /// Returns a [`StructDefinition`] that references the fields of this
+    /// definition.
+    pub fn as_struct_definition(&self) -> StructDefinition<'_> {
+        StructDefinition {
+            guid: self.guid,
+            field_names: self.field_name_ptrs.as_ptr(),
+            field_types: self.field_types.as_ptr(),
+            field_offsets: self.field_offsets.as_ptr(),
+            num_fields: self.field_types.len() as u16,
+            memory_kind: self.memory_kind,
+        }
+    }
+
+    /// Converts a deserialized type ID to a [`TypeId`], allocating the types
+    /// it references.
+    fn alloc_type_id(&mut self, type_id: TypeIdData) -> TypeId<'static> {
+        match type_id {
+            TypeIdData::Concrete(guid) => TypeId::Concrete(guid),
+            TypeIdData::Pointer { pointee, mutable } => TypeId::Pointer(PointerTypeId {
+                pointee: self.alloc_nested_type_id(*pointee),
+                mutable,
+            }),
+            TypeIdData::Array { element } => TypeId::Array(ArrayTypeId {
+                element: self.alloc_nested_type_id(*element),
+            }),
+        }
+    }
+
+    /// Allocates a type referenced by another type on the heap.
+    fn alloc_nested_type_id(&mut self, type_id: TypeIdData) -> &'static TypeId<'static> {
+        let type_id = Box::into_raw(Box::new(self.alloc_type_id(type_id)));
+        self.nested_type_ids.push(type_id);
+
+        // Safety: The allocation is only freed when `self` is dropped, and the
+        // `'static` lifetime never escapes `self`; `as_struct_definition`
+        // shortens it to the lifetime of the borrow.
+        unsafe { &*type_id }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Drop for OwnedStructDefinition {
+    fn drop(&mut self) {
+        for type_id in self.nested_type_ids.drain(..) {
+            // Safety: The pointer was created by `Box::into_raw` in
+            // `alloc_nested_type_id`.
+            drop(unsafe { Box::from_raw(type_id) });
+        }
+    }
+}
+
+/// The deserialized representation of a [`TypeId`], which owns the types it
+/// references.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+enum TypeIdData {
+    Concrete(Guid),
+    Pointer {
+        pointee: Box<TypeIdData>,
+        mutable: bool,
+    },
+    Array {
+        element: Box<TypeIdData>,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OwnedStructDefinition {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        struct Field {
+            name: String,
+            r#type: TypeIdData,
+            offset: u16,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct Definition {
+            guid: Guid,
+            fields: Vec<Field>,
+            memory_kind: StructMemoryKind,
+        }
+
+        let definition = Definition::deserialize(deserializer)?;
+        if u16::try_from(definition.fields.len()).is_err() {
+            return Err(D::Error::custom("too many struct fields"));
+        }
+
+        let mut owned = OwnedStructDefinition {
+            guid: definition.guid,
+            field_names: Vec::with_capacity(definition.fields.len()),
+            field_name_ptrs: Vec::with_capacity(definition.fields.len()),
+            field_types: Vec::with_capacity(definition.fields.len()),
+            field_offsets: Vec::with_capacity(definition.fields.len()),
+            memory_kind: definition.memory_kind,
+            nested_type_ids: Vec::new(),
+        };
+        for field in definition.fields {
+            owned
+                .field_names
+                .push(CString::new(field.name).map_err(D::Error::custom)?);
+            let type_id = owned.alloc_type_id(field.r#type);
+            owned.field_types.push(type_id);
+            owned.field_offsets.push(field.offset);
+        }
+        owned.field_name_ptrs = owned.field_names.iter().map(|n| n.as_ptr()).collect();
+
+        Ok(owned)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::ffi::CString;
@@ -140,17 +338,21 @@ mod tests {
         let field_names = &[];
         let field_types = &[];
         let field_offsets = &[];
-        let struct_info = fake_struct_definition(
-            &CString::new(FAKE_STRUCT_NAME).unwrap(),
-            field_names,
-            field_types,
-            field_offsets,
-            StructMemoryKind::default(),
-        );
+        let struct_info = unsafe {
+            fake_struct_definition(
+                &CString::new(FAKE_STRUCT_NAME).unwrap(),
+                field_names,
+                field_types,
+                field_offsets,
+                StructMemoryKind::default(),
+            )
+        };
 
         assert_eq!(struct_info.field_names().count(), 0);
         assert_eq!(struct_info.field_types(), field_types);
         assert_eq!(struct_info.field_offsets(), field_offsets);
+        assert_eq!(struct_info.iter_fields().count(), 0);
+        assert_eq!(struct_info.field_at(0), None);
     }
 
     #[test]
@@ -162,13 +364,15 @@ mod tests {
         let field_names = &[field_name.as_ptr()];
         let field_types = &[type_id.clone()];
         let field_offsets = &[1];
-        let struct_info = fake_struct_definition(
-            &struct_name,
-            field_names,
-            field_types,
-            field_offsets,
-            StructMemoryKind::default(),
-        );
+        let struct_info = unsafe {
+            fake_struct_definition(
+                &struct_name,
+                field_names,
+                field_types,
+                field_offsets,
+                StructMemoryKind::default(),
+            )
+        };
 
         assert_eq!(struct_info.num_fields(), 1);
         for (lhs, rhs) in struct_info.field_names().zip([FAKE_FIELD_NAME].iter()) {
@@ -178,11 +382,79 @@ mod tests {
         assert_eq!(struct_info.field_offsets(), field_offsets);
     }
 
+    #[test]
+    fn test_struct_info_field_by_name() {
+        let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name.");
+        let foo_name = CString::new("foo").expect("Invalid fake field name.");
+        let foobar_name = CString::new("foobar").expect("Invalid fake field name.");
+
+        let field_names = &[foobar_name.as_ptr(), foo_name.as_ptr()];
+        let field_types = &[i32::type_id().clone(), f64::type_id().clone()];
+        let field_offsets = &[0, 8];
+        let struct_info = unsafe {
+            fake_struct_definition(
+                &struct_name,
+                field_names,
+                field_types,
+                field_offsets,
+                StructMemoryKind::default(),
+            )
+        };
+
+        let foo = struct_info
+            .field_by_name("foo")
+            .expect("field should exist");
+        assert_eq!(foo.name, "foo");
+        assert_eq!(foo.type_id, f64::type_id());
+        assert_eq!(foo.offset, 8);
+
+        let foobar = unsafe { struct_info.field_by_name_unchecked("foobar") };
+        assert_eq!(foobar.name, "foobar");
+        assert_eq!(foobar.type_id, i32::type_id());
+        assert_eq!(foobar.offset, 0);
+
+        assert_eq!(struct_info.field_by_name("fo"), None);
+        assert_eq!(struct_info.field_by_name("foobarbaz"), None);
+    }
+
+    #[test]
+    fn test_struct_info_iter_fields() {
+        let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name.");
+        let foo_name = CString::new("foo").expect("Invalid fake field name.");
+        let bar_name = CString::new("bar").expect("Invalid fake field name.");
+
+        let field_names = &[foo_name.as_ptr(), bar_name.as_ptr()];
+        let field_types = &[i32::type_id().clone(), f64::type_id().clone()];
+        let field_offsets = &[0, 8];
+        let struct_info = unsafe {
+            fake_struct_definition(
+                &struct_name,
+                field_names,
+                field_types,
+                field_offsets,
+                StructMemoryKind::default(),
+            )
+        };
+
+        let fields = struct_info.iter_fields().collect::<Vec<_>>();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].name, "foo");
+        assert_eq!(fields[0].type_id, i32::type_id());
+        assert_eq!(fields[0].offset, 0);
+        assert_eq!(fields[1].name, "bar");
+        assert_eq!(fields[1].type_id, f64::type_id());
+        assert_eq!(fields[1].offset, 8);
+
+        assert_eq!(struct_info.field_at(1), Some(fields[1]));
+        assert_eq!(struct_info.field_at(2), None);
+    }
+
     #[test]
     fn test_struct_info_memory_kind_gc() {
         let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name.");
         let struct_memory_kind = StructMemoryKind::Gc;
-        let struct_info = fake_struct_definition(&struct_name, &[], &[], &[], struct_memory_kind);
+        let struct_info =
+            unsafe { fake_struct_definition(&struct_name, &[], &[], &[], struct_memory_kind) };
 
         assert_eq!(struct_info.memory_kind, struct_memory_kind);
     }
@@ -191,8 +463,58 @@ mod tests {
     fn test_struct_info_memory_kind_value() {
         let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name.");
         let struct_memory_kind = StructMemoryKind::Value;
-        let struct_info = fake_struct_definition(&struct_name, &[], &[], &[], struct_memory_kind);
+        let struct_info =
+            unsafe { fake_struct_definition(&struct_name, &[], &[], &[], struct_memory_kind) };
 
         assert_eq!(struct_info.memory_kind, struct_memory_kind);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_struct_definition_serde() {
+        use super::OwnedStructDefinition;
+        use crate::{ArrayTypeId, TypeId};
+
+        let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name.");
+        let field_names = ["a", "b", "c"].map(|name| CString::new(name).unwrap());
+        let field_name_ptrs = field_names.each_ref().map(|name| name.as_ptr());
+        let field_types = [
+            i32::type_id().clone(),
+            <*const f64>::type_id().clone(),
+            TypeId::Array(ArrayTypeId {
+                element: <*mut u8>::type_id(),
+            }),
+        ];
+        let field_offsets = [0, 8, 16];
+        let struct_info = unsafe {
+            fake_struct_definition(
+                &struct_name,
+                &field_name_ptrs,
+                &field_types,
+                &field_offsets,
+                StructMemoryKind::Value,
+            )
+        };
+
+        let serialized = serde_json::to_string(&struct_info).unwrap();
+        let owned: OwnedStructDefinition = serde_json::from_str(&serialized).unwrap();
+        let deserialized = owned.as_struct_definition();
+
+        assert_eq!(deserialized.guid, struct_info.guid);
+        assert_eq!(deserialized.memory_kind, struct_info.memory_kind);
+        assert!(deserialized.iter_fields().eq(struct_info.iter_fields()));
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), serialized);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_struct_memory_kind_serde() {
+        for memory_kind in [StructMemoryKind::Gc, StructMemoryKind::Value] {
+            let serialized = serde_json::to_string(&memory_kind).unwrap();
+            assert_eq!(
+                serde_json::from_str::<StructMemoryKind>(&serialized).unwrap(),
+                memory_kind
+            );
+        }
+    }
 }
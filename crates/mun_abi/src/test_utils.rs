@@ -1,6 +1,9 @@
+//! Helpers to construct fake ABI data for use in tests.
+
 use std::{
     ffi::{self, CStr},
     os::raw::c_char,
+    ptr,
 };
 
 use crate::{
@@ -9,21 +12,34 @@ use crate::{
     ModuleInfo, StructDefinition, StructMemoryKind, TypeDefinition, TypeDefinitionData, TypeLut,
 };
 
-pub(crate) const FAKE_TYPE_GUID: Guid =
-    Guid([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
-pub(crate) const FAKE_TYPE_ID: TypeId<'static> = TypeId::Concrete(FAKE_TYPE_GUID);
-pub(crate) const FAKE_DEPENDENCY: &str = "path/to/dependency.munlib";
-pub(crate) const FAKE_FIELD_NAME: &str = "field_name";
-pub(crate) const FAKE_FN_NAME: &str = "fn_name";
-pub(crate) const FAKE_MODULE_PATH: &str = "path::to::module";
-pub(crate) const FAKE_STRUCT_NAME: &str = "StructName";
-pub(crate) const FAKE_TYPE_NAME: &str = "TypeName";
+/// A GUID used for fake types
+pub const FAKE_TYPE_GUID: Guid = Guid([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+/// A [`TypeId`] used for fake types
+pub const FAKE_TYPE_ID: TypeId<'static> = TypeId::Concrete(FAKE_TYPE_GUID);
+/// A path used for fake assembly dependencies
+pub const FAKE_DEPENDENCY: &str = "path/to/dependency.munlib";
+/// A name used for fake struct fields
+pub const FAKE_FIELD_NAME: &str = "field_name";
+/// A name used for fake functions
+pub const FAKE_FN_NAME: &str = "fn_name";
+/// A path used for fake modules
+pub const FAKE_MODULE_PATH: &str = "path::to::module";
+/// A name used for fake structs
+pub const FAKE_STRUCT_NAME: &str = "StructName";
+/// A name used for fake types
+pub const FAKE_TYPE_NAME: &str = "TypeName";
 
-pub(crate) fn fake_assembly_info<'a>(
+/// Constructs an [`AssemblyInfo`] from its parts.
+///
+/// # Safety
+///
+/// Each of the `dependencies` must point to a valid nul-terminated string that
+/// outlives the returned [`AssemblyInfo`].
+pub unsafe fn fake_assembly_info<'a>(
     symbols: ModuleInfo<'a>,
     dispatch_table: DispatchTable<'a>,
     type_lut: TypeLut<'a>,
-    dependencies: &[*const c_char],
+    dependencies: &'a [*const c_char],
 ) -> AssemblyInfo<'a> {
     AssemblyInfo {
         symbols,
@@ -34,10 +50,17 @@ pub(crate) fn fake_assembly_info<'a>(
     }
 }
 
-pub(crate) fn fake_type_lut<'a>(
-    type_ids: &[TypeId<'a>],
-    type_handles: &mut [*const ffi::c_void],
-    type_names: &[*const c_char],
+/// Constructs a [`TypeLut`] that references the given slices.
+///
+/// # Safety
+///
+/// Accessing the names of the returned [`TypeLut`] is _undefined behavior_
+/// unless each of the `type_names` points to a valid nul-terminated string
+/// that outlives it.
+pub unsafe fn fake_type_lut<'a>(
+    type_ids: &'a [TypeId<'a>],
+    type_handles: &'a mut [*const ffi::c_void],
+    type_names: &'a [*const c_char],
 ) -> TypeLut<'a> {
     assert_eq!(type_ids.len(), type_handles.len());
 
@@ -49,9 +72,10 @@ pub(crate) fn fake_type_lut<'a>(
     }
 }
 
-pub(crate) fn fake_dispatch_table<'a>(
-    fn_prototypes: &[FunctionPrototype<'a>],
-    fn_ptrs: &mut [*const ffi::c_void],
+/// Constructs a [`DispatchTable`] that references the given slices.
+pub fn fake_dispatch_table<'a>(
+    fn_prototypes: &'a [FunctionPrototype<'a>],
+    fn_ptrs: &'a mut [*const ffi::c_void],
 ) -> DispatchTable<'a> {
     assert_eq!(fn_prototypes.len(), fn_ptrs.len());
 
@@ -62,8 +86,10 @@ pub(crate) fn fake_dispatch_table<'a>(
     }
 }
 
-pub(crate) fn fake_fn_signature<'a>(
-    arg_types: &[TypeId<'a>],
+/// Constructs a [`FunctionSignature`] that references the given argument
+/// types. A `return_type` of `None` denotes the unit type.
+pub fn fake_fn_signature<'a>(
+    arg_types: &'a [TypeId<'a>],
     return_type: Option<TypeId<'a>>,
 ) -> FunctionSignature<'a> {
     FunctionSignature {
@@ -73,9 +99,10 @@ pub(crate) fn fake_fn_signature<'a>(
     }
 }
 
-pub(crate) fn fake_fn_prototype<'a>(
-    name: &CStr,
-    arg_types: &[TypeId<'a>],
+/// Constructs a [`FunctionPrototype`] with the given name and signature.
+pub fn fake_fn_prototype<'a>(
+    name: &'a CStr,
+    arg_types: &'a [TypeId<'a>],
     return_type: Option<TypeId<'a>>,
 ) -> FunctionPrototype<'a> {
     FunctionPrototype {
@@ -84,10 +111,11 @@ pub(crate) fn fake_fn_prototype<'a>(
     }
 }
 
-pub(crate) fn fake_module_info<'a>(
-    path: &CStr,
-    functions: &[FunctionDefinition<'a>],
-    types: &[TypeDefinition<'a>],
+/// Constructs a [`ModuleInfo`] that references the given functions and types.
+pub fn fake_module_info<'a>(
+    path: &'a CStr,
+    functions: &'a [FunctionDefinition<'a>],
+    types: &'a [TypeDefinition<'a>],
 ) -> ModuleInfo<'a> {
     ModuleInfo {
         path: path.as_ptr(),
@@ -95,14 +123,23 @@ pub(crate) fn fake_module_info<'a>(
         num_functions: functions.len() as u32,
         types: types.as_ptr(),
         num_types: types.len() as u32,
+        dependencies: ptr::null(),
+        num_dependencies: 0,
     }
 }
 
-pub(crate) fn fake_struct_definition<'a>(
+/// Constructs a [`StructDefinition`] that references the given field
+/// information. The GUID is derived from `name`.
+///
+/// # Safety
+///
+/// Each of the `field_names` must point to a valid nul-terminated string that
+/// outlives the returned [`StructDefinition`].
+pub unsafe fn fake_struct_definition<'a>(
     name: &CStr,
-    field_names: &[*const c_char],
-    field_types: &[TypeId<'a>],
-    field_offsets: &[u16],
+    field_names: &'a [*const c_char],
+    field_types: &'a [TypeId<'a>],
+    field_offsets: &'a [u16],
     memory_kind: StructMemoryKind,
 ) -> StructDefinition<'a> {
     assert!(field_names.len() == field_types.len());
@@ -118,8 +155,10 @@ pub(crate) fn fake_struct_definition<'a>(
     }
 }
 
-pub(crate) fn fake_type_definition<'a>(
-    name: &CStr,
+/// Constructs a [`TypeDefinition`] with the given name, size in bits,
+/// alignment, and type-specific data.
+pub fn fake_type_definition<'a>(
+    name: &'a CStr,
     size: u32,
     alignment: u8,
     data: TypeDefinitionData<'a>,
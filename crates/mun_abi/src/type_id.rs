@@ -2,7 +2,7 @@ use std::fmt;
 
 use once_cell::sync::OnceCell;
 
-use crate::{static_type_map::StaticTypeMap, Guid};
+use crate::{static_type_map::StaticTypeMap, Guid, PrimitiveType};
 
 /// Represents a unique identifier for types. The runtime can use this to lookup
 /// the corresponding [`TypeInfo`]. A [`TypeId`] is a key for a [`TypeInfo`].
@@ -10,7 +10,7 @@ use crate::{static_type_map::StaticTypeMap, Guid};
 /// A [`TypeId`] only contains enough information to query the runtime for a
 /// [`TypeInfo`].
 #[repr(u8)]
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TypeId<'a> {
     /// Represents a concrete type with a specific Guid
@@ -25,7 +25,7 @@ pub enum TypeId<'a> {
 
 /// Represents a pointer to another type.
 #[repr(C)]
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Ord, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PointerTypeId<'a> {
     /// The type to which this pointer points
@@ -37,7 +37,7 @@ pub struct PointerTypeId<'a> {
 
 /// Represents an array of a specific type.
 #[repr(C)]
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Ord, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ArrayTypeId<'a> {
     /// The element type of the array
@@ -60,6 +60,55 @@ impl<'a> From<PointerTypeId<'a>> for TypeId<'a> {
     }
 }
 
+impl<'a> From<ArrayTypeId<'a>> for TypeId<'a> {
+    fn from(array: ArrayTypeId<'a>) -> Self {
+        TypeId::Array(array)
+    }
+}
+
+impl<'a> TypeId<'a> {
+    /// Constructs the [`TypeId`] of the concrete type with the specified
+    /// fully qualified `name`, e.g. `"core::i32"`.
+    pub const fn from_type_name(name: &str) -> TypeId<'a> {
+        TypeId::Concrete(Guid::from_str(name))
+    }
+
+    /// Constructs the [`TypeId`] of the primitive type `T`.
+    pub fn from_primitive<T: PrimitiveType>() -> TypeId<'a> {
+        TypeId::Concrete(*T::guid())
+    }
+
+    /// Returns the pointer type information if this type represents a pointer.
+    pub fn as_pointer(&self) -> Option<PointerTypeId<'a>> {
+        match self {
+            TypeId::Pointer(pointer) => Some(pointer.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the array type information if this type represents an array.
+    pub fn as_array(&self) -> Option<ArrayTypeId<'a>> {
+        match self {
+            TypeId::Array(array) => Some(array.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> PointerTypeId<'a> {
+    /// Returns the [`TypeId`] that represents this pointer type.
+    pub fn to_type_id(&self) -> TypeId<'a> {
+        TypeId::Pointer(self.clone())
+    }
+}
+
+impl<'a> ArrayTypeId<'a> {
+    /// Returns the [`TypeId`] that represents this array type.
+    pub fn to_type_id(&self) -> TypeId<'a> {
+        TypeId::Array(self.clone())
+    }
+}
+
 impl fmt::Display for TypeId<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -123,7 +172,29 @@ impl<T: HasStaticTypeId + 'static> HasStaticTypeId for *mut T {
 
 #[cfg(test)]
 mod test {
-    use crate::{ArrayTypeId, HasStaticTypeId, PointerTypeId, PrimitiveType, TypeId};
+    use crate::{ArrayTypeId, Guid, HasStaticTypeId, PointerTypeId, PrimitiveType, TypeId};
+
+    #[test]
+    fn from_type_name() {
+        const I32_TYPE_ID: TypeId<'static> = TypeId::from_type_name("core::i32");
+        assert_eq!(&I32_TYPE_ID, i32::type_id());
+        assert_eq!(
+            TypeId::from_type_name("foo::Bar"),
+            TypeId::Concrete(Guid::from_str("foo::Bar"))
+        );
+    }
+
+    #[test]
+    fn from_primitive() {
+        assert_eq!(&TypeId::from_primitive::<i32>(), i32::type_id());
+        assert_eq!(&TypeId::from_primitive::<f64>(), f64::type_id());
+        assert_eq!(&TypeId::from_primitive::<bool>(), bool::type_id());
+        assert_eq!(&TypeId::from_primitive::<usize>(), usize::type_id());
+        assert_eq!(
+            TypeId::from_primitive::<u8>(),
+            TypeId::from_type_name(u8::name())
+        );
+    }
 
     #[test]
     fn display() {
@@ -160,4 +231,68 @@ mod test {
             format!("[{}]", i32::guid())
         );
     }
+
+    /// Calls `f` with every type id that can be constructed from the primitive
+    /// types by nesting pointers and arrays up to `depth` levels deep.
+    fn for_each_type_id(depth: usize, f: &mut dyn FnMut(&TypeId<'_>)) {
+        fn visit(type_id: &TypeId<'_>, depth: usize, f: &mut dyn FnMut(&TypeId<'_>)) {
+            f(type_id);
+            if depth == 0 {
+                return;
+            }
+
+            for mutable in [false, true] {
+                let pointer = TypeId::from(PointerTypeId {
+                    pointee: type_id,
+                    mutable,
+                });
+                visit(&pointer, depth - 1, f);
+            }
+
+            let array = TypeId::from(ArrayTypeId { element: type_id });
+            visit(&array, depth - 1, f);
+        }
+
+        for type_id in [
+            i8::type_id(),
+            i64::type_id(),
+            u128::type_id(),
+            f32::type_id(),
+            bool::type_id(),
+            <()>::type_id(),
+        ] {
+            visit(type_id, depth, f);
+        }
+    }
+
+    #[test]
+    fn pointer_round_trip() {
+        for_each_type_id(3, &mut |type_id| match type_id.as_pointer() {
+            Some(pointer) => {
+                assert_eq!(&pointer.to_type_id(), type_id);
+                assert_eq!(
+                    type_id.to_string(),
+                    format!(
+                        "*{} {}",
+                        if pointer.mutable { "mut" } else { "const" },
+                        pointer.pointee
+                    )
+                );
+                assert!(type_id.as_array().is_none());
+            }
+            None => assert!(!matches!(type_id, TypeId::Pointer(_))),
+        });
+    }
+
+    #[test]
+    fn array_round_trip() {
+        for_each_type_id(3, &mut |type_id| match type_id.as_array() {
+            Some(array) => {
+                assert_eq!(&array.to_type_id(), type_id);
+                assert_eq!(type_id.to_string(), format!("[{}]", array.element));
+                assert!(type_id.as_pointer().is_none());
+            }
+            None => assert!(!matches!(type_id, TypeId::Array(_))),
+        });
+    }
 }
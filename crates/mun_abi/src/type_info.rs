@@ -92,10 +92,14 @@ impl<'a> TypeDefinition<'a> {
         }
     }
 
+    /// Returns whether this is a struct type.
+    pub fn is_struct(&self) -> bool {
+        self.data.is_struct()
+    }
+
     /// Retrieves the type's struct information, if available.
-    pub fn as_struct(&self) -> Option<&StructDefinition<'_>> {
-        let TypeDefinitionData::Struct(s) = &self.data;
-        Some(s)
+    pub fn as_struct(&self) -> Option<&StructDefinition<'a>> {
+        self.data.as_struct()
     }
 
     /// Returns the size of the type in bits
@@ -137,11 +141,18 @@ impl Eq for TypeDefinition<'_> {}
 unsafe impl Send for TypeDefinition<'_> {}
 unsafe impl Sync for TypeDefinition<'_> {}
 
-impl TypeDefinitionData<'_> {
+impl<'a> TypeDefinitionData<'a> {
     /// Returns whether this is a struct type.
     pub fn is_struct(&self) -> bool {
         matches!(self, TypeDefinitionData::Struct(_))
     }
+
+    /// Retrieves the struct information, if this is a struct type.
+    pub fn as_struct(&self) -> Option<&StructDefinition<'a>> {
+        match self {
+            TypeDefinitionData::Struct(s) => Some(s),
+        }
+    }
 }
 
 /// A trait that defines that for a type we can statically return a type name.
@@ -157,7 +168,7 @@ mod tests {
     use super::TypeDefinitionData;
     use crate::{
         test_utils::{fake_struct_definition, fake_type_definition, FAKE_TYPE_NAME},
-        StructMemoryKind,
+        StructDefinition, StructMemoryKind,
     };
 
     #[test]
@@ -166,13 +177,15 @@ mod tests {
         let field_names = &[];
         let field_types = &[];
         let field_offsets = &[];
-        let struct_info = fake_struct_definition(
-            &type_name,
-            field_names,
-            field_types,
-            field_offsets,
-            StructMemoryKind::default(),
-        );
+        let struct_info = unsafe {
+            fake_struct_definition(
+                &type_name,
+                field_names,
+                field_types,
+                field_offsets,
+                StructMemoryKind::default(),
+            )
+        };
 
         let type_definition =
             fake_type_definition(&type_name, 1, 1, TypeDefinitionData::Struct(struct_info));
@@ -185,13 +198,15 @@ mod tests {
         let field_names = &[];
         let field_types = &[];
         let field_offsets = &[];
-        let struct_info = fake_struct_definition(
-            &type_name,
-            field_names,
-            field_types,
-            field_offsets,
-            StructMemoryKind::default(),
-        );
+        let struct_info = unsafe {
+            fake_struct_definition(
+                &type_name,
+                field_names,
+                field_types,
+                field_offsets,
+                StructMemoryKind::default(),
+            )
+        };
 
         let type_definition =
             fake_type_definition(&type_name, 24, 8, TypeDefinitionData::Struct(struct_info));
@@ -207,17 +222,30 @@ mod tests {
         let field_names = &[];
         let field_types = &[];
         let field_offsets = &[];
-        let struct_info = fake_struct_definition(
-            &type_name,
-            field_names,
-            field_types,
-            field_offsets,
-            StructMemoryKind::default(),
-        );
+        let struct_info = unsafe {
+            fake_struct_definition(
+                &type_name,
+                field_names,
+                field_types,
+                field_offsets,
+                StructMemoryKind::default(),
+            )
+        };
 
         let type_definition =
             fake_type_definition(&type_name, 1, 1, TypeDefinitionData::Struct(struct_info));
         assert!(type_definition.data.is_struct());
+        assert!(type_definition.is_struct());
+        assert_eq!(
+            type_definition.data.as_struct(),
+            type_definition.as_struct()
+        );
+        assert_eq!(
+            type_definition
+                .as_struct()
+                .map(StructDefinition::num_fields),
+            Some(0)
+        );
     }
 
     #[test]
@@ -226,13 +254,15 @@ mod tests {
         let field_names = &[];
         let field_types = &[];
         let field_offsets = &[];
-        let struct_info = fake_struct_definition(
-            &type_name,
-            field_names,
-            field_types,
-            field_offsets,
-            StructMemoryKind::default(),
-        );
+        let struct_info = unsafe {
+            fake_struct_definition(
+                &type_name,
+                field_names,
+                field_types,
+                field_offsets,
+                StructMemoryKind::default(),
+            )
+        };
 
         let type_definition =
             fake_type_definition(&type_name, 1, 1, TypeDefinitionData::Struct(struct_info));
@@ -1,13 +1,18 @@
-use std::{ffi, ffi::CStr, os::raw::c_char, slice, str};
+use std::{ffi, ffi::CStr, fmt, os::raw::c_char, ptr, slice, str};
 
 use itertools::izip;
 
-use crate::type_id::TypeId;
+use crate::{type_id::TypeId, ModuleInfo};
 
 /// Represents a lookup table for type information. This is used for runtime
 /// linking.
 ///
 /// Type IDs and handles are stored separately for cache efficiency.
+///
+/// [`TypeLut::binary_search`] expects the type IDs to be sorted in ascending
+/// order (see [`TypeId`]'s `Ord` implementation). This is not guaranteed for
+/// all assemblies. Debug builds assert that the type IDs are sorted, release
+/// builds fall back to a linear search for unsorted tables.
 #[repr(C)]
 pub struct TypeLut<'a> {
     /// Type IDs
@@ -133,6 +138,100 @@ impl<'a> TypeLut<'a> {
         }
     }
 
+    /// Returns the index of the entry with the specified `type_id`. If no such
+    /// entry exists, a new entry is inserted and its index is returned.
+    ///
+    /// If the type IDs are sorted (see [`TypeLut::is_sorted`]), the new entry
+    /// is inserted at its sorted position, so that [`TypeLut::binary_search`]
+    /// keeps working. The indices of all entries after it shift by one.
+    /// Otherwise the new entry is appended to the table.
+    ///
+    /// # Safety
+    ///
+    /// The type lookup table does not own its memory. If the `type_id` is not
+    /// yet present, the memory backing the type IDs, type handles, and type
+    /// names must be writable and have room for at least one more entry.
+    /// The `type_name` must point to a valid, nul-terminated C string that
+    /// outlives the table.
+    pub unsafe fn lookup_or_insert(
+        &mut self,
+        type_id: TypeId<'a>,
+        type_handle: *const ffi::c_void,
+        type_name: *const c_char,
+    ) -> u32 {
+        let idx = if self.is_sorted() {
+            match self.type_ids().binary_search(&type_id) {
+                Ok(idx) => return idx as u32,
+                Err(idx) => idx,
+            }
+        } else {
+            if let Some(idx) = self.type_ids().iter().position(|id| *id == type_id) {
+                return idx as u32;
+            }
+            self.num_entries as usize
+        };
+
+        // Shift all entries after `idx` one place to the back to make room for
+        // the new entry.
+        let type_ids = self.type_ids.cast_mut();
+        let type_names = self.type_names.cast_mut();
+        let num_moved = self.num_entries as usize - idx;
+        ptr::copy(type_ids.add(idx), type_ids.add(idx + 1), num_moved);
+        ptr::copy(
+            self.type_handles.add(idx),
+            self.type_handles.add(idx + 1),
+            num_moved,
+        );
+        ptr::copy(type_names.add(idx), type_names.add(idx + 1), num_moved);
+
+        type_ids.add(idx).write(type_id);
+        self.type_handles.add(idx).write(type_handle);
+        type_names.add(idx).write(type_name);
+        self.num_entries += 1;
+
+        idx as u32
+    }
+
+    /// Returns true if the type IDs are sorted in ascending order.
+    pub fn is_sorted(&self) -> bool {
+        self.type_ids().windows(2).all(|w| w[0] <= w[1])
+    }
+
+    /// Returns the index of the entry with the given `type_id`, or `None` if
+    /// it is not present.
+    ///
+    /// The type IDs are expected to be sorted (see [`TypeLut::is_sorted`]).
+    /// Debug builds assert this. Release builds fall back to a linear search
+    /// if the binary search fails and the type IDs turn out to be unsorted.
+    pub fn binary_search(&self, type_id: &TypeId<'_>) -> Option<u32> {
+        debug_assert!(self.is_sorted(), "type IDs are not sorted");
+        let type_ids = self.type_ids();
+        let idx = match type_ids.binary_search(type_id) {
+            Ok(idx) => Some(idx),
+            Err(_) if !self.is_sorted() => type_ids.iter().position(|id| id == type_id),
+            Err(_) => None,
+        };
+        idx.map(|idx| idx as u32)
+    }
+
+    /// Returns the type handle of the type with the specified `name`, or
+    /// `None` if it is not present.
+    ///
+    /// Types defined in `module` are resolved to a [`TypeId`] through
+    /// [`ModuleInfo::type_by_name`], after which the type handle is looked up.
+    /// Other types, e.g. `core::i32` or types imported from other modules, are
+    /// found through the names stored in the table itself. This does not
+    /// require the type IDs to be sorted.
+    pub fn find_by_name(&self, name: &str, module: &ModuleInfo<'_>) -> Option<*const ffi::c_void> {
+        let idx = if let Some(type_def) = module.type_by_name(name) {
+            let type_id = TypeId::Concrete(*type_def.as_concrete());
+            self.type_ids().iter().position(|id| *id == type_id)
+        } else {
+            self.type_names().position(|type_name| type_name == name)
+        }?;
+        Some(unsafe { self.get_type_handle_unchecked(idx as u32) })
+    }
+
     /// Returns type names.
     pub fn type_names(&self) -> impl Iterator<Item = &str> {
         let type_names = if self.num_entries == 0 {
@@ -145,8 +244,151 @@ impl<'a> TypeLut<'a> {
             .iter()
             .map(|n| unsafe { str::from_utf8_unchecked(CStr::from_ptr(*n).to_bytes()) })
     }
+
+    /// Merges the entries of all `tables` into a single owned table, sorted by
+    /// type ID so that lookups can use a binary search.
+    ///
+    /// Returns an error if a type ID is present in more than one entry.
+    pub fn merge(tables: &[&TypeLut<'a>]) -> Result<OwnedTypeLut<'a>, TypeLutMergeError> {
+        let mut entries: Vec<_> = tables
+            .iter()
+            .flat_map(|table| {
+                let (type_handles, type_names): (&[_], &[_]) = if table.num_entries == 0 {
+                    (&[], &[])
+                } else {
+                    let num_entries = table.num_entries as usize;
+                    unsafe {
+                        (
+                            slice::from_raw_parts(table.type_handles, num_entries),
+                            slice::from_raw_parts(table.type_names, num_entries),
+                        )
+                    }
+                };
+                izip!(table.type_ids(), type_handles, type_names).map(
+                    |(type_id, type_handle, type_name)| (type_id.clone(), *type_handle, *type_name),
+                )
+            })
+            .collect();
+        entries.sort_by(|(lhs, _, _), (rhs, _, _)| lhs.cmp(rhs));
+
+        if let Some(duplicate) = entries.windows(2).find(|w| w[0].0 == w[1].0) {
+            let (type_id, _, type_name) = &duplicate[0];
+            return Err(TypeLutMergeError::DuplicateTypeId {
+                type_id: type_id.to_string(),
+                type_name: unsafe { CStr::from_ptr(*type_name) }
+                    .to_string_lossy()
+                    .into_owned(),
+            });
+        }
+
+        let mut merged = OwnedTypeLut {
+            type_ids: Vec::with_capacity(entries.len()),
+            type_handles: Vec::with_capacity(entries.len()),
+            type_names: Vec::with_capacity(entries.len()),
+        };
+        for (type_id, type_handle, type_name) in entries {
+            merged.type_ids.push(type_id);
+            merged.type_handles.push(type_handle);
+            merged.type_names.push(type_name);
+        }
+        Ok(merged)
+    }
+}
+
+/// A lookup table for type information that owns the memory of its entries,
+/// e.g. the result of merging the [`TypeLut`]s of multiple assemblies with
+/// [`TypeLut::merge`].
+///
+/// The entries are sorted by type ID. The type names are not owned and must
+/// outlive the table.
+#[derive(Debug)]
+pub struct OwnedTypeLut<'a> {
+    type_ids: Vec<TypeId<'a>>,
+    type_handles: Vec<*const ffi::c_void>,
+    type_names: Vec<*const c_char>,
+}
+
+impl<'a> OwnedTypeLut<'a> {
+    /// Returns the number of entries in the table.
+    pub fn len(&self) -> usize {
+        self.type_ids.len()
+    }
+
+    /// Returns true if the table contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.type_ids.is_empty()
+    }
+
+    /// Returns type IDs.
+    pub fn type_ids(&self) -> &[TypeId<'a>] {
+        &self.type_ids
+    }
+
+    /// Returns type handles.
+    pub fn type_handles(&self) -> &[*const ffi::c_void] {
+        &self.type_handles
+    }
+
+    /// Returns mutable type handles.
+    pub fn type_handles_mut(&mut self) -> &mut [*const ffi::c_void] {
+        &mut self.type_handles
+    }
+
+    /// Returns type names.
+    pub fn type_names(&self) -> impl Iterator<Item = &str> {
+        self.type_names
+            .iter()
+            .map(|n| unsafe { str::from_utf8_unchecked(CStr::from_ptr(*n).to_bytes()) })
+    }
+
+    /// Returns the index of the entry with the given `type_id`, or `None` if
+    /// it is not present.
+    pub fn binary_search(&self, type_id: &TypeId<'_>) -> Option<usize> {
+        self.type_ids.binary_search(type_id).ok()
+    }
+
+    /// Returns a [`TypeLut`] that references the entries of this table. The
+    /// returned table borrows `self` and must not be used to insert new
+    /// entries.
+    pub fn as_type_lut(&mut self) -> TypeLut<'_> {
+        TypeLut {
+            type_ids: self.type_ids.as_ptr(),
+            type_handles: self.type_handles.as_mut_ptr(),
+            type_names: self.type_names.as_ptr(),
+            num_entries: self
+                .type_ids
+                .len()
+                .try_into()
+                .expect("too many entries for a type lookup table"),
+        }
+    }
+}
+
+/// An error that can occur when merging [`TypeLut`]s.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeLutMergeError {
+    /// The same type ID is present in more than one entry.
+    DuplicateTypeId {
+        /// The duplicated type ID
+        type_id: String,
+        /// The name of the type
+        type_name: String,
+    },
 }
 
+impl fmt::Display for TypeLutMergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeLutMergeError::DuplicateTypeId { type_id, type_name } => write!(
+                f,
+                "type `{type_name}` ({type_id}) is present in multiple type lookup tables"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TypeLutMergeError {}
+
 #[cfg(feature = "serde")]
 impl serde::Serialize for TypeLut<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -170,19 +412,29 @@ impl serde::Serialize for TypeLut<'_> {
 
 #[cfg(test)]
 mod tests {
-    use std::{ffi::CString, ptr};
-
-    use crate::test_utils::{fake_type_lut, FAKE_TYPE_ID, FAKE_TYPE_NAME};
+    use std::{
+        ffi::{self, CString},
+        os::raw::c_char,
+        ptr,
+    };
+
+    use super::{TypeLut, TypeLutMergeError};
+    use crate::{
+        test_utils::{
+            fake_module_info, fake_struct_definition, fake_type_definition, fake_type_lut,
+            FAKE_MODULE_PATH, FAKE_STRUCT_NAME, FAKE_TYPE_ID, FAKE_TYPE_NAME,
+        },
+        HasStaticTypeId, StructMemoryKind, TypeDefinitionData, TypeId,
+    };
 
     #[test]
     fn test_type_lut_iter_mut_none() {
         let type_ids = &[];
         let type_ptrs = &mut [];
         let type_names = &[];
-        let mut type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let mut type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
 
-        let iter = type_ids.iter().zip(type_ptrs.iter_mut());
-        assert_eq!(type_lut.iter_mut().count(), iter.count());
+        assert_eq!(type_lut.iter_mut().count(), 0);
     }
 
     #[test]
@@ -190,13 +442,14 @@ mod tests {
         let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
 
         let type_ids = &[FAKE_TYPE_ID];
-        let type_ptrs = &mut [ptr::null()];
+        let expected_ptrs = [ptr::null()];
+        let type_ptrs = &mut expected_ptrs.clone();
         let type_names = &[type_name.as_ptr()];
-        let mut type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let mut type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
 
-        let iter = type_ids.iter().zip(type_ptrs.iter_mut());
-        assert_eq!(type_lut.iter_mut().count(), iter.len());
+        assert_eq!(type_lut.iter_mut().count(), type_ids.len());
 
+        let iter = type_ids.iter().zip(expected_ptrs.iter());
         for (lhs, rhs) in type_lut.iter_mut().zip(iter) {
             assert_eq!(lhs.0, rhs.0);
             assert_eq!(lhs.1, rhs.1);
@@ -208,10 +461,9 @@ mod tests {
         let type_ids = &[];
         let type_ptrs = &mut [];
         let type_names = &[];
-        let type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
 
-        let iter = type_ids.iter().zip(type_ptrs.iter_mut());
-        assert_eq!(type_lut.iter().count(), iter.count());
+        assert_eq!(type_lut.iter().count(), 0);
     }
 
     #[test]
@@ -219,13 +471,14 @@ mod tests {
         let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
 
         let type_ids = &[FAKE_TYPE_ID];
-        let type_ptrs = &mut [ptr::null()];
+        let expected_ptrs = [ptr::null()];
+        let type_ptrs = &mut expected_ptrs.clone();
         let type_names = &[type_name.as_ptr()];
-        let type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
 
-        let iter = type_ids.iter().zip(type_ptrs.iter_mut());
-        assert_eq!(type_lut.iter().count(), iter.len());
+        assert_eq!(type_lut.iter().count(), type_ids.len());
 
+        let iter = type_ids.iter().zip(expected_ptrs.iter());
         for (lhs, rhs) in type_lut.iter().zip(iter) {
             assert_eq!(lhs.0, rhs.0);
             assert_eq!(lhs.1, rhs.1);
@@ -237,7 +490,7 @@ mod tests {
         let type_ids = &[];
         let type_ptrs = &mut [];
         let type_names = &[];
-        let mut type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let mut type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
 
         assert_eq!(type_lut.type_handles_mut().len(), 0);
     }
@@ -247,13 +500,14 @@ mod tests {
         let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
 
         let type_ids = &[FAKE_TYPE_ID];
-        let type_ptrs = &mut [ptr::null()];
+        let expected_ptrs = [ptr::null()];
+        let type_ptrs = &mut expected_ptrs.clone();
         let type_names = &[type_name.as_ptr()];
-        let mut type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let mut type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
 
         let result = type_lut.type_handles_mut();
-        assert_eq!(result.len(), type_ptrs.len());
-        for (lhs, rhs) in result.iter().zip(type_ptrs.iter()) {
+        assert_eq!(result.len(), expected_ptrs.len());
+        for (lhs, rhs) in result.iter().zip(expected_ptrs.iter()) {
             assert_eq!(lhs, rhs);
         }
     }
@@ -263,7 +517,7 @@ mod tests {
         let type_ids = &[];
         let type_ptrs = &mut [];
         let type_names = &[];
-        let type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
 
         assert_eq!(type_lut.type_ids().len(), 0);
     }
@@ -275,7 +529,7 @@ mod tests {
         let type_ids = &[FAKE_TYPE_ID];
         let type_ptrs = &mut [ptr::null()];
         let type_names = &[type_name.as_ptr()];
-        let type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
 
         let result = type_lut.type_ids();
         assert_eq!(result.len(), type_ids.len());
@@ -292,7 +546,7 @@ mod tests {
         let type_ptrs = &mut [ptr::null()];
         let type_names = &[type_name.as_ptr()];
 
-        let type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
         assert_eq!(
             unsafe { type_lut.get_type_handle_unchecked(0) },
             type_ptrs[0]
@@ -307,7 +561,7 @@ mod tests {
         let type_ptrs = &mut [ptr::null()];
         let type_names = &[type_name.as_ptr()];
 
-        let type_lut = fake_type_lut(prototype, type_ptrs, type_names);
+        let type_lut = unsafe { fake_type_lut(prototype, type_ptrs, type_names) };
         assert_eq!(type_lut.get_type_handle(1), None);
     }
 
@@ -319,7 +573,7 @@ mod tests {
         let type_ptrs = &mut [ptr::null()];
         let type_names = &[type_name.as_ptr()];
 
-        let type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
         assert_eq!(type_lut.get_type_handle(0), Some(type_ptrs[0]));
     }
 
@@ -329,13 +583,14 @@ mod tests {
 
         let type_ids = &[FAKE_TYPE_ID];
         let type_ptrs = &mut [ptr::null()];
+        let type_ptr_addr: *const *const ffi::c_void = &type_ptrs[0];
         let type_names = &[type_name.as_ptr()];
 
-        let mut type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
-        assert_eq!(
+        let mut type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
+        assert!(ptr::eq(
             unsafe { type_lut.get_type_handle_unchecked_mut(0) },
-            &mut type_ptrs[0]
-        );
+            type_ptr_addr
+        ));
     }
 
     #[test]
@@ -346,7 +601,7 @@ mod tests {
         let type_ptrs = &mut [ptr::null()];
         let type_names = &[type_name.as_ptr()];
 
-        let mut type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let mut type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
         assert_eq!(type_lut.get_type_handle_mut(1), None);
     }
 
@@ -356,10 +611,160 @@ mod tests {
 
         let type_ids = &[FAKE_TYPE_ID];
         let type_ptrs = &mut [ptr::null()];
+        let type_ptr_addr: *const *const ffi::c_void = &type_ptrs[0];
         let type_names = &[type_name.as_ptr()];
 
-        let mut type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
-        assert_eq!(type_lut.get_type_handle_mut(0), Some(&mut type_ptrs[0]));
+        let mut type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
+        let type_handle = type_lut.get_type_handle_mut(0).expect("entry must exist");
+        assert!(ptr::eq(type_handle, type_ptr_addr));
+    }
+
+    #[test]
+    fn test_type_lut_lookup_or_insert() {
+        let i32_name = CString::new("core::i32").expect("Invalid fake type name.");
+        let f64_name = CString::new("core::f64").expect("Invalid fake type name.");
+        let fake_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let handle = |h: usize| h as *const ffi::c_void;
+
+        let mut entries = [
+            (i32::type_id().clone(), handle(1), &i32_name),
+            (f64::type_id().clone(), handle(2), &f64_name),
+            (FAKE_TYPE_ID, handle(3), &fake_name),
+        ];
+        entries.sort_by(|(lhs, _, _), (rhs, _, _)| lhs.cmp(rhs));
+
+        // Start with an empty table that has room for three entries
+        let mut type_id_storage = [FAKE_TYPE_ID, FAKE_TYPE_ID, FAKE_TYPE_ID];
+        let mut type_handle_storage = [ptr::null(); 3];
+        let mut type_name_storage: [*const c_char; 3] = [ptr::null(); 3];
+        let mut type_lut = TypeLut {
+            type_ids: type_id_storage.as_mut_ptr().cast_const(),
+            type_handles: type_handle_storage.as_mut_ptr(),
+            type_names: type_name_storage.as_mut_ptr().cast_const(),
+            num_entries: 0,
+        };
+
+        // Inserting in descending order places every new entry at the front
+        for (type_id, type_handle, type_name) in entries.iter().rev() {
+            let result = unsafe {
+                type_lut.lookup_or_insert(type_id.clone(), *type_handle, type_name.as_ptr())
+            };
+            assert_eq!(result, 0);
+            assert!(type_lut.is_sorted());
+        }
+        assert_eq!(type_lut.num_entries, 3);
+
+        // Inserting an existing type should return its index
+        let (type_id, type_handle, type_name) = &entries[1];
+        let result =
+            unsafe { type_lut.lookup_or_insert(type_id.clone(), ptr::null(), type_name.as_ptr()) };
+        assert_eq!(result, 1);
+        assert_eq!(type_lut.num_entries, 3);
+        assert_eq!(type_lut.get_type_handle(1), Some(*type_handle));
+
+        for (idx, (type_id, type_handle, type_name)) in entries.iter().enumerate() {
+            assert_eq!(type_lut.binary_search(type_id), Some(idx as u32));
+            assert_eq!(type_lut.get_type_handle(idx as u32), Some(*type_handle));
+            assert_eq!(type_lut.type_names().nth(idx), type_name.to_str().ok());
+        }
+    }
+
+    #[test]
+    fn test_type_lut_lookup_or_insert_unsorted() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+
+        let mut sorted_ids = [
+            i32::type_id().clone(),
+            f64::type_id().clone(),
+            bool::type_id().clone(),
+        ];
+        sorted_ids.sort();
+
+        // An unsorted table with room for one more entry
+        let mut type_id_storage = [sorted_ids[2].clone(), sorted_ids[0].clone(), FAKE_TYPE_ID];
+        let mut type_handle_storage = [ptr::null(); 3];
+        let mut type_name_storage = [type_name.as_ptr(); 3];
+        let mut type_lut = TypeLut {
+            type_ids: type_id_storage.as_mut_ptr().cast_const(),
+            type_handles: type_handle_storage.as_mut_ptr(),
+            type_names: type_name_storage.as_mut_ptr().cast_const(),
+            num_entries: 2,
+        };
+
+        // New entries are appended to unsorted tables
+        let result = unsafe {
+            type_lut.lookup_or_insert(sorted_ids[1].clone(), ptr::null(), type_name.as_ptr())
+        };
+        assert_eq!(result, 2);
+        assert_eq!(
+            unsafe {
+                type_lut.lookup_or_insert(sorted_ids[0].clone(), ptr::null(), type_name.as_ptr())
+            },
+            1
+        );
+        assert_eq!(type_lut.num_entries, 3);
+    }
+
+    #[test]
+    fn test_type_lut_binary_search() {
+        let mut sorted_ids = [
+            i32::type_id().clone(),
+            f64::type_id().clone(),
+            bool::type_id().clone(),
+        ];
+        sorted_ids.sort();
+
+        let type_ptrs = &mut [ptr::null(); 3];
+        let type_names = &[ptr::null(); 3];
+        let type_lut = unsafe { fake_type_lut(&sorted_ids, type_ptrs, type_names) };
+
+        assert!(type_lut.is_sorted());
+        for (idx, type_id) in sorted_ids.iter().enumerate() {
+            assert_eq!(type_lut.binary_search(type_id), Some(idx as u32));
+        }
+        assert_eq!(type_lut.binary_search(&FAKE_TYPE_ID), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "type IDs are not sorted")]
+    fn test_type_lut_binary_search_unsorted() {
+        let mut unsorted_ids = [
+            i32::type_id().clone(),
+            f64::type_id().clone(),
+            bool::type_id().clone(),
+        ];
+        unsorted_ids.sort();
+        unsorted_ids.reverse();
+
+        let type_ptrs = &mut [ptr::null(); 3];
+        let type_names = &[ptr::null(); 3];
+        let type_lut = unsafe { fake_type_lut(&unsorted_ids, type_ptrs, type_names) };
+
+        assert!(!type_lut.is_sorted());
+        type_lut.binary_search(&unsorted_ids[0]);
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn test_type_lut_binary_search_unsorted_fallback() {
+        let mut unsorted_ids = [
+            i32::type_id().clone(),
+            f64::type_id().clone(),
+            bool::type_id().clone(),
+        ];
+        unsorted_ids.sort();
+        unsorted_ids.reverse();
+
+        let type_ptrs = &mut [ptr::null(); 3];
+        let type_names = &[ptr::null(); 3];
+        let type_lut = unsafe { fake_type_lut(&unsorted_ids, type_ptrs, type_names) };
+
+        assert!(!type_lut.is_sorted());
+        for (idx, type_id) in unsorted_ids.iter().enumerate() {
+            assert_eq!(type_lut.binary_search(type_id), Some(idx as u32));
+        }
+        assert_eq!(type_lut.binary_search(&FAKE_TYPE_ID), None);
     }
 
     #[test]
@@ -367,7 +772,7 @@ mod tests {
         let type_ids = &[];
         let type_ptrs = &mut [];
         let type_names = &[];
-        let type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
 
         assert_eq!(type_lut.type_names().count(), 0);
     }
@@ -379,10 +784,134 @@ mod tests {
         let type_ids = &[FAKE_TYPE_ID];
         let type_ptrs = &mut [ptr::null()];
         let type_names = &[type_name.as_ptr()];
-        let type_lut = fake_type_lut(type_ids, type_ptrs, type_names);
+        let type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
 
         for (lhs, rhs) in type_lut.type_names().zip([FAKE_TYPE_NAME].iter()) {
             assert_eq!(lhs, *rhs);
         }
     }
+
+    #[test]
+    fn test_type_lut_find_by_name() {
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name.");
+        let struct_def =
+            unsafe { fake_struct_definition(&struct_name, &[], &[], &[], StructMemoryKind::Value) };
+        let struct_type_id = TypeId::Concrete(struct_def.guid);
+        let types = [fake_type_definition(
+            &struct_name,
+            0,
+            1,
+            TypeDefinitionData::Struct(struct_def),
+        )];
+        let module = fake_module_info(&module_path, &[], &types);
+
+        let handle = 0xbeef as *const ffi::c_void;
+        let fake_handle = 0xf00d as *const ffi::c_void;
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_ids = &[FAKE_TYPE_ID, struct_type_id];
+        let type_ptrs = &mut [fake_handle, handle];
+        let type_names = &[type_name.as_ptr(), struct_name.as_ptr()];
+        let type_lut = unsafe { fake_type_lut(type_ids, type_ptrs, type_names) };
+
+        assert_eq!(
+            type_lut.find_by_name(FAKE_STRUCT_NAME, &module),
+            Some(handle)
+        );
+        // Types that are not defined in the module are found by their name in
+        // the table
+        assert_eq!(
+            type_lut.find_by_name(FAKE_TYPE_NAME, &module),
+            Some(fake_handle)
+        );
+        assert_eq!(type_lut.find_by_name("core::i32", &module), None);
+
+        let empty_lut = unsafe { fake_type_lut(&[], &mut [], &[]) };
+        assert_eq!(empty_lut.find_by_name(FAKE_STRUCT_NAME, &module), None);
+    }
+
+    #[test]
+    fn test_type_lut_merge() {
+        let i32_name = CString::new("core::i32").expect("Invalid fake type name.");
+        let f64_name = CString::new("core::f64").expect("Invalid fake type name.");
+        let bool_name = CString::new("core::bool").expect("Invalid fake type name.");
+        let fake_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+
+        let handle = |h: usize| h as *const ffi::c_void;
+
+        let type_ids_a = [i32::type_id().clone(), f64::type_id().clone()];
+        let type_ptrs_a = &mut [handle(1), handle(2)];
+        let type_names_a = &[i32_name.as_ptr(), f64_name.as_ptr()];
+        let table_a = unsafe { fake_type_lut(&type_ids_a, type_ptrs_a, type_names_a) };
+
+        let type_ids_b = [bool::type_id().clone()];
+        let type_ptrs_b = &mut [handle(3)];
+        let type_names_b = &[bool_name.as_ptr()];
+        let table_b = unsafe { fake_type_lut(&type_ids_b, type_ptrs_b, type_names_b) };
+
+        let type_ids_c = [FAKE_TYPE_ID];
+        let type_ptrs_c = &mut [handle(4)];
+        let type_names_c = &[fake_name.as_ptr()];
+        let table_c = unsafe { fake_type_lut(&type_ids_c, type_ptrs_c, type_names_c) };
+
+        let empty = unsafe { fake_type_lut(&[], &mut [], &[]) };
+
+        let mut merged = TypeLut::merge(&[&table_a, &table_b, &empty, &table_c])
+            .expect("tables without duplicates should merge");
+        assert_eq!(merged.len(), 4);
+
+        let mut expected_ids = [
+            i32::type_id().clone(),
+            f64::type_id().clone(),
+            bool::type_id().clone(),
+            FAKE_TYPE_ID,
+        ];
+        expected_ids.sort();
+        assert_eq!(merged.type_ids(), &expected_ids);
+
+        for (type_id, type_handle, type_name) in [
+            (i32::type_id(), handle(1), "core::i32"),
+            (f64::type_id(), handle(2), "core::f64"),
+            (bool::type_id(), handle(3), "core::bool"),
+            (&FAKE_TYPE_ID, handle(4), FAKE_TYPE_NAME),
+        ] {
+            let idx = merged.binary_search(type_id).expect("type should exist");
+            assert_eq!(merged.type_handles()[idx], type_handle);
+            assert_eq!(merged.type_names().nth(idx), Some(type_name));
+        }
+
+        let type_lut = merged.as_type_lut();
+        assert!(type_lut.is_sorted());
+        assert_eq!(type_lut.num_entries, 4);
+        assert_eq!(
+            type_lut
+                .binary_search(bool::type_id())
+                .and_then(|idx| type_lut.get_type_handle(idx)),
+            Some(handle(3))
+        );
+    }
+
+    #[test]
+    fn test_type_lut_merge_duplicate() {
+        let i32_name = CString::new("core::i32").expect("Invalid fake type name.");
+        let f64_name = CString::new("core::f64").expect("Invalid fake type name.");
+
+        let type_ids_a = [i32::type_id().clone(), f64::type_id().clone()];
+        let type_ptrs_a = &mut [ptr::null(); 2];
+        let type_names_a = &[i32_name.as_ptr(), f64_name.as_ptr()];
+        let table_a = unsafe { fake_type_lut(&type_ids_a, type_ptrs_a, type_names_a) };
+
+        let type_ids_b = [i32::type_id().clone()];
+        let type_ptrs_b = &mut [ptr::null()];
+        let type_names_b = &[i32_name.as_ptr()];
+        let table_b = unsafe { fake_type_lut(&type_ids_b, type_ptrs_b, type_names_b) };
+
+        assert_eq!(
+            TypeLut::merge(&[&table_a, &table_b]).unwrap_err(),
+            TypeLutMergeError::DuplicateTypeId {
+                type_id: i32::type_id().to_string(),
+                type_name: "core::i32".to_owned(),
+            }
+        );
+    }
 }
@@ -0,0 +1,138 @@
+//! Compile-time verification that the layout of the ABI types matches the
+//! definitions in the C header (`c/include/mun/abi.h`).
+//!
+//! Every check is a `const` assertion, so a layout regression fails the build
+//! instead of corrupting memory that is shared with C code. The offsets of
+//! types that contain pointers are only verified for 64-bit targets.
+
+use std::mem::{align_of, offset_of, size_of};
+
+use crate::{AssemblyDependency, Guid, StructMemoryKind};
+
+/// Asserts the size and alignment of a type, and the offsets of its fields.
+macro_rules! assert_layout {
+    ($ty:ty, size = $size:expr, align = $align:expr $(, $field:ident = $offset:expr)* $(,)?) => {
+        const _: () = {
+            assert!(size_of::<$ty>() == $size);
+            assert!(align_of::<$ty>() == $align);
+            $(assert!(offset_of!($ty, $field) == $offset);)*
+        };
+    };
+}
+
+assert_layout!(Guid, size = 16, align = 1);
+assert_layout!(StructMemoryKind, size = 1, align = 1);
+assert_layout!(
+    AssemblyDependency,
+    size = 20,
+    align = 4,
+    guid = 0,
+    min_abi_version = 16,
+);
+
+#[cfg(target_pointer_width = "64")]
+mod pointer_width_64 {
+    use std::mem::{align_of, offset_of, size_of};
+
+    use crate::{
+        ArrayTypeId, AssemblyInfo, DispatchTable, FunctionDefinition, FunctionPrototype,
+        FunctionSignature, ModuleInfo, PointerTypeId, StructDefinition, TypeDefinition,
+        TypeDefinitionData, TypeId, TypeLut,
+    };
+
+    assert_layout!(
+        PointerTypeId<'static>,
+        size = 16,
+        align = 8,
+        pointee = 0,
+        mutable = 8,
+    );
+    assert_layout!(ArrayTypeId<'static>, size = 8, align = 8, element = 0);
+    assert_layout!(TypeId<'static>, size = 24, align = 8);
+
+    assert_layout!(
+        FunctionSignature<'static>,
+        size = 40,
+        align = 8,
+        arg_types = 0,
+        return_type = 8,
+        num_arg_types = 32,
+    );
+    assert_layout!(
+        FunctionPrototype<'static>,
+        size = 48,
+        align = 8,
+        name = 0,
+        signature = 8,
+    );
+    assert_layout!(
+        FunctionDefinition<'static>,
+        size = 64,
+        align = 8,
+        prototype = 0,
+        fn_ptr = 48,
+        is_extern = 56,
+    );
+
+    assert_layout!(
+        StructDefinition<'static>,
+        size = 48,
+        align = 8,
+        guid = 0,
+        field_names = 16,
+        field_types = 24,
+        field_offsets = 32,
+        num_fields = 40,
+        memory_kind = 42,
+    );
+    assert_layout!(TypeDefinitionData<'static>, size = 56, align = 8);
+    assert_layout!(
+        TypeDefinition<'static>,
+        size = 72,
+        align = 8,
+        name = 0,
+        size_in_bits = 8,
+        alignment = 12,
+        data = 16,
+    );
+
+    assert_layout!(
+        ModuleInfo<'static>,
+        size = 48,
+        align = 8,
+        path = 0,
+        functions = 8,
+        types = 16,
+        dependencies = 24,
+        num_functions = 32,
+        num_types = 36,
+        num_dependencies = 40,
+    );
+    assert_layout!(
+        DispatchTable<'static>,
+        size = 24,
+        align = 8,
+        prototypes = 0,
+        fn_ptrs = 8,
+        num_entries = 16,
+    );
+    assert_layout!(
+        TypeLut<'static>,
+        size = 32,
+        align = 8,
+        type_ids = 0,
+        type_handles = 8,
+        type_names = 16,
+        num_entries = 24,
+    );
+    assert_layout!(
+        AssemblyInfo<'static>,
+        size = 120,
+        align = 8,
+        symbols = 0,
+        dispatch_table = 48,
+        type_lut = 72,
+        dependencies = 104,
+        num_dependencies = 112,
+    );
+}
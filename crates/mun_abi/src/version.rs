@@ -0,0 +1,178 @@
+//! Utilities for working with packed ABI versions like [`ABI_VERSION`].
+//!
+//! A version is packed as a decimal number of the form `MM_mm_pp`, where `MM`
+//! is the major, `mm` the minor, and `pp` the patch version.
+//!
+//! [`ABI_VERSION`]: crate::ABI_VERSION
+
+use std::fmt;
+
+use crate::ABI_VERSION;
+
+/// Returns the major component of a packed ABI version.
+pub const fn abi_major(v: u32) -> u32 {
+    v / 1_00_00
+}
+
+/// Returns the minor component of a packed ABI version.
+pub const fn abi_minor(v: u32) -> u32 {
+    (v / 1_00) % 1_00
+}
+
+/// Returns the patch component of a packed ABI version.
+pub const fn abi_patch(v: u32) -> u32 {
+    v % 1_00
+}
+
+/// Returns true if an assembly compiled against `assembly_version` can be
+/// loaded by a runtime that implements `runtime_version`.
+///
+/// Versions are compatible if their major versions are equal and the minor
+/// version of the assembly is not newer than that of the runtime. Patch
+/// versions never affect compatibility.
+pub const fn is_compatible(assembly_version: u32, runtime_version: u32) -> bool {
+    abi_major(assembly_version) == abi_major(runtime_version)
+        && abi_minor(assembly_version) <= abi_minor(runtime_version)
+}
+
+/// Checks whether an assembly compiled against `assembly_version` can be
+/// loaded by this version of the ABI, [`ABI_VERSION`].
+///
+/// The assembly's version is typically obtained by calling the function
+/// exported by a munlib as [`GET_VERSION_FN_NAME`].
+///
+/// The same compatibility rule as [`is_compatible`] applies: the major
+/// versions must be equal and the assembly's minor version must not exceed
+/// that of [`ABI_VERSION`].
+///
+/// [`GET_VERSION_FN_NAME`]: crate::GET_VERSION_FN_NAME
+pub fn check_abi_version(assembly_version: u32) -> Result<(), AbiVersionError> {
+    if is_compatible(assembly_version, ABI_VERSION) {
+        Ok(())
+    } else {
+        Err(AbiVersionError {
+            expected: ABI_VERSION,
+            actual: assembly_version,
+        })
+    }
+}
+
+/// An error that occurs when an assembly was compiled against an ABI version
+/// that is incompatible with [`ABI_VERSION`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AbiVersionError {
+    /// The packed ABI version implemented by this crate
+    pub expected: u32,
+    /// The packed ABI version of the assembly
+    pub actual: u32,
+}
+
+impl fmt::Display for AbiVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ABI version {} of the assembly is incompatible with ABI version {}",
+            DisplayVersion(self.actual),
+            DisplayVersion(self.expected)
+        )
+    }
+}
+
+impl std::error::Error for AbiVersionError {}
+
+/// Formats a packed ABI version as `major.minor.patch`.
+pub(crate) struct DisplayVersion(pub u32);
+
+impl fmt::Display for DisplayVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}",
+            abi_major(self.0),
+            abi_minor(self.0),
+            abi_patch(self.0)
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::zero_prefixed_literal)]
+mod tests {
+    use super::{
+        abi_major, abi_minor, abi_patch, check_abi_version, is_compatible, AbiVersionError,
+    };
+    use crate::ABI_VERSION;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(abi_major(01_02_03), 1);
+        assert_eq!(abi_minor(01_02_03), 2);
+        assert_eq!(abi_patch(01_02_03), 3);
+
+        assert_eq!(abi_major(00_03_00), 0);
+        assert_eq!(abi_minor(00_03_00), 3);
+        assert_eq!(abi_patch(00_03_00), 0);
+    }
+
+    #[test]
+    fn test_is_compatible_exact_match() {
+        assert!(is_compatible(01_02_03, 01_02_03));
+    }
+
+    #[test]
+    fn test_is_compatible_older_minor() {
+        assert!(is_compatible(01_02_00, 01_03_00));
+        assert!(is_compatible(01_00_07, 01_03_00));
+    }
+
+    #[test]
+    fn test_is_compatible_newer_minor() {
+        assert!(!is_compatible(01_03_00, 01_02_00));
+        assert!(!is_compatible(01_03_00, 01_02_09));
+    }
+
+    #[test]
+    fn test_is_compatible_patch_mismatch() {
+        assert!(is_compatible(01_02_05, 01_02_00));
+        assert!(is_compatible(01_02_00, 01_02_05));
+    }
+
+    #[test]
+    fn test_is_compatible_major_mismatch() {
+        assert!(!is_compatible(01_02_00, 02_02_00));
+        assert!(!is_compatible(02_02_00, 01_02_00));
+        assert!(!is_compatible(00_02_00, 01_02_00));
+    }
+
+    #[test]
+    fn test_check_abi_version() {
+        assert_eq!(check_abi_version(ABI_VERSION), Ok(()));
+        assert_eq!(check_abi_version(ABI_VERSION + 1), Ok(()));
+        assert_eq!(
+            check_abi_version(ABI_VERSION + 1_00_00),
+            Err(AbiVersionError {
+                expected: ABI_VERSION,
+                actual: ABI_VERSION + 1_00_00,
+            })
+        );
+        assert_eq!(
+            check_abi_version(ABI_VERSION + 1_00),
+            Err(AbiVersionError {
+                expected: ABI_VERSION,
+                actual: ABI_VERSION + 1_00,
+            })
+        );
+    }
+
+    #[test]
+    fn test_abi_version_error_display() {
+        let error = AbiVersionError {
+            expected: 00_05_00,
+            actual: 01_02_03,
+        };
+        assert_eq!(
+            error.to_string(),
+            "ABI version 1.2.3 of the assembly is incompatible with ABI version 0.5.0"
+        );
+    }
+}
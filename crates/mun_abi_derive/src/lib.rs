@@ -0,0 +1,60 @@
+//! Derive macros for the Mun ABI.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, LitStr};
+
+/// This procedural macro implements the `HasStaticTypeId` trait for a Rust
+/// type. The GUID of the type is computed from the name of the type, the same
+/// way `Guid::from_str` does.
+///
+/// The name defaults to the identifier of the type but can be overridden with
+/// the `#[type_id(name = "my::custom::Name")]` attribute.
+#[proc_macro_derive(HasStaticTypeId, attributes(type_id))]
+pub fn has_static_type_id_derive(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+
+    match impl_has_static_type_id(&derive_input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn impl_has_static_type_id(derive_input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    // A single static type id cannot represent all instantiations of a generic type
+    if !derive_input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &derive_input.generics,
+            "`HasStaticTypeId` cannot be derived for generic types",
+        ));
+    }
+
+    let mut type_name = None;
+    for attr in derive_input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("type_id"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                type_name = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `type_id` attribute, expected `name`"))
+            }
+        })?;
+    }
+
+    let ident = &derive_input.ident;
+    let type_name = type_name.unwrap_or_else(|| ident.to_string());
+
+    Ok(quote! {
+        impl ::mun_abi::HasStaticTypeId for #ident {
+            fn type_id() -> &'static ::mun_abi::TypeId<'static> {
+                const TYPE_ID: ::mun_abi::TypeId<'static> =
+                    ::mun_abi::TypeId::Concrete(::mun_abi::Guid::from_str(#type_name));
+                &TYPE_ID
+            }
+        }
+    })
+}
@@ -0,0 +1,34 @@
+use mun_abi::{Guid, HasStaticTypeId, TypeId};
+use mun_abi_derive::HasStaticTypeId;
+
+#[derive(HasStaticTypeId)]
+#[allow(dead_code)]
+struct Foo {
+    a: i32,
+}
+
+#[derive(HasStaticTypeId)]
+#[type_id(name = "my::custom::Name")]
+#[allow(dead_code)]
+enum Bar {
+    A,
+    B,
+}
+
+#[test]
+fn derive_type_name() {
+    // md5("Foo")
+    let guid = Guid([
+        0x13, 0x56, 0xc6, 0x7d, 0x7a, 0xd1, 0x63, 0x8d, 0x81, 0x6b, 0xfb, 0x82, 0x2d, 0xd2, 0xc2,
+        0x5d,
+    ]);
+    assert_eq!(Foo::type_id(), &TypeId::Concrete(guid));
+}
+
+#[test]
+fn derive_custom_type_name() {
+    assert_eq!(
+        Bar::type_id(),
+        &TypeId::Concrete(Guid::from_str("my::custom::Name"))
+    );
+}
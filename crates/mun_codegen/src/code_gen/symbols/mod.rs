@@ -247,6 +247,9 @@ fn get_function_definition_array<'ink, 'a>(
                     value.as_global_value().as_pointer_value(),
                     context,
                 ),
+                // Only functions with a body end up in the assembly's function table, extern
+                // functions are linked by the host through the dispatch table instead.
+                is_extern: f.is_extern(db),
             }
         })
         .into_value(context)
@@ -392,6 +395,9 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
         num_functions,
         types,
         num_types,
+        // Cross-assembly type references are not supported yet
+        dependencies: Value::null(context),
+        num_dependencies: 0,
     };
 
     // Construct the dispatch table struct
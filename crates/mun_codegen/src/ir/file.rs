@@ -47,9 +47,7 @@ pub(crate) fn gen_file_ir<'ink>(
                 functions.insert(f, fun);
 
                 let fn_sig = f.ty(code_gen.db).callable_sig(code_gen.db).unwrap();
-                if f.visibility(code_gen.db).is_externally_visible()
-                    && !fn_sig.marshallable(code_gen.db)
-                {
+                if f.is_exported(code_gen.db) && !fn_sig.marshallable(code_gen.db) {
                     let wrapper_fun = function::gen_public_prototype(
                         code_gen.db,
                         &code_gen.hir_types,
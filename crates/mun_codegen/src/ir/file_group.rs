@@ -62,9 +62,7 @@ pub(crate) fn gen_file_group_ir<'ink>(
                 );
 
                 let fn_sig = f.ty(code_gen.db).callable_sig(code_gen.db).unwrap();
-                if f.visibility(code_gen.db).is_externally_visible()
-                    && !fn_sig.marshallable(code_gen.db)
-                {
+                if f.is_exported(code_gen.db) && !fn_sig.marshallable(code_gen.db) {
                     intrinsics::collect_wrapper_body(
                         code_gen.context,
                         code_gen.target_machine.get_target_data(),
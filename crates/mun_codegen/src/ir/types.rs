@@ -96,6 +96,7 @@ pub struct FunctionPrototype<'ink> {
 pub struct FunctionDefinition<'ink> {
     pub prototype: FunctionPrototype<'ink>,
     pub fn_ptr: Value<'ink, *const fn()>,
+    pub is_extern: bool,
 }
 
 #[derive(AsValue)]
@@ -108,13 +109,21 @@ pub struct StructDefinition<'ink> {
     pub memory_kind: abi::StructMemoryKind,
 }
 
+#[derive(AsValue)]
+pub struct AssemblyDependency {
+    pub guid: abi::Guid,
+    pub min_abi_version: u32,
+}
+
 #[derive(AsValue)]
 pub struct ModuleInfo<'ink> {
     pub path: Value<'ink, *const u8>,
     pub functions: Value<'ink, *const FunctionDefinition<'ink>>,
     pub types: Value<'ink, *const TypeDefinition<'ink>>,
+    pub dependencies: Value<'ink, *const AssemblyDependency>,
     pub num_functions: u32,
     pub num_types: u32,
+    pub num_dependencies: u32,
 }
 
 #[derive(AsValue)]
@@ -40,6 +40,7 @@ fn abi_struct_sizes() {
     test_type_size::<abi::TypeDefinition<'_>, ir::TypeDefinition<'_>>(&type_context);
     test_type_size::<abi::FunctionSignature<'_>, ir::FunctionSignature<'_>>(&type_context);
     test_type_size::<abi::FunctionPrototype<'_>, ir::FunctionPrototype<'_>>(&type_context);
+    test_type_size::<abi::AssemblyDependency, ir::AssemblyDependency>(&type_context);
     test_type_size::<abi::ModuleInfo<'_>, ir::ModuleInfo<'_>>(&type_context);
     test_type_size::<abi::DispatchTable<'_>, ir::DispatchTable<'_>>(&type_context);
     test_type_size::<abi::TypeLut<'_>, ir::TypeLut<'_>>(&type_context);
@@ -2,7 +2,10 @@ use std::cell::RefCell;
 
 use inkwell::{context::Context, OptimizationLevel};
 use mun_db::Upcast;
-use mun_hir::{diagnostics::DiagnosticSink, HirDatabase};
+use mun_hir::{
+    diagnostics::{DiagnosticSink, Severity},
+    HirDatabase,
+};
 use mun_hir_input::{SourceDatabase, WithFixture};
 use mun_target::spec::Target;
 
@@ -1086,6 +1089,11 @@ fn test_snapshot_with_optimization(name: &str, text: &str, opt: OptimizationLeve
     // Build and extra diagnostics
     let messages = RefCell::new(Vec::new());
     let mut sink = DiagnosticSink::new(|diag| {
+        // Warnings do not prevent code generation
+        if diag.severity() == Severity::Warning {
+            return;
+        }
+
         let file_id = diag.source().file_id;
         let line_index = db.line_index(file_id);
         let source_root_id = db.file_source_root(file_id);
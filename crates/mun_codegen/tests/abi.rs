@@ -42,6 +42,7 @@ fn test_abi_compatibility() {
                 return_type: None,
               ),
             ),
+            is_extern: false,
           ),
           FunctionDefinition(
             prototype: FunctionPrototype(
@@ -53,6 +54,7 @@ fn test_abi_compatibility() {
                 return_type: Some(Concrete("17797a74-19d6-3217-d235-954317885bfa")),
               ),
             ),
+            is_extern: false,
           ),
         ],
         types: [
@@ -99,6 +101,7 @@ fn test_abi_compatibility() {
             )),
           ),
         ],
+        dependencies: [],
       ),
       dispatch_table: DispatchTable(
         prototypes: [
@@ -2,7 +2,7 @@
 //! compilation by retaining state from previous compilation.
 
 use mun_codegen::{AssemblyIr, CodeGenDatabase, ModuleGroup, TargetAssembly};
-use mun_hir::{AstDatabase, DiagnosticSink, Module};
+use mun_hir::{diagnostics::Severity, AstDatabase, DiagnosticSink, Module};
 use mun_hir_input::{FileId, PackageSet, SourceDatabase, SourceRoot, SourceRootId};
 use mun_paths::RelativePathBuf;
 
@@ -242,7 +242,10 @@ impl Driver {
                     module.diagnostics(
                         self.db.upcast(),
                         &mut DiagnosticSink::new(|d| {
-                            has_error = true;
+                            // Warnings are emitted but do not prevent compilation
+                            if d.severity() == Severity::Error {
+                                has_error = true;
+                            }
                             if let Err(e) =
                                 emit_hir_diagnostic(d, &self.db, file_id, emit_colors, writer)
                             {
@@ -0,0 +1,62 @@
+//! Queries that analyze which functions call each other.
+
+use std::sync::Arc;
+
+use rustc_hash::FxHashSet;
+
+use crate::{expr::Expr, ids::FunctionId, ty::CallableDef, HirDatabase};
+
+/// Returns all functions that are called from the body of the specified
+/// function, in order of first occurrence.
+pub(crate) fn callees_query(db: &dyn HirDatabase, def: FunctionId) -> Arc<[FunctionId]> {
+    let body = db.body(def.into());
+    let infer = db.infer(def.into());
+
+    let mut callees = Vec::new();
+    for (expr_id, expr) in body.exprs() {
+        let callee = match expr {
+            Expr::Call { callee, .. } => match infer[*callee].as_callable_def() {
+                Some(CallableDef::Function(f)) => Some(f.id),
+                _ => None,
+            },
+            Expr::MethodCall { .. } => infer.method_resolution(expr_id),
+            _ => None,
+        };
+        if let Some(callee) = callee {
+            if !callees.contains(&callee) {
+                callees.push(callee);
+            }
+        }
+    }
+    callees.into()
+}
+
+/// Returns true if the body of the specified function contains a call to
+/// itself.
+pub(crate) fn is_directly_recursive_query(db: &dyn HirDatabase, def: FunctionId) -> bool {
+    db.callees(def).contains(&def)
+}
+
+/// Returns true if the specified function calls itself through one or more
+/// other functions. The call graph is followed across module boundaries.
+pub(crate) fn is_mutually_recursive_query(db: &dyn HirDatabase, def: FunctionId) -> bool {
+    let mut visited = FxHashSet::default();
+    let mut stack = db
+        .callees(def)
+        .iter()
+        .copied()
+        .filter(|&callee| callee != def)
+        .collect::<Vec<_>>();
+    while let Some(function) = stack.pop() {
+        if !visited.insert(function) {
+            continue;
+        }
+        for &callee in db.callees(function).iter() {
+            if callee == def {
+                return true;
+            }
+            stack.push(callee);
+        }
+    }
+    false
+}
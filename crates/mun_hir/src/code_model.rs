@@ -12,12 +12,12 @@ use std::sync::Arc;
 pub use self::{
     function::{Function, FunctionData},
     module::{Module, ModuleDef},
-    package::Package,
+    package::{ExportedSymbol, Package},
     primitive_type::PrimitiveType,
     r#impl::{AssocItem, ImplData},
     r#struct::{Field, Struct, StructData, StructKind, StructMemoryKind},
     src::HasSource,
-    type_alias::{TypeAlias, TypeAliasData},
+    type_alias::{CyclicAliasError, TypeAlias, TypeAliasData},
 };
 use crate::{expr::BodySourceMap, HirDatabase, Name};
 
@@ -17,6 +17,9 @@ use crate::{
     InferenceResult, Name, Pat, Ty, Visibility,
 };
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct Function {
     pub(crate) id: FunctionId,
@@ -173,6 +176,21 @@ impl Function {
             .collect()
     }
 
+    /// Returns the names of the parameters of the function in declaration
+    /// order, including `self`. Parameters that are not bound to a name, like
+    /// wildcards, are named `_`.
+    pub fn parameter_names(self, db: &dyn HirDatabase) -> Vec<Name> {
+        let body = self.body(db);
+        body.self_param()
+            .into_iter()
+            .chain(body.params())
+            .map(|(pat_id, _)| match &body[*pat_id] {
+                Pat::Bind { name } => name.clone(),
+                _ => Name::new("_"),
+            })
+            .collect()
+    }
+
     pub fn ret_type(self, db: &dyn HirDatabase) -> Ty {
         let resolver = self.id.resolver(db.upcast());
         let data = self.data(db.upcast());
@@ -187,6 +205,30 @@ impl Function {
         db.fn_data(self.id).flags.is_extern()
     }
 
+    /// Returns true if the body of this function contains a call to itself.
+    pub fn is_directly_recursive(self, db: &dyn HirDatabase) -> bool {
+        db.is_directly_recursive(self.id)
+    }
+
+    /// Returns true if this function calls itself through one or more other
+    /// functions.
+    pub fn is_mutually_recursive(self, db: &dyn HirDatabase) -> bool {
+        db.is_mutually_recursive(self.id)
+    }
+
+    /// Returns true if this function calls itself, either directly or through
+    /// other functions.
+    pub fn is_recursive(self, db: &dyn HirDatabase) -> bool {
+        self.is_directly_recursive(db) || self.is_mutually_recursive(db)
+    }
+
+    /// Returns true if the function is exported in the ABI of its assembly.
+    /// That is the case for externally visible functions that are not
+    /// `extern`.
+    pub fn is_exported(self, db: &dyn HirDatabase) -> bool {
+        !self.is_extern(db) && self.visibility(db).is_externally_visible()
+    }
+
     pub(crate) fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
         db.body_with_source_map(self.id.into()).1
     }
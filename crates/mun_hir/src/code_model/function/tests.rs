@@ -0,0 +1,94 @@
+use mun_hir_input::WithFixture;
+
+use crate::{
+    mock::MockDatabase,
+    utils::tests::{find_function, root_module},
+};
+
+#[test]
+fn function_is_exported() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    pub fn exported() {}
+    pub(package) fn package_only() {}
+    fn private() {}
+    pub extern fn external();
+    "#,
+    );
+
+    let mut exported = root_module(&db)
+        .all_functions(&db)
+        .into_iter()
+        .filter(|f| f.is_exported(&db))
+        .map(|f| f.name(&db).to_string())
+        .collect::<Vec<_>>();
+    exported.sort();
+    assert_eq!(exported, ["exported"]);
+}
+
+#[test]
+fn function_parameter_names() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    fn add(a: i32, b: i32) -> i32 { a + b }
+    fn ignore(_: i32, value: f64) {}
+    fn none() {}
+    struct Foo;
+    impl Foo {
+        fn scale(self, factor: f32) {}
+    }
+    "#,
+    );
+
+    let parameter_names = |name| {
+        find_function(&db, name)
+            .parameter_names(&db)
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(parameter_names("add"), ["a", "b"]);
+    assert_eq!(parameter_names("ignore"), ["_", "value"]);
+    assert!(parameter_names("none").is_empty());
+    assert_eq!(parameter_names("scale"), ["self", "factor"]);
+}
+
+#[test]
+fn function_is_recursive() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    fn fact(n: i32) -> i32 { if n <= 1 { 1 } else { n * fact(n - 1) } }
+    fn is_even(n: i32) -> bool { if n == 0 { true } else { is_odd(n - 1) } }
+    fn is_odd(n: i32) -> bool { if n == 0 { false } else { foo::is_even(n - 1) } }
+    fn caller() -> i32 { fact(3) }
+    fn leaf() {}
+    struct Foo;
+    impl Foo {
+        fn visit(self) { self.visit() }
+    }
+    //- /foo.mun
+    pub fn is_even(n: i32) -> bool { super::is_even(n) }
+    "#,
+    );
+
+    let fact = find_function(&db, "fact");
+    assert!(fact.is_directly_recursive(&db));
+    assert!(!fact.is_mutually_recursive(&db));
+    assert!(fact.is_recursive(&db));
+
+    let is_odd = find_function(&db, "is_odd");
+    assert!(!is_odd.is_directly_recursive(&db));
+    assert!(is_odd.is_mutually_recursive(&db));
+    assert!(is_odd.is_recursive(&db));
+
+    let visit = find_function(&db, "visit");
+    assert!(visit.is_directly_recursive(&db));
+
+    for name in ["caller", "leaf"] {
+        assert!(!find_function(&db, name).is_recursive(&db), "{name}");
+    }
+}
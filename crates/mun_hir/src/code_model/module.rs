@@ -1,7 +1,10 @@
 use mun_hir_input::{FileId, ModuleId};
 
 use super::{r#impl::Impl, AssocItem, Function, Package, PrimitiveType, Struct, TypeAlias};
-use crate::{ids::ItemDefinitionId, DiagnosticSink, HirDatabase};
+use crate::{ids::ItemDefinitionId, DiagnosticSink, HasVisibility, HirDatabase};
+
+#[cfg(test)]
+mod tests;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct Module {
@@ -126,6 +129,58 @@ impl Module {
             .collect()
     }
 
+    /// Returns all functions declared in this module and, recursively, in all
+    /// of its child modules. This includes the functions of `impl` blocks.
+    pub fn all_functions(self, db: &dyn HirDatabase) -> Vec<Function> {
+        self.descendants(db)
+            .into_iter()
+            .flat_map(|module| {
+                let declared = module.declarations(db).into_iter().filter_map(|decl| {
+                    if let ModuleDef::Function(f) = decl {
+                        Some(f)
+                    } else {
+                        None
+                    }
+                });
+                let associated = module.impls(db).into_iter().flat_map(|item| {
+                    item.items(db).into_iter().map(|associated_item| {
+                        let AssocItem::Function(fun) = associated_item;
+                        fun
+                    })
+                });
+                declared.chain(associated).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Returns all structs declared in this module and, recursively, in all of
+    /// its child modules.
+    pub fn all_structs(self, db: &dyn HirDatabase) -> Vec<Struct> {
+        self.descendants(db)
+            .into_iter()
+            .flat_map(|module| module.declarations(db))
+            .filter_map(|decl| {
+                if let ModuleDef::Struct(s) = decl {
+                    Some(s)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Returns this module followed by all of its descendants, in depth-first
+    /// order.
+    fn descendants(self, db: &dyn HirDatabase) -> Vec<Module> {
+        let mut result = Vec::new();
+        let mut stack = vec![self];
+        while let Some(module) = stack.pop() {
+            result.push(module);
+            stack.extend(module.children(db).into_iter().rev());
+        }
+        result
+    }
+
     /// Returns the path from this module to the root module
     pub fn path_to_root(self, db: &dyn HirDatabase) -> Vec<Module> {
         let mut res = vec![self];
@@ -148,6 +203,11 @@ impl Module {
         .collect()
     }
 
+    /// Returns true if `def` is visible from this module.
+    pub fn visibility_of(self, db: &dyn HirDatabase, def: impl HasVisibility) -> bool {
+        def.is_visible_from(db, self)
+    }
+
     pub fn impls(self, db: &dyn HirDatabase) -> Vec<Impl> {
         let package_defs = db.package_defs(self.id.package);
         package_defs.modules[self.id.local_id]
@@ -0,0 +1,104 @@
+use mun_hir_input::WithFixture;
+
+use crate::{mock::MockDatabase, utils::tests::root_module, Package};
+
+#[test]
+fn module_all_functions_and_structs() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /main.mun
+    fn main() {}
+    struct Main;
+    //- /foo.mun
+    fn foo() {}
+    struct Foo;
+    impl Foo {
+        fn new() -> Self { Foo }
+    }
+    //- /foo/bar.mun
+    fn bar() {}
+    struct Bar;
+    "#,
+    );
+
+    let root_module = root_module(&db);
+
+    let mut functions = root_module
+        .all_functions(&db)
+        .into_iter()
+        .map(|f| f.name(&db).to_string())
+        .collect::<Vec<_>>();
+    functions.sort();
+    assert_eq!(functions, ["bar", "foo", "main", "new"]);
+
+    let mut structs = root_module
+        .all_structs(&db)
+        .into_iter()
+        .map(|s| s.name(&db).to_string())
+        .collect::<Vec<_>>();
+    structs.sort();
+    assert_eq!(structs, ["Bar", "Foo", "Main"]);
+}
+
+#[test]
+fn module_visibility_of() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    fn root_private() {}
+    //- /foo.mun
+    pub fn public() {}
+    pub(package) fn package_only() {}
+    pub(super) fn super_only() {}
+    fn private() {}
+    struct Private;
+    //- /foo/bar.mun
+    fn nested() {}
+    //- /baz.mun
+    fn sibling() {}
+    "#,
+    );
+
+    let package = Package::all(&db).into_iter().next().unwrap();
+    let modules = package.modules(&db);
+    let find_module = |name| {
+        *modules
+            .iter()
+            .find(|m| m.name(&db).as_deref() == Some(name))
+            .unwrap()
+    };
+    let root = package.root_module(&db);
+    let foo = find_module("foo");
+    let bar = find_module("bar");
+    let baz = find_module("baz");
+
+    let foo_functions = foo.all_functions(&db);
+    let find_function = |name| {
+        *foo_functions
+            .iter()
+            .find(|f| f.name(&db).as_str() == Some(name))
+            .unwrap()
+    };
+
+    for module in [root, foo, bar, baz] {
+        assert!(module.visibility_of(&db, find_function("public")));
+        assert!(module.visibility_of(&db, find_function("package_only")));
+    }
+
+    let super_only = find_function("super_only");
+    assert!(root.visibility_of(&db, super_only));
+    assert!(baz.visibility_of(&db, super_only));
+
+    let private = find_function("private");
+    assert!(foo.visibility_of(&db, private));
+    assert!(bar.visibility_of(&db, private));
+    assert!(!root.visibility_of(&db, private));
+    assert!(!baz.visibility_of(&db, private));
+
+    let private_struct = foo.all_structs(&db)[0];
+    assert!(foo.visibility_of(&db, private_struct));
+    assert!(!baz.visibility_of(&db, private_struct));
+
+    let root_private = root.all_functions(&db)[0];
+    assert!(baz.visibility_of(&db, root_private));
+}
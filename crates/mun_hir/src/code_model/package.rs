@@ -1,7 +1,18 @@
 use mun_hir_input::{ModuleId, PackageId};
 
-use super::Module;
-use crate::HirDatabase;
+use super::{Function, Module, Struct};
+use crate::{HasVisibility, HirDatabase};
+
+#[cfg(test)]
+mod tests;
+
+/// A publicly visible item of a [`Package`] that is exported from the
+/// resulting assembly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportedSymbol {
+    Function(Function),
+    Struct(Struct),
+}
 
 /// A `Package` describes a single package.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -41,4 +52,22 @@ impl Package {
             })
             .collect()
     }
+
+    /// Returns all exported functions and publicly visible structs of all
+    /// modules in the package. Functions declared in `impl` blocks are
+    /// included.
+    pub fn all_exported_symbols(self, db: &dyn HirDatabase) -> Vec<ExportedSymbol> {
+        let root = self.root_module(db);
+        let functions = root
+            .all_functions(db)
+            .into_iter()
+            .filter(|function| function.is_exported(db))
+            .map(ExportedSymbol::Function);
+        let structs = root
+            .all_structs(db)
+            .into_iter()
+            .filter(|s| s.visibility(db).is_externally_visible())
+            .map(ExportedSymbol::Struct);
+        functions.chain(structs).collect()
+    }
 }
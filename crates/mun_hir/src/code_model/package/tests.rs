@@ -0,0 +1,40 @@
+use mun_hir_input::WithFixture;
+
+use crate::{mock::MockDatabase, ExportedSymbol, Package};
+
+#[test]
+fn package_all_exported_symbols() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /main.mun
+    pub fn main() {}
+    fn private() {}
+    pub extern fn external();
+    pub struct Main;
+    //- /foo.mun
+    pub fn foo() {}
+    pub(package) fn package_only() {}
+    struct Foo;
+    impl Foo {
+        pub fn new() -> Self { Foo }
+    }
+    //- /foo/bar.mun
+    pub struct Bar;
+    "#,
+    );
+
+    let package = Package::all(&db).into_iter().next().unwrap();
+    let mut symbols = package
+        .all_exported_symbols(&db)
+        .into_iter()
+        .map(|symbol| match symbol {
+            ExportedSymbol::Function(f) => format!("fn {}", f.name(&db)),
+            ExportedSymbol::Struct(s) => format!("struct {}", s.name(&db)),
+        })
+        .collect::<Vec<_>>();
+    symbols.sort();
+    assert_eq!(
+        symbols,
+        ["fn foo", "fn main", "fn new", "struct Bar", "struct Main"]
+    );
+}
@@ -1,4 +1,4 @@
-use std::{fmt, iter::once, sync::Arc};
+use std::{alloc::Layout, fmt, iter::once, sync::Arc};
 
 pub use ast::StructMemoryKind;
 use la_arena::{Arena, Idx};
@@ -8,7 +8,9 @@ use mun_syntax::{
     ast::{NameOwner, TypeAscriptionOwner, VisibilityOwner},
 };
 
-use super::Module;
+use mun_target::abi::{Align, TargetDataLayout};
+
+use super::{r#impl::Impl, AssocItem, Function, Module};
 use crate::{
     has_module::HasModule,
     ids::{Lookup, StructId},
@@ -18,11 +20,15 @@ use crate::{
     ty::lower::LowerTyMap,
     type_ref::{LocalTypeRefId, TypeRefMap, TypeRefSourceMap},
     visibility::RawVisibility,
-    DefDatabase, DiagnosticSink, HasVisibility, HirDatabase, Name, Ty, Visibility,
+    DefDatabase, DiagnosticSink, FloatBitness, HasVisibility, HirDatabase, IntBitness, Name,
+    ResolveBitness, Ty, TyKind, Visibility,
 };
 
 pub(crate) mod validator;
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Struct {
     pub(crate) id: StructId,
@@ -60,6 +66,23 @@ impl Field {
         self.id.into_raw().into()
     }
 
+    /// Returns the offset in bytes of this field from the start of the parent
+    /// struct on the compilation target. Fields are laid out in declaration
+    /// order, like the fields of a `#[repr(C)]` struct.
+    ///
+    /// Returns `None` if the layout of the parent struct cannot be determined,
+    /// see [`Struct::memory_layout`].
+    pub fn byte_offset(self, db: &dyn HirDatabase) -> Option<usize> {
+        let fields = self.parent.fields(db);
+        let (_, offsets) = repr_c_field_offsets(
+            db,
+            fields.iter().map(|field| field.ty(db)),
+            &mut vec![self.parent],
+        )?;
+        let index = fields.iter().position(|field| *field == self)?;
+        Some(offsets[index])
+    }
+
     /// Returns the ID of the field with relation to the parent struct
     pub(crate) fn id(self) -> LocalFieldId {
         self.id
@@ -119,19 +142,143 @@ impl Struct {
         db.type_for_def(self.into(), Namespace::Types)
     }
 
+    /// Returns all functions that are associated with the struct through
+    /// `impl` blocks.
+    pub fn assoc_functions(self, db: &dyn HirDatabase) -> Vec<Function> {
+        let inherent_impls = db.inherent_impls_in_package(self.module(db).package().id);
+        inherent_impls
+            .for_self_ty(&self.ty(db))
+            .iter()
+            .flat_map(|impl_id| Impl::from(*impl_id).items(db))
+            .map(|item| {
+                let AssocItem::Function(fun) = item;
+                fun
+            })
+            .collect()
+    }
+
+    /// Returns the function with the specified `name` that is associated with
+    /// the struct through an `impl` block, if it exists.
+    pub fn assoc_function_by_name(self, db: &dyn HirDatabase, name: &Name) -> Option<Function> {
+        self.assoc_functions(db)
+            .into_iter()
+            .find(|fun| fun.name(db) == *name)
+    }
+
     pub fn lower(self, db: &dyn HirDatabase) -> Arc<LowerTyMap> {
         db.lower_struct(self)
     }
 
+    /// Returns the memory layout of the struct when it is stored in a field,
+    /// argument, or variable on the compilation target.
+    ///
+    /// The fields of a value struct are laid out in declaration order, like a
+    /// `#[repr(C)]` struct. A garbage collected struct is referenced through a
+    /// pointer, so it has the layout of a pointer.
+    ///
+    /// Returns `None` if the layout cannot be determined. That is the case if a
+    /// value struct (indirectly) contains itself, if it contains a type that
+    /// could not be resolved, or if its size overflows.
+    pub fn memory_layout(self, db: &dyn HirDatabase) -> Option<Layout> {
+        struct_memory_layout(db, self, &mut Vec::new())
+    }
+
     pub fn diagnostics(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink<'_>) {
         let data = self.data(db.upcast());
         let lower = self.lower(db);
         lower.add_diagnostics(db, self.file_id(db), data.type_ref_source_map(), sink);
         let validator = validator::StructValidator::new(self, db, self.file_id(db));
         validator.validate_privacy(sink);
+        validator.validate_field_types(sink);
+        validator.validate_unused_fields(sink);
     }
 }
 
+/// Returns the memory layout of the struct `s`. `visiting` contains the value
+/// structs whose layout is currently being computed, which is used to detect
+/// structs that contain themselves.
+fn struct_memory_layout(
+    db: &dyn HirDatabase,
+    s: Struct,
+    visiting: &mut Vec<Struct>,
+) -> Option<Layout> {
+    match s.data(db.upcast()).memory_kind {
+        StructMemoryKind::Gc => pointer_layout(&db.target_data_layout()),
+        StructMemoryKind::Value => {
+            if visiting.contains(&s) {
+                return None;
+            }
+
+            visiting.push(s);
+            let layout = repr_c_field_offsets(
+                db,
+                s.fields(db).into_iter().map(|field| field.ty(db)),
+                visiting,
+            )
+            .map(|(layout, _)| layout);
+            visiting.pop();
+            layout
+        }
+    }
+}
+
+/// Returns the layout of a pointer on the `target`.
+fn pointer_layout(target: &TargetDataLayout) -> Option<Layout> {
+    layout_from_target(target.pointer_size.bytes(), target.pointer_align.abi)
+}
+
+/// Returns the layout of a `#[repr(C)]` struct with fields of the specified
+/// `types`, together with the offset in bytes of every field.
+fn repr_c_field_offsets(
+    db: &dyn HirDatabase,
+    types: impl IntoIterator<Item = Ty>,
+    visiting: &mut Vec<Struct>,
+) -> Option<(Layout, Vec<usize>)> {
+    let mut layout = Layout::new::<()>();
+    let mut offsets = Vec::new();
+    for ty in types {
+        let (new_layout, offset) = layout.extend(ty_memory_layout(db, &ty, visiting)?).ok()?;
+        layout = new_layout;
+        offsets.push(offset);
+    }
+    Some((layout.pad_to_align(), offsets))
+}
+
+/// Returns the memory layout of a value of type `ty` on the compilation
+/// target, or `None` if it cannot be determined.
+fn ty_memory_layout(db: &dyn HirDatabase, ty: &Ty, visiting: &mut Vec<Struct>) -> Option<Layout> {
+    let target = db.target_data_layout();
+    match ty.interned() {
+        TyKind::Struct(s) => struct_memory_layout(db, *s, visiting),
+        TyKind::Float(float_ty) => match float_ty.bitness.resolve(&target) {
+            FloatBitness::X32 => layout_from_target(4, target.f32_align.abi),
+            FloatBitness::X64 => layout_from_target(8, target.f64_align.abi),
+        },
+        TyKind::Int(int_ty) => match int_ty.bitness.resolve(&target) {
+            IntBitness::X8 => layout_from_target(1, target.i8_align.abi),
+            IntBitness::X16 => layout_from_target(2, target.i16_align.abi),
+            IntBitness::X32 => layout_from_target(4, target.i32_align.abi),
+            IntBitness::X64 => layout_from_target(8, target.i64_align.abi),
+            IntBitness::X128 => layout_from_target(16, target.i128_align.abi),
+            IntBitness::Xsize => None,
+        },
+        TyKind::Bool => layout_from_target(1, target.i8_align.abi),
+        TyKind::Tuple(_, substitution) => {
+            repr_c_field_offsets(db, substitution.iter().cloned(), visiting)
+                .map(|(layout, _)| layout)
+        }
+        TyKind::TypeAlias(alias) => ty_memory_layout(db, &alias.target_type(db), visiting),
+        TyKind::Array(_) => pointer_layout(&target),
+        TyKind::Never | TyKind::FnDef(..) => Some(Layout::new::<()>()),
+        TyKind::InferenceVar(_) | TyKind::Unknown => None,
+    }
+}
+
+/// Constructs a `Layout` from a size in bytes and an alignment of the target.
+fn layout_from_target(size: u64, align: Align) -> Option<Layout> {
+    Layout::from_size_align(size as usize, align.bytes() as usize).ok()
+}
+
 /// A single field of a record
 /// ```mun
 /// struct Foo {
@@ -0,0 +1,135 @@
+use std::alloc::Layout;
+
+use mun_hir_input::WithFixture;
+
+use crate::{mock::MockDatabase, utils::tests::find_struct, Name};
+
+#[test]
+fn struct_memory_layout() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Value {
+        a: u8,
+        b: u32,
+        c: u16,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Nested {
+        a: Value,
+        b: bool,
+    }
+
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    struct(value) Value { a: u8, b: u32, c: u16 }
+    struct(value) Nested { a: Value, b: bool }
+    struct Gc { a: u64 }
+    "#,
+    );
+
+    assert_eq!(
+        find_struct(&db, "Value").memory_layout(&db),
+        Some(Layout::new::<Value>())
+    );
+    assert_eq!(
+        find_struct(&db, "Nested").memory_layout(&db),
+        Some(Layout::new::<Nested>())
+    );
+    assert_eq!(
+        find_struct(&db, "Gc").memory_layout(&db),
+        Some(Layout::new::<*const u8>())
+    );
+}
+
+#[test]
+fn struct_memory_layout_recursive() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    struct(value) Foo { foo: Foo }
+    struct(value) Bar { baz: Baz }
+    struct(value) Baz { bar: Bar }
+    struct(value) Unknown { a: DoesNotExist }
+    struct Gc { gc: Gc }
+    "#,
+    );
+
+    for name in ["Foo", "Bar", "Baz", "Unknown"] {
+        let s = find_struct(&db, name);
+        assert_eq!(s.memory_layout(&db), None, "{name}");
+        assert_eq!(s.fields(&db)[0].byte_offset(&db), None, "{name}");
+    }
+
+    let gc = find_struct(&db, "Gc");
+    assert!(gc.memory_layout(&db).is_some());
+    assert_eq!(gc.fields(&db)[0].byte_offset(&db), Some(0));
+}
+
+#[test]
+fn field_byte_offset() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    struct(value) Inner { a: u8, b: u16 }
+    struct Foo { a: u8, b: u32, c: u16, d: u64, e: bool, f: Inner, g: f32 }
+    "#,
+    );
+
+    let offsets = find_struct(&db, "Foo")
+        .fields(&db)
+        .into_iter()
+        .map(|field| (field.name(&db).to_string(), field.byte_offset(&db)))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        offsets,
+        [
+            ("a".to_owned(), Some(0)),
+            ("b".to_owned(), Some(4)),
+            ("c".to_owned(), Some(8)),
+            ("d".to_owned(), Some(16)),
+            ("e".to_owned(), Some(24)),
+            ("f".to_owned(), Some(26)),
+            ("g".to_owned(), Some(32)),
+        ]
+    );
+}
+
+#[test]
+fn struct_assoc_functions() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    struct Foo;
+    struct Bar;
+    impl Foo {
+        fn new() -> Self { Foo }
+        fn bar() {}
+    }
+    impl Foo {
+        fn baz(self) {}
+    }
+    "#,
+    );
+
+    let foo = find_struct(&db, "Foo");
+    let mut names = foo
+        .assoc_functions(&db)
+        .into_iter()
+        .map(|fun| fun.name(&db).to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["bar", "baz", "new"]);
+
+    let baz = foo
+        .assoc_function_by_name(&db, &Name::new("baz"))
+        .expect("`baz` should be associated with `Foo`");
+    assert_eq!(baz.name(&db).to_string(), "baz");
+    assert!(foo
+        .assoc_function_by_name(&db, &Name::new("missing"))
+        .is_none());
+
+    assert!(find_struct(&db, "Bar").assoc_functions(&db).is_empty());
+}
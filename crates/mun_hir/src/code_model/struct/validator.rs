@@ -1,9 +1,17 @@
 use mun_hir_input::FileId;
+use mun_syntax::{ast, AstNode, Direction, SyntaxKind, SyntaxNode, SyntaxNodePtr, TextRange};
+use rustc_hash::FxHashSet;
 
-use super::Struct;
+use super::{LocalFieldId, Struct, StructMemoryKind};
 use crate::{
-    diagnostics::ExportedPrivate, resolve::HasResolver, visibility::RawVisibility, DiagnosticSink,
-    HasVisibility, HirDatabase, Ty, Visibility,
+    diagnostics::{
+        ExportedPrivate, NeverFieldType, RecursiveValueStruct, SuggestRemoveField, UnusedField,
+    },
+    expr::Expr,
+    resolve::HasResolver,
+    ty::lower::CallableDef,
+    visibility::RawVisibility,
+    DiagnosticSink, HasSource, HasVisibility, HirDatabase, InFile, Ty, TyKind, Visibility,
 };
 
 #[cfg(test)]
@@ -59,4 +67,166 @@ impl<'a> StructValidator<'a> {
                 });
             });
     }
+
+    /// Emits an error for every field whose type cannot be laid out in memory.
+    /// That is the case for types that contain `never`, and for types through
+    /// which a value struct contains itself by value.
+    pub fn validate_field_types(&self, sink: &mut DiagnosticSink<'_>) {
+        let struct_data = self.strukt.data(self.db.upcast());
+        let lower = self.strukt.lower(self.db);
+        let is_value_struct = struct_data.memory_kind == StructMemoryKind::Value;
+
+        for (_, field_data) in struct_data.fields.iter() {
+            let ty = &lower[field_data.type_ref];
+            let type_ref = struct_data
+                .type_ref_source_map()
+                .type_ref_syntax(field_data.type_ref)
+                .unwrap();
+
+            if contains_never(self.db, ty) {
+                sink.push(NeverFieldType {
+                    file: self.file_id,
+                    type_ref,
+                });
+            } else if is_value_struct && self.contains_by_value(ty, &mut FxHashSet::default()) {
+                sink.push(RecursiveValueStruct {
+                    file: self.file_id,
+                    type_ref,
+                    struct_name: struct_data.name.clone(),
+                });
+            }
+        }
+    }
+
+    /// Returns true if a value of type `ty` stores the validated struct by
+    /// value, either directly or through other value types. Garbage collected
+    /// structs and arrays are stored by reference, so they break the chain.
+    fn contains_by_value(&self, ty: &Ty, visited: &mut FxHashSet<Struct>) -> bool {
+        match ty.interned() {
+            TyKind::Struct(s)
+                if s.data(self.db.upcast()).memory_kind == StructMemoryKind::Value =>
+            {
+                *s == self.strukt
+                    || (visited.insert(*s)
+                        && s.fields(self.db)
+                            .into_iter()
+                            .any(|field| self.contains_by_value(&field.ty(self.db), visited)))
+            }
+            TyKind::Tuple(_, substitution) => substitution
+                .iter()
+                .any(|ty| self.contains_by_value(ty, visited)),
+            TyKind::TypeAlias(alias) => {
+                self.contains_by_value(&alias.target_type(self.db), visited)
+            }
+            _ => false,
+        }
+    }
+
+    /// Emits a warning for every private field of the struct that is never
+    /// read or written in any function body of the module that defines the
+    /// struct. Private fields are also accessible from child modules, so their
+    /// function bodies are taken into account as well.
+    pub fn validate_unused_fields(&self, sink: &mut DiagnosticSink<'_>) {
+        let struct_data = self.strukt.data(self.db.upcast());
+        if !struct_data
+            .fields
+            .iter()
+            .any(|(_, field_data)| field_data.visibility == RawVisibility::This)
+        {
+            return;
+        }
+
+        let src = self.strukt.source(self.db.upcast());
+        let field_syntax: Vec<SyntaxNode> = match src.value.kind() {
+            ast::StructKind::Record(r) => r.fields().map(|fd| fd.syntax().clone()).collect(),
+            ast::StructKind::Tuple(t) => t.fields().map(|fd| fd.syntax().clone()).collect(),
+            ast::StructKind::Unit => Vec::new(),
+        };
+
+        let used_fields = self.used_fields();
+        struct_data
+            .fields
+            .iter()
+            .zip(field_syntax)
+            .filter(|((id, field_data), _)| {
+                field_data.visibility == RawVisibility::This && !used_fields.contains(id)
+            })
+            .for_each(|((_, field_data), syntax)| {
+                sink.push(UnusedField {
+                    file: self.file_id,
+                    field: SyntaxNodePtr::new(&syntax),
+                    struct_name: struct_data.name.clone(),
+                    field_name: field_data.name.clone(),
+                    fix: SuggestRemoveField {
+                        range: InFile::new(self.file_id, removal_range(&syntax)),
+                    },
+                });
+            });
+    }
+
+    /// Returns the fields of the struct that are read or written in any
+    /// function body of the module that defines the struct or one of its child
+    /// modules.
+    fn used_fields(&self) -> FxHashSet<LocalFieldId> {
+        let struct_data = self.strukt.data(self.db.upcast());
+        let all_fields = || struct_data.fields.iter().map(|(id, _)| id);
+
+        let mut used = FxHashSet::default();
+        for function in self.strukt.module(self.db).all_functions(self.db) {
+            let body = function.body(self.db);
+            let infer = function.infer(self.db);
+            for (expr_id, expr) in body.exprs() {
+                match expr {
+                    Expr::Field { expr, name } if infer[*expr].as_struct() == Some(self.strukt) => {
+                        used.extend(struct_data.find_field(name));
+                    }
+                    Expr::RecordLit { fields, spread, .. }
+                        if infer[expr_id].as_struct() == Some(self.strukt) =>
+                    {
+                        if spread.is_some() {
+                            used.extend(all_fields());
+                        } else {
+                            used.extend(
+                                fields
+                                    .iter()
+                                    .filter_map(|field| struct_data.find_field(&field.name)),
+                            );
+                        }
+                    }
+                    // Tuple structs are constructed by calling either the struct or `Self`
+                    Expr::Call { callee, .. }
+                        if infer[*callee].as_callable_def()
+                            == Some(CallableDef::Struct(self.strukt))
+                            || infer[*callee].as_struct() == Some(self.strukt) =>
+                    {
+                        used.extend(all_fields());
+                    }
+                    _ => {}
+                }
+            }
+        }
+        used
+    }
+}
+
+/// Returns true if `ty` is `never` or stores a `never` value inline.
+fn contains_never(db: &dyn HirDatabase, ty: &Ty) -> bool {
+    match ty.interned() {
+        TyKind::Never => true,
+        TyKind::Tuple(_, substitution) => substitution.iter().any(|ty| contains_never(db, ty)),
+        TyKind::TypeAlias(alias) => contains_never(db, &alias.target_type(db)),
+        _ => false,
+    }
+}
+
+/// Returns the range of the specified field declaration, extended with the
+/// comma that separates it from the next field, if any.
+fn removal_range(field: &SyntaxNode) -> TextRange {
+    let range = field.text_range();
+    field
+        .siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .find(|element| !element.kind().is_trivia())
+        .filter(|element| element.kind() == SyntaxKind::COMMA)
+        .map_or(range, |comma| range.cover(comma.text_range()))
 }
@@ -36,7 +36,110 @@ fn test_private_leak_struct_fields() {
     }
     "#),
     @r###"
+    12..17: field `0` of struct `Foo` is never used
+    35..40: field `0` of struct `Bar` is never used
+    89..97: field `foo` of struct `Baz` is never used
     180..183: can't leak private type
     394..397: can't leak private type
     "###);
 }
+
+#[test]
+fn test_unused_struct_fields() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    struct Foo {
+        read: i32,
+        unused: i32,
+        pub public: i32,
+    }
+
+    // valid, all fields are written when constructed
+    struct Bar(i32, i32);
+
+    // valid, all fields are written when constructed
+    struct Baz {
+        a: i32,
+        b: i32,
+    }
+
+    // invalid, the field is never constructed or accessed
+    struct FooBar(i32);
+
+    fn foo(foo: Foo) -> i32 {
+        let bar = Bar(1, 2);
+        let baz = Baz { a: 1, b: 2 };
+        foo.read
+    }
+    "#),
+    @r###"
+    32..43: field `unused` of struct `Foo` is never used
+    301..304: field `0` of struct `FooBar` is never used
+    "###);
+}
+
+#[test]
+fn test_unused_struct_field_fix() {
+    use mun_hir_input::{SourceDatabase, WithFixture};
+
+    use crate::{diagnostics::UnusedField, mock::MockDatabase, DiagnosticSink, Package};
+
+    let text = r#"
+    struct Foo { a: i32, b: i32 }
+    struct Bar(i32, i32);
+
+    fn foo(foo: Foo, bar: Bar) -> i32 {
+        foo.b + bar.0
+    }
+    "#;
+    let (db, file_id) = MockDatabase::with_single_file(text);
+
+    let source = db.file_text(file_id);
+    let mut removals = Vec::new();
+    let mut sink = DiagnosticSink::new(|_| {}).on::<UnusedField, _>(|diag| {
+        assert_eq!(diag.fix.range.file_id, file_id);
+        removals.push(source[diag.fix.range.value].to_owned());
+    });
+    for module in Package::all(&db).iter().flat_map(|pkg| pkg.modules(&db)) {
+        module.diagnostics(&db, &mut sink);
+    }
+    drop(sink);
+
+    assert_eq!(removals, ["a: i32,", "i32"]);
+}
+
+#[test]
+fn test_unsupported_struct_field_types() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    // invalid, contains itself by value
+    pub struct(value) Foo {
+        pub foo: Foo,
+    }
+
+    // invalid, contains itself by value through another value struct
+    pub struct(value) Bar(pub Baz);
+    pub struct(value) Baz(pub Bar);
+
+    // valid, garbage collected structs are stored by reference
+    pub struct Gc {
+        pub gc: Gc,
+    }
+
+    // valid, garbage collected structs are stored by reference
+    pub struct(value) ValueGc(pub GcValue);
+    pub struct GcValue(pub ValueGc);
+
+    // valid, arrays are stored by reference
+    pub struct(value) ValueArray(pub [ValueArray]);
+
+    // invalid, `never` cannot be stored
+    pub struct Never(pub never);
+    "#),
+    @r###"
+    74..77: value struct `Foo` has infinite size because it recursively contains itself
+    174..177: value struct `Bar` has infinite size because it recursively contains itself
+    206..209: value struct `Baz` has infinite size because it recursively contains itself
+    590..595: the type of a field cannot contain `never`
+    "###);
+}
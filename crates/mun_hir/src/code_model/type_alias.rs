@@ -14,11 +14,21 @@ use crate::{
     DefDatabase, DiagnosticSink, HasVisibility, HirDatabase, Name, Ty, TyKind, Visibility,
 };
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TypeAlias {
     pub(crate) id: TypeAliasId,
 }
 
+/// An error that occurs when a chain of type aliases contains a cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CyclicAliasError {
+    /// The type alias whose chain contains the cycle
+    pub alias: TypeAlias,
+}
+
 impl From<TypeAliasId> for TypeAlias {
     fn from(id: TypeAliasId) -> Self {
         TypeAlias { id }
@@ -50,28 +60,40 @@ impl TypeAlias {
         db.lower_type_alias(self)
     }
 
+    /// Returns the type that this alias ultimately refers to, or
+    /// `TyKind::Unknown` if the chain of aliases is cyclic. See
+    /// [`TypeAlias::resolve_fully`].
     pub fn target_type(self, db: &dyn HirDatabase) -> Ty {
-        let data = self.data(db.upcast());
-        let mut ty = Ty::from_hir(
-            db,
-            &self.id.resolver(db.upcast()),
-            data.type_ref_map(),
-            data.type_ref_id,
-        )
-        .0;
-
-        while let &TyKind::TypeAlias(alias) = ty.interned() {
+        self.resolve_fully(db)
+            .unwrap_or_else(|_| TyKind::Unknown.intern())
+    }
+
+    /// Follows the chain of type aliases starting at this alias until a type
+    /// is reached that is not an alias, and returns that type. Returns an
+    /// error if the chain contains a cycle.
+    pub fn resolve_fully(self, db: &dyn HirDatabase) -> Result<Ty, CyclicAliasError> {
+        let mut visited = Vec::new();
+        let mut alias = self;
+        loop {
+            if visited.contains(&alias) {
+                return Err(CyclicAliasError { alias: self });
+            }
+            visited.push(alias);
+
             let data = alias.data(db.upcast());
-            ty = Ty::from_hir(
+            let ty = Ty::from_hir(
                 db,
                 &alias.id.resolver(db.upcast()),
                 data.type_ref_map(),
                 data.type_ref_id,
             )
             .0;
-        }
 
-        ty
+            match ty.interned() {
+                &TyKind::TypeAlias(next) => alias = next,
+                _ => return Ok(ty),
+            }
+        }
     }
 
     pub fn diagnostics(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink<'_>) {
@@ -0,0 +1,26 @@
+use mun_hir_input::WithFixture;
+
+use crate::{mock::MockDatabase, utils::tests::find_type_alias, CyclicAliasError, HirDisplay};
+
+#[test]
+fn type_alias_resolve_fully() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    type A = B;
+    type B = C;
+    type C = i32;
+
+    type X = Y;
+    type Y = X;
+    "#,
+    );
+
+    let a = find_type_alias(&db, "A")
+        .resolve_fully(&db)
+        .expect("alias chain should not be cyclic");
+    assert_eq!(a.display(&db).to_string(), "i32");
+
+    let x = find_type_alias(&db, "X");
+    assert_eq!(x.resolve_fully(&db), Err(CyclicAliasError { alias: x }));
+}
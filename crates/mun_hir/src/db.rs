@@ -123,6 +123,19 @@ pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> {
 
     #[salsa::invoke(InherentImpls::inherent_impls_in_package_query)]
     fn inherent_impls_in_package(&self, package: PackageId) -> Arc<InherentImpls>;
+
+    /// Returns all functions that are called from the body of a function.
+    #[salsa::invoke(crate::call_graph::callees_query)]
+    fn callees(&self, def: FunctionId) -> Arc<[FunctionId]>;
+
+    /// Returns true if the body of a function contains a call to itself.
+    #[salsa::invoke(crate::call_graph::is_directly_recursive_query)]
+    fn is_directly_recursive(&self, def: FunctionId) -> bool;
+
+    /// Returns true if a function calls itself through one or more other
+    /// functions.
+    #[salsa::invoke(crate::call_graph::is_mutually_recursive_query)]
+    fn is_mutually_recursive(&self, def: FunctionId) -> bool;
 }
 
 fn parse_query(db: &dyn AstDatabase, file_id: FileId) -> Parse<SourceFile> {
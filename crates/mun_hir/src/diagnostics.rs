@@ -23,9 +23,23 @@ pub trait Diagnostic: Any + Send + Sync + fmt::Debug + 'static {
     fn highlight_range(&self) -> TextRange {
         self.source().value.range()
     }
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
     fn as_any(&self) -> &(dyn Any + Send + 'static);
 }
 
+/// The severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// The diagnostic prevents the code from being compiled
+    Error,
+
+    /// The diagnostic indicates a potential problem but does not prevent the
+    /// code from being compiled
+    Warning,
+}
+
 pub trait AstDiagnostic {
     type AST;
     fn ast(&self, db: &dyn HirDatabase) -> Self::AST;
@@ -202,6 +216,94 @@ impl Diagnostic for ExportedPrivate {
     }
 }
 
+/// An error that is emitted for a field of a value struct that contains the
+/// struct itself by value, which would give the struct an infinite size.
+#[derive(Debug)]
+pub struct RecursiveValueStruct {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+    pub struct_name: Name,
+}
+
+impl Diagnostic for RecursiveValueStruct {
+    fn message(&self) -> String {
+        format!(
+            "value struct `{}` has infinite size because it recursively contains itself",
+            self.struct_name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a field whose type contains the `never` type,
+/// which has no values and can therefore not be stored.
+#[derive(Debug)]
+pub struct NeverFieldType {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for NeverFieldType {
+    fn message(&self) -> String {
+        "the type of a field cannot contain `never`".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A warning that is emitted for a private field of a struct that is never read
+/// or written.
+#[derive(Debug)]
+pub struct UnusedField {
+    pub file: FileId,
+    pub field: SyntaxNodePtr,
+    pub struct_name: Name,
+    pub field_name: Name,
+    pub fix: SuggestRemoveField,
+}
+
+impl Diagnostic for UnusedField {
+    fn message(&self) -> String {
+        format!(
+            "field `{}` of struct `{}` is never used",
+            self.field_name, self.struct_name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.field.clone())
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A suggestion to remove a field from the definition of a struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuggestRemoveField {
+    /// The range of source text to remove. This includes the separating comma
+    /// that follows the field, if any.
+    pub range: InFile<TextRange>,
+}
+
 #[derive(Debug)]
 pub struct ParameterCountMismatch {
     pub file: FileId,
@@ -184,40 +184,16 @@ impl<'a> TypeAliasValidator<'a> {
 
     /// Validates the provided `TypeAlias` is not cyclic.
     pub fn validate_acyclic(&self, sink: &mut DiagnosticSink<'_>) {
-        let mut next_alias = Some(self.type_alias);
-
-        let mut ids = Vec::new();
-        while let Some(alias) = next_alias.take() {
-            let type_ref = alias.type_ref(self.db);
-
-            // Detect cyclic type
-            if ids.contains(&alias.id) {
-                let src = self.type_alias.source(self.db.upcast());
-                sink.push(CyclicType {
-                    file: src.file_id,
-                    type_ref: self
-                        .type_alias
-                        .data(self.db.upcast())
-                        .type_ref_source_map()
-                        .type_ref_syntax(type_ref)
-                        .unwrap(),
-                });
-                break;
-            }
-
-            ids.push(alias.id);
-
-            let ty = Ty::from_hir(
-                self.db,
-                &alias.id.resolver(self.db.upcast()),
-                alias.data(self.db.upcast()).type_ref_map(),
-                type_ref,
-            )
-            .0;
-
-            if let TyKind::TypeAlias(alias) = ty.into_inner() {
-                next_alias = Some(alias);
-            }
+        if self.type_alias.resolve_fully(self.db).is_err() {
+            let src = self.type_alias.source(self.db.upcast());
+            let data = self.type_alias.data(self.db.upcast());
+            sink.push(CyclicType {
+                file: src.file_id,
+                type_ref: data
+                    .type_ref_source_map()
+                    .type_ref_syntax(data.type_ref_id)
+                    .unwrap(),
+            });
         }
     }
 }
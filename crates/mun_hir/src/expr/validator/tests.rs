@@ -127,8 +127,10 @@ fn test_private_leak_function_args() {
     }
     "#,
     ), @r###"
+    11..16: field `0` of struct `Foo` is never used
     34..37: can't leak private type
     113..116: can't leak private type
+    157..162: field `0` of struct `FooBar` is never used
     "###);
 }
 
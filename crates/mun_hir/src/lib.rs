@@ -11,8 +11,8 @@ pub use mun_hir_input::ModuleId;
 pub use salsa;
 
 pub use self::code_model::{
-    Field, Function, FunctionData, HasSource, Module, ModuleDef, Package, PrimitiveType, Struct,
-    StructMemoryKind, TypeAlias,
+    CyclicAliasError, ExportedSymbol, Field, Function, FunctionData, HasSource, Module, ModuleDef,
+    Package, PrimitiveType, Struct, StructMemoryKind, TypeAlias,
 };
 pub use crate::{
     db::{
@@ -42,6 +42,7 @@ use crate::{name::AsName, source_id::AstIdMap};
 
 #[macro_use]
 mod macros;
+mod call_graph;
 mod code_model;
 mod db;
 pub mod diagnostics;
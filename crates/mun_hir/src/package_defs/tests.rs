@@ -2,8 +2,9 @@ use mun_hir_input::WithFixture;
 use rustc_hash::FxHashSet;
 
 use crate::{
-    db::DefDatabase, ids::ItemDefinitionId, mock::MockDatabase, package_defs::PackageDefs,
-    DiagnosticSink, Function, HirDatabase, Module, Package, Struct, TypeAlias,
+    db::DefDatabase, diagnostics::Severity, ids::ItemDefinitionId, mock::MockDatabase,
+    package_defs::PackageDefs, DiagnosticSink, Function, HirDatabase, Module, Package, Struct,
+    TypeAlias,
 };
 
 #[test]
@@ -210,6 +211,7 @@ fn use_() {
     |   +-- struct Bar
     |   '-- use struct package::foo::Foo
     '-- mod foo
+        +-- WARNING: 52..60: field `baz` of struct `Foo` is never used
         +-- fn foo_from_bar
         +-- struct Foo
         +-- use struct package::Baz
@@ -245,8 +247,12 @@ fn tree_for_module(
 
     // Add module level diagnostics
     let mut diag_sink = DiagnosticSink::new(|diag| {
+        let severity = match diag.severity() {
+            Severity::Error => "ERROR",
+            Severity::Warning => "WARNING",
+        };
         node.push(format!(
-            "ERROR: {:?}: {}",
+            "{severity}: {:?}: {}",
             diag.highlight_range(),
             diag.message()
         ));
@@ -294,6 +294,9 @@ fn private_access() {
     76..93: access of private type
     128..145: access of private type
     177..197: access of private type
+    116..119: field `0` of struct `PubBar` is never used
+    251..254: field `0` of struct `PubSupBar` is never used
+    432..435: field `0` of struct `PubPackageBar` is never used
     10..812 '{     ...o(); }': ()
     20..21 'a': Foo
     24..44 'packag...Foo {}': Foo
@@ -1224,6 +1227,10 @@ fn struct_decl() {
     }
     "#),
     @r###"
+    33..39: field `f` of struct `Bar` is never used
+    45..51: field `i` of struct `Bar` is never used
+    73..76: field `0` of struct `Baz` is never used
+    78..81: field `1` of struct `Baz` is never used
     96..153 '{     ...Baz; }': ()
     106..109 'foo': Foo
     124..127 'bar': Bar
@@ -13,7 +13,10 @@ pub(crate) fn make_mut_slice<T: Clone>(a: &mut Arc<[T]>) -> &mut [T] {
 pub mod tests {
     use mun_hir_input::WithFixture;
 
-    use crate::{diagnostics::DiagnosticSink, mock::MockDatabase, AstDatabase, Package};
+    use crate::{
+        diagnostics::DiagnosticSink, mock::MockDatabase, AstDatabase, Function, Module, ModuleDef,
+        Package, Struct, TypeAlias,
+    };
 
     pub fn diagnostics(content: &str) -> String {
         let (db, _file_id) = MockDatabase::with_single_file(content);
@@ -40,4 +43,55 @@ pub mod tests {
         drop(diag_sink);
         diags.join("\n")
     }
+
+    /// Returns the root module of the first package in the database.
+    pub fn root_module(db: &MockDatabase) -> Module {
+        Package::all(db)
+            .into_iter()
+            .next()
+            .expect("database should contain a package")
+            .root_module(db)
+    }
+
+    /// Returns the struct called `name` declared in the root module.
+    pub fn find_struct(db: &MockDatabase, name: &str) -> Struct {
+        match find_declaration(db, name) {
+            ModuleDef::Struct(s) => s,
+            decl => panic!("`{name}` is not a struct: {decl:?}"),
+        }
+    }
+
+    /// Returns the type alias called `name` declared in the root module.
+    pub fn find_type_alias(db: &MockDatabase, name: &str) -> TypeAlias {
+        match find_declaration(db, name) {
+            ModuleDef::TypeAlias(alias) => alias,
+            decl => panic!("`{name}` is not a type alias: {decl:?}"),
+        }
+    }
+
+    /// Returns the function called `name` declared in the root module or in
+    /// any of its submodules and impls.
+    pub fn find_function(db: &MockDatabase, name: &str) -> Function {
+        root_module(db)
+            .all_functions(db)
+            .into_iter()
+            .find(|f| f.name(db).as_str() == Some(name))
+            .unwrap_or_else(|| panic!("no function named `{name}`"))
+    }
+
+    fn find_declaration(db: &MockDatabase, name: &str) -> ModuleDef {
+        root_module(db)
+            .declarations(db)
+            .into_iter()
+            .find(|decl| {
+                let decl_name = match decl {
+                    ModuleDef::Function(f) => f.name(db),
+                    ModuleDef::Struct(s) => s.name(db),
+                    ModuleDef::TypeAlias(alias) => alias.name(db),
+                    ModuleDef::Module(_) | ModuleDef::PrimitiveType(_) => return false,
+                };
+                decl_name.as_str() == Some(name)
+            })
+            .unwrap_or_else(|| panic!("no declaration named `{name}`"))
+    }
 }
@@ -1,7 +1,7 @@
 use std::cell::RefCell;
 
 use mun_diagnostics::DiagnosticForWith;
-use mun_hir::{AstDatabase, InFile, Module};
+use mun_hir::{diagnostics::Severity, AstDatabase, InFile, Module};
 use mun_hir_input::{FileId, ModuleId, PackageId, SourceDatabase};
 use mun_syntax::{Location, TextRange};
 
@@ -18,8 +18,8 @@ pub struct Diagnostic {
     pub message: String,
     pub range: TextRange,
     pub additional_annotations: Vec<SourceAnnotation>,
+    pub severity: Severity,
     // pub fix: Option<SourceChange>,
-    // pub severity: Severity,
 }
 
 /// Converts a location to a a range for use in diagnostics
@@ -40,11 +40,13 @@ pub(crate) fn diagnostics(db: &AnalysisDatabase, file_id: FileId) -> Vec<Diagnos
         message: format!("parse error: {err}"),
         range: location_to_range(err.location()),
         additional_annotations: vec![],
+        severity: Severity::Error,
     }));
 
     // Add all HIR diagnostics
     let result = RefCell::new(result);
     let mut sink = mun_hir::diagnostics::DiagnosticSink::new(|d| {
+        let severity = d.severity();
         result.borrow_mut().push(d.with_diagnostic(db, |d| {
             Diagnostic {
                 message: format!("{}\n{}", d.title(), d.footer().join("\n"))
@@ -59,6 +61,7 @@ pub(crate) fn diagnostics(db: &AnalysisDatabase, file_id: FileId) -> Vec<Diagnos
                         range: annotation.range,
                     })
                     .collect(),
+                severity,
             }
         }));
     });
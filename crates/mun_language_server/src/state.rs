@@ -6,6 +6,7 @@ use lsp_types::{
     notification::{Notification, PublishDiagnostics},
     PublishDiagnosticsParams,
 };
+use mun_hir::diagnostics::Severity;
 use mun_hir_input::{FileId, PackageId, PackageSet};
 use mun_paths::AbsPathBuf;
 use mun_vfs::VirtualFileSystem;
@@ -264,7 +265,10 @@ fn handle_diagnostics(state: LanguageServerSnapshot, sender: Sender<Task>) -> an
                 for d in diagnostics {
                     lsp_diagnostics.push(lsp_types::Diagnostic {
                         range: to_lsp::range(d.range, &line_index),
-                        severity: Some(lsp_types::DiagnosticSeverity::ERROR),
+                        severity: Some(match d.severity {
+                            Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
+                            Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+                        }),
                         code: None,
                         code_description: None,
                         source: Some("mun".to_string()),
@@ -17,6 +17,37 @@ macro_rules! insert_cast_fn {
     }
 }
 
+/// Inserts casts between all integer types and the floating-point types in both
+/// directions. Casts use the semantics of Rust's `as` operator: integers are
+/// rounded to the nearest floating-point value, while floating-point values are
+/// truncated and clamped to the range of the integer type, with `NaN` becoming
+/// zero.
+macro_rules! insert_int_float_cast_fns {
+    { $table:ident, $($Int:ty),+ } => {
+        $(
+            insert_as_cast_fn!($table, $Int, f32);
+            insert_as_cast_fn!($table, $Int, f64);
+            insert_as_cast_fn!($table, f32, $Int);
+            insert_as_cast_fn!($table, f64, $Int);
+        )+
+    }
+}
+
+macro_rules! insert_as_cast_fn {
+    { $table:ident, $A:ty, $B:ty } => {{
+        #[allow(clippy::cast_lossless)]
+        fn cast(src: NonNull<u8>, dest: NonNull<u8>) {
+            let value = unsafe { *src.cast::<$A>().as_ref() };
+            unsafe { *dest.cast::<$B>().as_mut() = value as $B };
+        }
+
+        $table.insert(
+            (<$A>::type_info().clone(), <$B>::type_info().clone()),
+            cast as CastFn,
+        )
+    }}
+}
+
 lazy_static! {
     static ref CAST_FN_TABLE: HashMap<(Type, Type), CastFn> = {
         let mut table = HashMap::new();
@@ -51,6 +82,7 @@ lazy_static! {
         insert_cast_fn!(table, u32, u128);
         insert_cast_fn!(table, u64, i128);
         insert_cast_fn!(table, u64, u128);
+        insert_int_float_cast_fns!(table, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
         table
     };
 }
@@ -63,8 +95,31 @@ where
     unsafe { *dest.cast::<B>().as_mut() = value.into() };
 }
 
+/// Returns the unsigned integer type that has the same width as a pointer on
+/// the current platform.
+fn pointer_sized_uint() -> &'static Type {
+    #[cfg(target_pointer_width = "64")]
+    return u64::type_info();
+    #[cfg(target_pointer_width = "32")]
+    return u32::type_info();
+}
+
+/// Returns true if `old_id` and `new_id` are a pointer type and the unsigned
+/// integer type of the same width, in either order.
+fn is_pointer_uint_cast(old_id: &Type, new_id: &Type) -> bool {
+    let uint = pointer_sized_uint();
+    (old_id.is_pointer() && new_id == uint) || (old_id == uint && new_id.is_pointer())
+}
+
 pub fn try_cast_from_to(old_id: Type, new_id: Type, src: NonNull<u8>, dest: NonNull<u8>) -> bool {
-    if let Some(cast_fn) = CAST_FN_TABLE.get(&(old_id, new_id)) {
+    if is_pointer_uint_cast(&old_id, &new_id) {
+        // Pointers are reinterpreted as integers of the same width, preserving their
+        // bit pattern.
+        debug_assert_eq!(old_id.value_layout().size(), std::mem::size_of::<usize>());
+        debug_assert_eq!(new_id.value_layout().size(), std::mem::size_of::<usize>());
+        unsafe { *dest.cast::<usize>().as_mut() = *src.cast::<usize>().as_ref() };
+        true
+    } else if let Some(cast_fn) = CAST_FN_TABLE.get(&(old_id, new_id)) {
         cast_fn(src, dest);
         true
     } else {
@@ -247,4 +302,80 @@ mod tests {
     fn cast_u64_to_u128() {
         assert_cast(5u64, 0u128);
     }
+
+    fn cast_as<A: HasStaticType, B: Default + HasStaticType>(a: A) -> B {
+        let mut b = B::default();
+        assert!(try_cast_from_to(
+            A::type_info().clone(),
+            B::type_info().clone(),
+            unsafe { NonNull::new_unchecked(&a as *const _ as *mut _) },
+            unsafe { NonNull::new_unchecked(&mut b as *mut _) }.cast::<u8>(),
+        ));
+        b
+    }
+
+    macro_rules! assert_int_float_casts {
+        ($($Int:ty),+) => {
+            $(
+                for value in [<$Int>::MIN, 0, 1, <$Int>::MAX] {
+                    assert_eq!(cast_as::<$Int, f32>(value), value as f32);
+                    assert_eq!(cast_as::<$Int, f64>(value), value as f64);
+                }
+                for value in [f32::NEG_INFINITY, -1e30, -1.5, 0.0, 1.5, 1e30, f32::INFINITY, f32::NAN] {
+                    assert_eq!(cast_as::<f32, $Int>(value), value as $Int);
+                }
+                for value in [f64::NEG_INFINITY, -1e300, -1.5, 0.0, 1.5, 1e300, f64::INFINITY, f64::NAN] {
+                    assert_eq!(cast_as::<f64, $Int>(value), value as $Int);
+                }
+            )+
+        };
+    }
+
+    #[test]
+    #[allow(clippy::cast_lossless)]
+    fn cast_int_float() {
+        assert_int_float_casts!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+    }
+
+    #[test]
+    fn cast_float_to_int_clamps() {
+        assert_eq!(cast_as::<f64, i32>(1e10), i32::MAX);
+        assert_eq!(cast_as::<f64, i32>(-1e10), i32::MIN);
+        assert_eq!(cast_as::<f32, u8>(-1.0), 0);
+        assert_eq!(cast_as::<f32, u8>(2.9), 2);
+        assert_eq!(cast_as::<f64, i64>(f64::NAN), 0);
+    }
+
+    #[test]
+    fn cast_pointer_to_uint_and_back() {
+        #[cfg(target_pointer_width = "64")]
+        type Uint = u64;
+        #[cfg(target_pointer_width = "32")]
+        type Uint = u32;
+
+        let pointer = 0xdead_beef_usize as *const i64;
+        let uint: Uint = cast_as::<*const i64, Uint>(pointer);
+        assert_eq!(uint as usize, pointer as usize);
+
+        let mut result: *mut f64 = std::ptr::null_mut();
+        assert!(try_cast_from_to(
+            Uint::type_info().clone(),
+            <*mut f64>::type_info().clone(),
+            unsafe { NonNull::new_unchecked(&uint as *const _ as *mut _) },
+            unsafe { NonNull::new_unchecked(&mut result as *mut _) }.cast::<u8>(),
+        ));
+        assert_eq!(result as usize, pointer as usize);
+    }
+
+    #[test]
+    fn cast_pointer_to_other_int_fails() {
+        let pointer = 0xdead_beef_usize as *const i64;
+        let mut value = 0u16;
+        assert!(!try_cast_from_to(
+            <*const i64>::type_info().clone(),
+            u16::type_info().clone(),
+            unsafe { NonNull::new_unchecked(&pointer as *const _ as *mut _) },
+            unsafe { NonNull::new_unchecked(&mut value as *mut _) }.cast::<u8>(),
+        ));
+    }
 }
@@ -32,6 +32,68 @@ pub enum FieldDiff {
     },
 }
 
+impl FieldDiff {
+    /// Returns a human-readable sentence describing the change, given the
+    /// `old_struct` and `new_struct` types that this diff was computed for.
+    pub fn explain(&self, old_struct: &Type, new_struct: &Type) -> String {
+        fn field_name(ty: &Type, index: usize) -> String {
+            ty.as_struct()
+                .and_then(|s| s.fields().get(index))
+                .map_or_else(|| format!("#{index}"), |f| f.name().to_owned())
+        }
+
+        match self {
+            FieldDiff::Insert { index, new_type } => format!(
+                "field '{}' of type {} was added to struct '{}'",
+                field_name(new_struct, *index),
+                new_type.name(),
+                new_struct.name()
+            ),
+            FieldDiff::Edit {
+                old_type,
+                new_type,
+                old_index,
+                new_index,
+                kind,
+            } => {
+                let old_name = field_name(old_struct, old_index.unwrap_or(*new_index));
+                let explanation = match kind {
+                    FieldEditKind::ChangedTyped => format!(
+                        "field '{old_name}' changed type from {} to {}",
+                        old_type.name(),
+                        new_type.name()
+                    ),
+                    FieldEditKind::RenamedField => format!(
+                        "field '{old_name}' was renamed to '{}'",
+                        field_name(new_struct, *new_index)
+                    ),
+                };
+
+                match old_index {
+                    Some(old_index) => {
+                        format!("{explanation} and moved from position {old_index} to {new_index}")
+                    }
+                    None => explanation,
+                }
+            }
+            FieldDiff::Move {
+                old_index,
+                new_index,
+                ..
+            } => format!(
+                "field '{}' was moved from position {old_index} to {new_index} in struct '{}'",
+                field_name(old_struct, *old_index),
+                new_struct.name()
+            ),
+            FieldDiff::Delete { index } => format!(
+                "field '{}' was removed from struct '{}'",
+                field_name(old_struct, *index),
+                old_struct.name()
+            ),
+        }
+    }
+}
+
 /// The difference between an old and new ordered set of structs.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum StructDiff {
@@ -56,6 +118,20 @@ pub enum StructDiff {
     Delete { index: usize, ty: Type },
 }
 
+impl StructDiff {
+    /// Returns true if `ty` is the old or new type of this diff.
+    pub fn affects_type(&self, ty: &Type) -> bool {
+        match self {
+            StructDiff::Insert { ty: diff_ty, .. } | StructDiff::Delete { ty: diff_ty, .. } => {
+                diff_ty == ty
+            }
+            StructDiff::Edit { old_ty, new_ty, .. } | StructDiff::Move { old_ty, new_ty, .. } => {
+                old_ty == ty || new_ty == ty
+            }
+        }
+    }
+}
+
 impl Ord for StructDiff {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         fn get_index(diff: &StructDiff) -> usize {
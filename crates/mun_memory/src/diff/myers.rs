@@ -9,7 +9,31 @@ pub enum Diff<T> {
 
 /// Computes the difference in ordering and uniqueness of values between and old
 /// and new set.
+///
+/// Only the range in between the common prefix and suffix of both sets is
+/// diffed, which is considerably faster than [`compute_full_diff`] when few
+/// values changed.
 pub fn compute_diff<T: Clone + Eq>(old: &[T], new: &[T]) -> Vec<Diff<T>> {
+    // Types are mostly unchanged between reloads, so only diff the range in
+    // between the common prefix and suffix.
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let (old, new) = (&old[prefix..], &new[prefix..]);
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old, new) = (&old[..old.len() - suffix], &new[..new.len() - suffix]);
+
+    let mut diff = Vec::new();
+    diff_impl(&mut diff, old, new, prefix, prefix);
+    diff
+}
+
+/// Computes the difference in ordering and uniqueness of values between and old
+/// and new set, by diffing both sets in their entirety.
+pub fn compute_full_diff<T: Clone + Eq>(old: &[T], new: &[T]) -> Vec<Diff<T>> {
     let mut diff = Vec::new();
     diff_impl(&mut diff, old, new, 0, 0);
     diff
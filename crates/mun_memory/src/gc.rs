@@ -3,18 +3,90 @@ mod mark_sweep;
 mod ptr;
 mod root_ptr;
 
-use std::{marker::PhantomData, ptr::NonNull};
+use std::{
+    collections::BTreeMap,
+    marker::PhantomData,
+    ops::{Add, AddAssign, Sub, SubAssign},
+    ptr::NonNull,
+    time::Duration,
+};
 
-pub use mark_sweep::MarkSweep;
+pub use mark_sweep::{MarkSweep, MemoryLayoutError};
 pub use ptr::{GcPtr, HasIndirectionPtr, RawGcPtr};
 pub use root_ptr::GcRootPtr;
 
 use crate::r#type::Type;
 
 /// Contains stats about the current state of a GC implementation
-#[derive(Debug, Clone, Default)]
+///
+/// Stats can be added together to combine them, or subtracted to compute the
+/// difference between two snapshots. Subtraction saturates at zero.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Stats {
     pub allocated_memory: usize,
+    /// The number of times the heap was compacted
+    pub compact_count: usize,
+    /// The total number of bytes moved by compacting the heap
+    pub bytes_moved: usize,
+}
+
+impl Add for Stats {
+    type Output = Stats;
+
+    fn add(mut self, rhs: Stats) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for Stats {
+    fn add_assign(&mut self, rhs: Stats) {
+        self.allocated_memory += rhs.allocated_memory;
+        self.compact_count += rhs.compact_count;
+        self.bytes_moved += rhs.bytes_moved;
+    }
+}
+
+impl Sub for Stats {
+    type Output = Stats;
+
+    fn sub(mut self, rhs: Stats) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl SubAssign for Stats {
+    fn sub_assign(&mut self, rhs: Stats) {
+        self.allocated_memory = self.allocated_memory.saturating_sub(rhs.allocated_memory);
+        self.compact_count = self.compact_count.saturating_sub(rhs.compact_count);
+        self.bytes_moved = self.bytes_moved.saturating_sub(rhs.bytes_moved);
+    }
+}
+
+/// Contains stats about a single garbage collection
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollectionStats {
+    /// The number of objects that were found to be reachable
+    pub objects_visited: usize,
+    /// The number of objects that were reclaimed
+    pub objects_freed: usize,
+    /// The number of bytes that were reclaimed
+    pub bytes_freed: usize,
+    /// The time it took to perform the collection
+    pub duration: Duration,
+}
+
+/// An error that might occur when allocating memory
+#[derive(Debug, thiserror::Error)]
+pub enum AllocError {
+    /// The allocator was unable to provide the requested memory
+    #[error("out of memory")]
+    OutOfMemory,
+
+    /// The memory layout of the requested allocation is invalid
+    #[error(transparent)]
+    Layout(#[from] MemoryLayoutError),
 }
 
 /// A trait used to trace an object type.
@@ -50,11 +122,56 @@ pub trait Array: Sized {
 pub trait GcRuntime: Send + Sync {
     type Array: Array;
 
-    /// Allocates an object of the given type returning a [`GcPtr`]
-    fn alloc(&self, ty: &Type) -> GcPtr;
+    /// Allocates an object of the given type returning a [`GcPtr`]. Panics if
+    /// the memory could not be allocated.
+    fn alloc(&self, ty: &Type) -> GcPtr {
+        self.try_alloc(ty)
+            .expect("failed to allocate memory for new object")
+    }
 
     /// Allocates an array of the given type. `ty` must be an array type.
-    fn alloc_array(&self, ty: &Type, n: usize) -> Self::Array;
+    /// Panics if the memory could not be allocated.
+    fn alloc_array(&self, ty: &Type, n: usize) -> Self::Array {
+        self.try_alloc_array(ty, n)
+            .expect("failed to allocate memory for array")
+    }
+
+    /// Tries to allocate an object of the given type returning a [`GcPtr`],
+    /// or an error if the memory could not be allocated.
+    fn try_alloc(&self, ty: &Type) -> Result<GcPtr, AllocError>;
+
+    /// Tries to allocate an array of the given type, or returns an error if
+    /// the memory could not be allocated. `ty` must be an array type.
+    fn try_alloc_array(&self, ty: &Type, n: usize) -> Result<Self::Array, AllocError>;
+
+    /// Allocates `count` objects of the given type. Panics if the memory could
+    /// not be allocated.
+    fn batch_alloc(&self, ty: &Type, count: usize) -> Vec<GcPtr> {
+        self.try_batch_alloc(ty, count)
+            .expect("failed to allocate memory for new objects")
+    }
+
+    /// Tries to allocate `count` objects of the given type, or returns an
+    /// error if the memory could not be allocated. If an error occurs, none of
+    /// the objects are allocated.
+    ///
+    /// The default implementation allocates the objects one by one.
+    fn try_batch_alloc(&self, ty: &Type, count: usize) -> Result<Vec<GcPtr>, AllocError> {
+        (0..count).map(|_| self.try_alloc(ty)).collect()
+    }
+
+    /// Allocates a shallow copy of the object referenced by `src`. Objects
+    /// referenced by `src` are not copied; the copy references the same
+    /// objects. Panics if the memory could not be allocated.
+    fn copy(&self, src: GcPtr) -> GcPtr {
+        self.try_copy(src)
+            .expect("failed to allocate memory for copied object")
+    }
+
+    /// Tries to allocate a shallow copy of the object referenced by `src`, or
+    /// returns an error if the memory could not be allocated. See
+    /// [`GcRuntime::copy`].
+    fn try_copy(&self, src: GcPtr) -> Result<GcPtr, AllocError>;
 
     /// Returns the type of the specified `obj`.
     fn ptr_type(&self, obj: GcPtr) -> Type;
@@ -75,10 +192,187 @@ pub trait GcRuntime: Send + Sync {
     /// `root` was called before the object can be collected.
     fn unroot(&self, obj: GcPtr);
 
+    /// Returns the total number of roots of all objects. An object that is
+    /// rooted multiple times contributes each of its roots.
+    fn root_count(&self) -> usize;
+
+    /// Returns all objects that are currently rooted.
+    fn rooted_objects(&self) -> Vec<GcPtr>;
+
+    /// Panics if any object is still rooted, listing the types of the leaked
+    /// objects. This is useful in test teardown to detect objects that were
+    /// never unrooted.
+    fn assert_no_roots(&self) {
+        let rooted = self.rooted_objects();
+        if rooted.is_empty() {
+            return;
+        }
+
+        let mut leaked_types = BTreeMap::<String, usize>::new();
+        for obj in rooted.iter() {
+            *leaked_types
+                .entry(self.ptr_type(*obj).name().to_owned())
+                .or_default() += 1;
+        }
+        let leaked_types = leaked_types
+            .into_iter()
+            .map(|(name, count)| format!("{count}x {name}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        panic!(
+            "{} object(s) are still rooted ({} roots in total): {leaked_types}",
+            rooted.len(),
+            self.root_count()
+        );
+    }
+
+    /// Copies the value of the object referenced by `obj` to `dst` and
+    /// immediately deallocates the object. This allows objects that never
+    /// escape a single function scope to live on the stack instead.
+    ///
+    /// # Safety
+    ///
+    /// `obj` must reference a non-array object that is not rooted and not
+    /// referenced by any other object. `dst` must be valid for writes of the
+    /// size of the object's value and suitably aligned for its type. After
+    /// this call `obj` is invalid and must not be used anymore.
+    unsafe fn move_to_stack(&self, obj: GcPtr, dst: *mut u8);
+
+    /// Returns stats about the current state of the runtime.
+    fn stats(&self) -> Stats;
+}
+
+/// An object-safe counterpart of [`GcRuntime`] that erases the array type,
+/// allowing different garbage collectors to be used through a
+/// `&dyn DynGcRuntime` or `Box<dyn DynGcRuntime>`.
+///
+/// Arrays are represented by their [`GcPtr`]. Every [`GcRuntime`]
+/// automatically implements this trait. Because the methods of both traits
+/// share their names, method calls on a concrete garbage collector are
+/// ambiguous if both traits are in scope.
+pub trait DynGcRuntime: Send + Sync {
+    /// Allocates an object of the given type returning a [`GcPtr`]. Panics if
+    /// the memory could not be allocated.
+    fn alloc(&self, ty: &Type) -> GcPtr {
+        self.try_alloc(ty)
+            .expect("failed to allocate memory for new object")
+    }
+
+    /// Allocates an array of the given type returning its [`GcPtr`]. `ty` must
+    /// be an array type. Panics if the memory could not be allocated.
+    fn alloc_array(&self, ty: &Type, n: usize) -> GcPtr {
+        self.try_alloc_array(ty, n)
+            .expect("failed to allocate memory for array")
+    }
+
+    /// Tries to allocate an object of the given type returning a [`GcPtr`],
+    /// or an error if the memory could not be allocated.
+    fn try_alloc(&self, ty: &Type) -> Result<GcPtr, AllocError>;
+
+    /// Tries to allocate an array of the given type returning its [`GcPtr`],
+    /// or an error if the memory could not be allocated. `ty` must be an
+    /// array type.
+    fn try_alloc_array(&self, ty: &Type, n: usize) -> Result<GcPtr, AllocError>;
+
+    /// Allocates `count` objects of the given type. Panics if the memory could
+    /// not be allocated.
+    fn batch_alloc(&self, ty: &Type, count: usize) -> Vec<GcPtr> {
+        self.try_batch_alloc(ty, count)
+            .expect("failed to allocate memory for new objects")
+    }
+
+    /// Tries to allocate `count` objects of the given type. See
+    /// [`GcRuntime::try_batch_alloc`].
+    fn try_batch_alloc(&self, ty: &Type, count: usize) -> Result<Vec<GcPtr>, AllocError>;
+
+    /// Tries to allocate a shallow copy of the object referenced by `src`, or
+    /// returns an error if the memory could not be allocated. See
+    /// [`GcRuntime::copy`].
+    fn try_copy(&self, src: GcPtr) -> Result<GcPtr, AllocError>;
+
+    /// Returns the type of the specified `obj`.
+    fn ptr_type(&self, obj: GcPtr) -> Type;
+
+    /// Roots the specified `obj`. See [`GcRuntime::root`].
+    fn root(&self, obj: GcPtr);
+
+    /// Unroots the specified `obj`. See [`GcRuntime::unroot`].
+    fn unroot(&self, obj: GcPtr);
+
+    /// Returns the total number of roots of all objects. See
+    /// [`GcRuntime::root_count`].
+    fn root_count(&self) -> usize;
+
+    /// Returns all objects that are currently rooted.
+    fn rooted_objects(&self) -> Vec<GcPtr>;
+
+    /// Panics if any object is still rooted. See
+    /// [`GcRuntime::assert_no_roots`].
+    fn assert_no_roots(&self);
+
+    /// Copies the value of `obj` to `dst` and deallocates it. See
+    /// [`GcRuntime::move_to_stack`].
+    ///
+    /// # Safety
+    ///
+    /// See [`GcRuntime::move_to_stack`].
+    unsafe fn move_to_stack(&self, obj: GcPtr, dst: *mut u8);
+
     /// Returns stats about the current state of the runtime.
     fn stats(&self) -> Stats;
 }
 
+impl<T: GcRuntime> DynGcRuntime for T {
+    fn try_alloc(&self, ty: &Type) -> Result<GcPtr, AllocError> {
+        GcRuntime::try_alloc(self, ty)
+    }
+
+    fn try_alloc_array(&self, ty: &Type, n: usize) -> Result<GcPtr, AllocError> {
+        GcRuntime::try_alloc_array(self, ty, n).map(|array| array.as_raw())
+    }
+
+    fn try_batch_alloc(&self, ty: &Type, count: usize) -> Result<Vec<GcPtr>, AllocError> {
+        GcRuntime::try_batch_alloc(self, ty, count)
+    }
+
+    fn try_copy(&self, src: GcPtr) -> Result<GcPtr, AllocError> {
+        GcRuntime::try_copy(self, src)
+    }
+
+    fn ptr_type(&self, obj: GcPtr) -> Type {
+        GcRuntime::ptr_type(self, obj)
+    }
+
+    fn root(&self, obj: GcPtr) {
+        GcRuntime::root(self, obj);
+    }
+
+    fn unroot(&self, obj: GcPtr) {
+        GcRuntime::unroot(self, obj);
+    }
+
+    fn root_count(&self) -> usize {
+        GcRuntime::root_count(self)
+    }
+
+    fn rooted_objects(&self) -> Vec<GcPtr> {
+        GcRuntime::rooted_objects(self)
+    }
+
+    fn assert_no_roots(&self) {
+        GcRuntime::assert_no_roots(self);
+    }
+
+    unsafe fn move_to_stack(&self, obj: GcPtr, dst: *mut u8) {
+        GcRuntime::move_to_stack(self, obj, dst);
+    }
+
+    fn stats(&self) -> Stats {
+        GcRuntime::stats(self)
+    }
+}
+
 /// The `Observer` trait allows receiving of `Event`s.
 pub trait Observer: Send + Sync {
     type Event;
@@ -94,6 +388,9 @@ pub enum Event {
     /// The GC performed an allocation
     Allocation(GcPtr),
 
+    /// The GC allocated the specified number of objects at once
+    BatchAllocation(usize),
+
     /// A GC cycle started
     Start,
 
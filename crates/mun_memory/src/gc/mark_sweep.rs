@@ -1,19 +1,25 @@
 use std::{
     alloc::{Layout, LayoutError},
     borrow::Cow,
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     pin::Pin,
     ptr::NonNull,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
 };
 
 use mapping::{Mapping, StructMapping};
+use mun_abi as abi;
 use parking_lot::RwLock;
 
 use crate::{
     cast,
     gc::{
-        array::ArrayHeader, Array as GcArray, Event, GcPtr, GcRuntime, Observer, RawGcPtr, Stats,
-        TypeTrace,
+        array::ArrayHeader, AllocError, Array as GcArray, CollectionStats, Event, GcPtr, GcRuntime,
+        HasIndirectionPtr, Observer, RawGcPtr, Stats, TypeTrace,
     },
     mapping::{self, resolve_struct_to_struct_edit, Action, FieldMapping, MemoryMapper},
     r#type::Type,
@@ -35,7 +41,7 @@ impl Trace {
             TypeKind::Primitive(_) | TypeKind::Pointer(_) => {}
             TypeKind::Struct(_) => {
                 trace.stack.push_back(CompositeTrace::Struct(StructTrace {
-                    struct_ptr: unsafe { obj_ref.data.ptr },
+                    struct_ptr: unsafe { NonNull::new_unchecked(obj_ref.field_ptr_unchecked(0)) },
                     struct_type: obj_ref.ty.clone(),
                     field_index: 0,
                 }));
@@ -99,8 +105,7 @@ impl TraceEvent {
             TypeKind::Primitive(_) | TypeKind::Pointer(_) => None,
             TypeKind::Struct(s) => {
                 if s.is_gc_struct() {
-                    let deref_ptr = unsafe { ptr.cast::<NonNull<ObjectInfo>>().as_ref() };
-                    Some(TraceEvent::Reference(*deref_ptr))
+                    Self::reference(ptr)
                 } else {
                     Some(TraceEvent::InlineStruct(StructTrace {
                         struct_ptr: ptr.cast(),
@@ -109,9 +114,16 @@ impl TraceEvent {
                     }))
                 }
             }
-            TypeKind::Array(_) => Some(TraceEvent::Reference(ptr.cast())),
+            TypeKind::Array(_) => Self::reference(ptr),
         }
     }
+
+    /// Reads the GC handle stored at the specified location. Returns `None` if
+    /// the handle is null.
+    fn reference(ptr: NonNull<u8>) -> Option<TraceEvent> {
+        let deref_ptr = unsafe { *ptr.cast::<*mut ObjectInfo>().as_ref() };
+        NonNull::new(deref_ptr).map(TraceEvent::Reference)
+    }
 }
 
 /// A struct that enables iterating over all GC references in a struct. Structs
@@ -185,6 +197,7 @@ where
     objects: RwLock<HashMap<GcPtr, Pin<Box<ObjectInfo>>>>,
     observer: O,
     stats: RwLock<Stats>,
+    collection_threshold: AtomicUsize,
 }
 
 impl<O> Default for MarkSweep<O>
@@ -196,6 +209,7 @@ where
             objects: RwLock::new(HashMap::new()),
             observer: O::default(),
             stats: RwLock::new(Stats::default()),
+            collection_threshold: AtomicUsize::new(usize::MAX),
         }
     }
 }
@@ -210,6 +224,103 @@ where
             objects: RwLock::new(HashMap::new()),
             observer,
             stats: RwLock::new(Stats::default()),
+            collection_threshold: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    /// Creates a `MarkSweep` memory collector with the specified `Observer`
+    /// that automatically collects garbage when the allocated memory exceeds
+    /// `threshold` bytes. See [`MarkSweep::set_collection_threshold`].
+    pub fn with_collection_threshold(observer: O, threshold: usize) -> Self {
+        let gc = Self::with_observer(observer);
+        gc.set_collection_threshold(threshold);
+        gc
+    }
+
+    /// Sets the number of allocated bytes after which an allocation
+    /// automatically triggers a collection. Use `usize::MAX` to disable
+    /// automatic collection, which is the default.
+    ///
+    /// Note that this makes the latency of allocations non-deterministic, as
+    /// any allocation might have to wait for a full collection.
+    ///
+    /// The collection is performed at the end of the allocation, after the
+    /// new object has been registered. The new object itself survives that
+    /// collection, but any other object that is not reachable from a root is
+    /// freed. Every handle that is still in use must therefore be rooted
+    /// before allocating, e.g. by wrapping it in a [`GcRootPtr`]. This
+    /// includes handles returned from earlier allocations and handles held by
+    /// running Mun code, which does not root its locals.
+    ///
+    /// [`GcRootPtr`]: crate::gc::GcRootPtr
+    pub fn set_collection_threshold(&self, bytes: usize) {
+        self.collection_threshold.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Returns the number of allocated bytes after which an allocation
+    /// automatically triggers a collection.
+    pub fn collection_threshold(&self) -> usize {
+        self.collection_threshold.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes allocated for objects of the type
+    /// identified by `type_id`.
+    ///
+    /// This scans all allocated objects, so it is intended for periodic
+    /// checks, like enforcing a memory budget, rather than hot code paths.
+    pub fn allocated_bytes_for_type(&self, type_id: &abi::TypeId<'_>) -> usize {
+        let objects = self.objects.read();
+        objects
+            .values()
+            .filter(|obj| type_matches_id(&obj.ty, type_id))
+            .map(|obj| obj.layout().size())
+            .sum()
+    }
+
+    /// Returns the number of allocated objects of the type identified by
+    /// `type_id`.
+    ///
+    /// Like [`MarkSweep::allocated_bytes_for_type`], this scans all allocated
+    /// objects.
+    pub fn allocated_object_count_for_type(&self, type_id: &abi::TypeId<'_>) -> usize {
+        let objects = self.objects.read();
+        objects
+            .values()
+            .filter(|obj| type_matches_id(&obj.ty, type_id))
+            .count()
+    }
+
+    /// Registers a newly allocated `object`, and collects garbage afterwards if
+    /// the allocated memory exceeds the collection threshold.
+    fn insert_object(&self, object: Pin<Box<ObjectInfo>>) -> GcPtr {
+        let size = object.layout().size();
+
+        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
+        let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
+
+        {
+            let mut objects = self.objects.write();
+            objects.insert(handle, object);
+        }
+
+        self.log_alloc(handle, size);
+        self.collect_if_exceeds_threshold(&[handle]);
+        handle
+    }
+
+    /// Collects garbage if the allocated memory exceeds the collection
+    /// threshold. The newly allocated objects referred to by `new_handles` are
+    /// kept alive, as their owner did not have a chance to root them yet.
+    fn collect_if_exceeds_threshold(&self, new_handles: &[GcPtr]) {
+        let allocated_memory = self.stats.read().allocated_memory;
+        if allocated_memory > self.collection_threshold() {
+            for handle in new_handles {
+                self.root(*handle);
+            }
+            self.collect();
+            for handle in new_handles {
+                self.unroot(*handle);
+            }
         }
     }
 
@@ -229,32 +340,43 @@ where
     }
 }
 
-fn alloc_obj(ty: Type) -> Pin<Box<ObjectInfo>> {
+fn alloc_obj(ty: Type) -> Result<Pin<Box<ObjectInfo>>, AllocError> {
     let ptr = NonNull::new(unsafe { std::alloc::alloc_zeroed(ty.value_layout()) })
-        .expect("failed to allocate memory for new object");
-    Box::pin(ObjectInfo {
+        .ok_or(AllocError::OutOfMemory)?;
+    Ok(Box::pin(ObjectInfo {
         data: ObjectInfoData { ptr },
         ty,
         roots: 0,
+        pins: 0,
         color: Color::White,
-    })
+        region: None,
+    }))
+}
+
+/// Returns true if `ty` is the type identified by `type_id`.
+fn type_matches_id(ty: &Type, type_id: &abi::TypeId<'_>) -> bool {
+    match type_id {
+        abi::TypeId::Concrete(guid) => ty.as_concrete() == Some(guid),
+        abi::TypeId::Pointer(p) => ty.as_pointer().map_or(false, |pointer| {
+            pointer.is_mutable() == p.mutable && type_matches_id(&pointer.pointee(), p.pointee)
+        }),
+        abi::TypeId::Array(a) => ty.as_array().map_or(false, |array| {
+            type_matches_id(&array.element_type(), a.element)
+        }),
+    }
 }
 
 /// An error that might occur when requesting memory layout of a type
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum MemoryLayoutError {
     /// An error that is returned when the memory requested is to large to deal
     /// with.
+    #[error("the requested memory size exceeds the maximum allocation size")]
     OutOfBounds,
 
     /// An error that is returned by constructing a Layout
-    LayoutError(LayoutError),
-}
-
-impl From<LayoutError> for MemoryLayoutError {
-    fn from(err: LayoutError) -> Self {
-        MemoryLayoutError::LayoutError(err)
-    }
+    #[error("invalid memory layout")]
+    LayoutError(#[from] LayoutError),
 }
 
 /// Helper object to work with [`GcPtr`] that represents an array.
@@ -320,26 +442,8 @@ impl ArrayHandle {
 
     /// Returns a pointer to the data.
     pub fn data(&self) -> NonNull<u8> {
-        // Determine the offset of the data relative from the start of the array
-        // pointer. This the header and the extra alignment padding between the
-        // header and the data.
-        let element_layout = self.element_layout();
-        let header_layout = Layout::new::<ArrayHeader>();
-        let (_, padded_header_size) = header_layout
-            .extend(element_layout)
-            .expect("error creating combined layout of header and element");
-
-        unsafe {
-            NonNull::new_unchecked(
-                self.obj
-                    .as_ref()
-                    .data
-                    .array
-                    .as_ptr()
-                    .cast::<u8>()
-                    .add(padded_header_size),
-            )
-        }
+        // Safety: we can be sure this is an array at this point.
+        unsafe { NonNull::new_unchecked(self.obj.as_ref().array_data_ptr()) }
     }
 }
 
@@ -426,19 +530,21 @@ fn repeat_layout(layout: Layout, n: usize) -> Result<Layout, MemoryLayoutError>
 
 /// Allocates memory for an array type with `length` elements. `array_ty` must
 /// be an array type.
-fn alloc_array(ty: Type, length: usize) -> Pin<Box<ObjectInfo>> {
-    Box::pin(ObjectInfo {
+fn alloc_array(ty: Type, length: usize) -> Result<Pin<Box<ObjectInfo>>, AllocError> {
+    Ok(Box::pin(ObjectInfo {
         data: ObjectInfoData {
-            array: array_header(&ty, length),
+            array: array_header(&ty, length)?,
         },
         ty,
         roots: 0,
+        pins: 0,
         color: Color::White,
-    })
+        region: None,
+    }))
 }
 
 /// Constructs an array header for an array type with `length` elements.
-fn array_header(ty: &Type, length: usize) -> NonNull<ArrayHeader> {
+fn array_header(ty: &Type, length: usize) -> Result<NonNull<ArrayHeader>, AllocError> {
     let array_ty = ty
         .as_array()
         .expect("array type doesnt have an element type");
@@ -446,20 +552,19 @@ fn array_header(ty: &Type, length: usize) -> NonNull<ArrayHeader> {
     // Allocate memory for the array data
     let header_layout = Layout::new::<ArrayHeader>();
     let element_ty_layout = array_ty.element_type().reference_layout();
-    let elements_layout = repeat_layout(element_ty_layout, length)
-        .expect("unable to create a memory layout for array elemets");
+    let elements_layout = repeat_layout(element_ty_layout, length)?;
     let (layout, _) = header_layout
         .extend(elements_layout)
-        .expect("unable to create memory layout for array");
+        .map_err(MemoryLayoutError::from)?;
 
     let mut array_header: NonNull<ArrayHeader> =
         NonNull::new(unsafe { std::alloc::alloc_zeroed(layout).cast() })
-            .expect("error allocating memory for array");
+            .ok_or(AllocError::OutOfMemory)?;
     let array = unsafe { array_header.as_mut() };
     array.length = length;
     array.capacity = length;
 
-    array_header
+    Ok(array_header)
 }
 
 impl<O> GcRuntime for MarkSweep<O>
@@ -468,40 +573,93 @@ where
 {
     type Array = ArrayHandle;
 
-    fn alloc(&self, ty: &Type) -> GcPtr {
+    fn try_alloc(&self, ty: &Type) -> Result<GcPtr, AllocError> {
         assert!(ty.is_concrete());
 
-        let object = alloc_obj(ty.clone());
-        let size = object.layout().size();
+        let object = alloc_obj(ty.clone())?;
+        Ok(self.insert_object(object))
+    }
 
-        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
-        let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
+    fn try_batch_alloc(&self, ty: &Type, count: usize) -> Result<Vec<GcPtr>, AllocError> {
+        assert!(ty.is_concrete());
+        if count == 0 {
+            return Ok(Vec::new());
+        }
 
-        {
-            let mut objects = self.objects.write();
-            objects.insert(handle, object);
+        let mut objects = Vec::with_capacity(count);
+        for _ in 0..count {
+            match alloc_obj(ty.clone()) {
+                Ok(object) => objects.push(object),
+                Err(err) => {
+                    for object in objects {
+                        unsafe { std::alloc::dealloc(object.data.ptr.as_ptr(), object.layout()) };
+                    }
+                    return Err(err);
+                }
+            }
         }
 
-        self.log_alloc(handle, size);
-        handle
-    }
+        let size = ty.value_layout().size().saturating_mul(count);
 
-    fn alloc_array(&self, ty: &Type, n: usize) -> Self::Array {
-        let object = alloc_array(ty.clone(), n);
-        let size = object.layout().size();
+        // We want to return pointers to the `ObjectInfo`s, to be used as handles.
+        let handles: Vec<GcPtr> = objects
+            .iter()
+            .map(|object| (&*object.as_ref() as *const _ as RawGcPtr).into())
+            .collect();
 
-        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
-        let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
+        {
+            let mut objects_map = self.objects.write();
+            objects_map.extend(handles.iter().copied().zip(objects));
+        }
 
         {
-            let mut objects = self.objects.write();
-            objects.insert(handle, object);
+            let mut stats = self.stats.write();
+            stats.allocated_memory += size;
         }
 
-        self.log_alloc(handle, size);
-        ArrayHandle {
+        self.observer.event(Event::BatchAllocation(count));
+        self.collect_if_exceeds_threshold(&handles);
+        Ok(handles)
+    }
+
+    fn try_alloc_array(&self, ty: &Type, n: usize) -> Result<Self::Array, AllocError> {
+        let object = alloc_array(ty.clone(), n)?;
+        let handle = self.insert_object(object);
+        Ok(ArrayHandle {
             obj: unsafe { NonNull::new_unchecked(handle.into()) },
-        }
+        })
+    }
+
+    fn try_copy(&self, src: GcPtr) -> Result<GcPtr, AllocError> {
+        // Keep the source alive in case allocating triggers a collection
+        self.root(src);
+        let ty = self.ptr_type(src);
+        let result = if let Some(src_array) = self.array(src) {
+            let length = src_array.length();
+            self.try_alloc_array(&ty, length).map(|dest_array| {
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        src_array.data().as_ptr(),
+                        dest_array.data().as_ptr(),
+                        length * src_array.element_stride(),
+                    );
+                }
+                dest_array.as_raw()
+            })
+        } else {
+            self.try_alloc(&ty).map(|mut dest| {
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        src.deref::<u8>(),
+                        dest.deref_mut::<u8>(),
+                        ty.value_layout().size(),
+                    );
+                }
+                dest
+            })
+        };
+        self.unroot(src);
+        result
     }
 
     fn ptr_type(&self, handle: GcPtr) -> Type {
@@ -528,6 +686,11 @@ where
     }
 
     fn root(&self, handle: GcPtr) {
+        debug_assert!(!handle.is_null(), "cannot root a null handle");
+        if handle.is_null() {
+            return;
+        }
+
         let _lock = self.objects.write();
 
         // Convert the handle to our internal representation
@@ -537,6 +700,11 @@ where
     }
 
     fn unroot(&self, handle: GcPtr) {
+        debug_assert!(!handle.is_null(), "cannot unroot a null handle");
+        if handle.is_null() {
+            return;
+        }
+
         let _lock = self.objects.write();
 
         // Convert the handle to our internal representation
@@ -545,6 +713,43 @@ where
         unsafe { (*object_info).roots -= 1 };
     }
 
+    fn root_count(&self) -> usize {
+        self.objects
+            .read()
+            .values()
+            .map(|obj| obj.roots as usize)
+            .sum()
+    }
+
+    fn rooted_objects(&self) -> Vec<GcPtr> {
+        self.objects
+            .read()
+            .iter()
+            .filter_map(|(handle, obj)| (obj.roots > 0).then_some(*handle))
+            .collect()
+    }
+
+    unsafe fn move_to_stack(&self, handle: GcPtr, dst: *mut u8) {
+        let mut objects = self.objects.write();
+
+        // Validate the object before removing it, so it stays registered if a
+        // check fails
+        let obj = objects
+            .get(&handle)
+            .expect("cannot move an object that was not allocated by this runtime");
+        assert!(!obj.ty.is_array(), "cannot move an array to the stack");
+        debug_assert_eq!(obj.roots, 0, "cannot move a rooted object to the stack");
+
+        let mut obj = objects.remove(&handle).expect("object was found above");
+        let layout = obj.layout();
+        std::ptr::copy_nonoverlapping(obj.data.ptr.as_ptr(), dst, layout.size());
+        obj.free_data();
+        self.observer.event(Event::Deallocation(handle));
+
+        let mut stats = self.stats.write();
+        stats.allocated_memory -= layout.size();
+    }
+
     fn stats(&self) -> Stats {
         self.stats.read().clone()
     }
@@ -556,7 +761,23 @@ where
 {
     /// Collects all memory that is no longer referenced by rooted objects.
     /// Returns `true` if memory was reclaimed, `false` otherwise.
-    pub fn collect(&self) -> bool {
+    #[deprecated(note = "use `collect` instead, which returns detailed statistics")]
+    pub fn collect_bool(&self) -> bool {
+        self.collect().objects_freed > 0
+    }
+
+    /// Collects all memory that is no longer referenced by rooted objects.
+    /// Returns statistics about the collection.
+    pub fn collect(&self) -> CollectionStats {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "gc_collect",
+            objects_visited = tracing::field::Empty,
+            bytes_freed = tracing::field::Empty
+        )
+        .entered();
+
+        let start_time = Instant::now();
         self.observer.event(Event::Start);
 
         let mut objects = self.objects.write();
@@ -597,6 +818,7 @@ where
 
         // Sweep all non-reachable objects
         let size_before = objects.len();
+        let mut bytes_freed = 0;
         objects.retain(|h, obj| {
             if obj.color == Color::Black {
                 unsafe {
@@ -605,7 +827,8 @@ where
                 true
             } else {
                 let value_memory_layout = obj.layout();
-                unsafe { std::alloc::dealloc(obj.data.ptr.as_mut(), value_memory_layout) };
+                bytes_freed += value_memory_layout.size();
+                unsafe { obj.free_data() };
                 self.observer.event(Event::Deallocation(*h));
                 {
                     let mut stats = self.stats.write();
@@ -616,9 +839,142 @@ where
         });
         let size_after = objects.len();
 
+        #[cfg(feature = "tracing")]
+        {
+            span.record("objects_visited", size_after);
+            span.record("bytes_freed", bytes_freed);
+        }
+
         self.observer.event(Event::End);
 
-        size_before != size_after
+        CollectionStats {
+            objects_visited: size_after,
+            objects_freed: size_before - size_after,
+            bytes_freed,
+            duration: start_time.elapsed(),
+        }
+    }
+
+    /// Pins the memory of the object referred to by `handle`, preventing it
+    /// from being moved by [`MarkSweep::compact`]. This is required when a
+    /// raw pointer to the object's memory is handed out, e.g. to a C
+    /// callback.
+    ///
+    /// Pins are reference counted; an object remains pinned until every call
+    /// to `pin_object` has been matched by a call to
+    /// [`MarkSweep::unpin_object`].
+    pub fn pin_object(&self, handle: GcPtr) {
+        debug_assert!(!handle.is_null(), "cannot pin a null handle");
+        if handle.is_null() {
+            return;
+        }
+
+        let _lock = self.objects.write();
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo = handle.into();
+
+        unsafe { (*object_info).pins += 1 };
+    }
+
+    /// Releases a pin previously acquired with [`MarkSweep::pin_object`].
+    pub fn unpin_object(&self, handle: GcPtr) {
+        debug_assert!(!handle.is_null(), "cannot unpin a null handle");
+        if handle.is_null() {
+            return;
+        }
+
+        let _lock = self.objects.write();
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo = handle.into();
+
+        unsafe {
+            debug_assert!((*object_info).pins > 0, "object is not pinned");
+            (*object_info).pins = (*object_info).pins.saturating_sub(1);
+        };
+    }
+
+    /// Moves the memory of all live objects into a single, freshly allocated
+    /// contiguous block. Objects that are pinned (see
+    /// [`MarkSweep::pin_object`]) are left in place, as are zero-sized
+    /// objects.
+    ///
+    /// Compaction runs in two passes. The first pass determines the offset of
+    /// every object in the new block, the second pass copies the data of
+    /// every object and points its [`ObjectInfo`] at the new location. The
+    /// memory previously used by an object is released; a block of an earlier
+    /// compaction is freed once none of its objects remain in it.
+    ///
+    /// Handles refer to an object's [`ObjectInfo`] rather than to its memory,
+    /// so [`GcPtr`]s stored in roots and other objects remain valid after
+    /// compaction. If the new block cannot be allocated, no objects are moved.
+    /// This operation is expensive and should only be called infrequently,
+    /// preferably directly after a [`MarkSweep::collect`].
+    pub fn compact(&self) {
+        let mut objects = self.objects.write();
+
+        // Determine where each object will be located in the new region
+        let mut region_layout = Layout::new::<()>();
+        let mut moves = Vec::new();
+        for obj in objects.values_mut() {
+            if obj.pins > 0 {
+                continue;
+            }
+
+            let layout = obj.layout();
+            if layout.size() == 0 {
+                continue;
+            }
+
+            let Ok((extended_layout, offset)) = region_layout.extend(layout) else {
+                break;
+            };
+            region_layout = extended_layout;
+            moves.push((obj, offset));
+        }
+
+        let mut bytes_moved = 0;
+        if let Some(region) = Region::new(region_layout.pad_to_align()) {
+            let region = Arc::new(region);
+
+            // Move the data of each object into the region
+            for (obj, offset) in moves {
+                let size = obj.layout().size();
+                unsafe {
+                    let obj = obj.as_mut().get_unchecked_mut();
+                    let new_ptr = NonNull::new_unchecked(region.ptr.as_ptr().add(offset));
+                    std::ptr::copy_nonoverlapping(obj.data.ptr.as_ptr(), new_ptr.as_ptr(), size);
+                    obj.free_data();
+                    obj.data.ptr = new_ptr;
+                    obj.region = Some(region.clone());
+                }
+                bytes_moved += size;
+            }
+        }
+
+        let mut stats = self.stats.write();
+        stats.compact_count += 1;
+        stats.bytes_moved += bytes_moved;
+    }
+
+    /// Deallocates every object, regardless of whether it is rooted or
+    /// reachable, and resets the stats. A [`Event::Deallocation`] is emitted
+    /// for every object.
+    ///
+    /// # Safety
+    ///
+    /// All [`GcPtr`]s handed out by this instance, including those held by
+    /// [`GcRootPtr`](super::GcRootPtr)s, dangle after calling this function.
+    /// They must not be used anymore.
+    pub unsafe fn reset(&self) {
+        let mut objects = self.objects.write();
+        for (handle, mut obj) in objects.drain() {
+            obj.free_data();
+            self.observer.event(Event::Deallocation(handle));
+        }
+
+        *self.stats.write() = Stats::default();
     }
 }
 
@@ -628,14 +984,13 @@ where
 {
     fn map_memory(&self, mapping: Mapping) -> Vec<GcPtr> {
         unsafe fn get_field_ptr(struct_ptr: NonNull<u8>, offset: usize) -> NonNull<u8> {
-            let mut ptr = struct_ptr.as_ptr() as usize;
-            ptr += offset;
-            NonNull::new_unchecked(ptr as *mut u8)
+            NonNull::new_unchecked(struct_ptr.as_ptr().add(offset))
         }
 
         #[allow(clippy::mutable_key_type)]
         fn map_array(
             new_allocations: &mut Vec<Pin<Box<ObjectInfo>>>,
+            visited: &mut HashSet<GcPtr>,
             conversions: &HashMap<Type, StructMapping>,
             mut src_object: NonNull<ObjectInfo>,
             element_action: &Action,
@@ -644,13 +999,16 @@ where
             let src_array = ArrayHandle { obj: src_object };
 
             // Initialize the array
-            let new_header = array_header(new_ty, src_array.length());
+            let new_header = array_header(new_ty, src_array.length())
+                .expect("failed to allocate memory for array");
 
             let mut dest_obj = ObjectInfo {
                 data: ObjectInfoData { array: new_header },
                 roots: unsafe { src_object.as_ref().roots },
+                pins: unsafe { src_object.as_ref().pins },
                 color: unsafe { src_object.as_ref().color },
                 ty: new_ty.clone(),
+                region: None,
             };
 
             let dest_array = ArrayHandle {
@@ -664,6 +1022,7 @@ where
                 .for_each(|(src, dest)| {
                     map_type(
                         new_allocations,
+                        visited,
                         conversions,
                         src,
                         dest,
@@ -674,7 +1033,7 @@ where
 
             unsafe {
                 let src_obj = src_object.as_mut();
-                std::alloc::dealloc(src_obj.data.ptr.as_mut(), src_obj.layout());
+                src_obj.free_data();
                 *src_obj = dest_obj;
             };
         }
@@ -682,6 +1041,7 @@ where
         #[allow(clippy::mutable_key_type)]
         fn map_type(
             new_allocations: &mut Vec<Pin<Box<ObjectInfo>>>,
+            visited: &mut HashSet<GcPtr>,
             conversions: &HashMap<Type, StructMapping>,
             src: NonNull<u8>,
             dest: NonNull<u8>,
@@ -691,7 +1051,8 @@ where
             match action {
                 mapping::Action::ArrayAlloc => {
                     // Initialize the array with no values
-                    let object = alloc_array(new_ty.clone(), 0);
+                    let object = alloc_array(new_ty.clone(), 0)
+                        .expect("failed to allocate memory for array");
 
                     // We want to return a pointer to the `ObjectInfo`, to be used as handle.
                     let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
@@ -707,20 +1068,16 @@ where
                     old_offset,
                 } => {
                     // Initialize the array with a single value
-                    let mut object = alloc_array(new_ty.clone(), 1);
-
-                    let array_handle = ArrayHandle {
-                        obj: unsafe {
-                            NonNull::new_unchecked(&mut *object.as_mut() as *mut ObjectInfo)
-                        },
-                    };
+                    let object = alloc_array(new_ty.clone(), 1)
+                        .expect("failed to allocate memory for array");
 
                     // Map single element to array
                     map_type(
                         new_allocations,
+                        visited,
                         conversions,
                         unsafe { get_field_ptr(src, *old_offset) },
-                        array_handle.data(),
+                        unsafe { NonNull::new_unchecked(object.element_ptr(0)) },
                         element_action,
                         &new_ty.as_array().expect("Must be an array.").element_type(),
                     );
@@ -743,13 +1100,19 @@ where
                     // Safety: we already hold a write lock on `objects`, so this is legal.
                     let src_obj = unsafe { *src_ptr.cast::<NonNull<ObjectInfo>>().as_ref() };
 
-                    map_array(
-                        new_allocations,
-                        conversions,
-                        src_obj,
-                        element_action,
-                        new_ty,
-                    );
+                    // Multiple objects can reference the same array, which must only be mapped
+                    // once.
+                    let handle = (src_obj.as_ptr() as RawGcPtr).into();
+                    if visited.insert(handle) {
+                        map_array(
+                            new_allocations,
+                            visited,
+                            conversions,
+                            src_obj,
+                            element_action,
+                            new_ty,
+                        );
+                    }
 
                     unsafe {
                         std::ptr::copy_nonoverlapping(
@@ -791,14 +1154,14 @@ where
                             .as_ref()
                     };
 
-                    let array_handle = ArrayHandle { obj };
-
-                    if array_handle.header().length > 0 {
+                    let obj = unsafe { obj.as_ref() };
+                    if unsafe { obj.data.array.as_ref() }.length > 0 {
                         // Map single element from array
                         map_type(
                             new_allocations,
+                            visited,
                             conversions,
-                            array_handle.data(),
+                            unsafe { NonNull::new_unchecked(obj.element_ptr(0)) },
                             dest,
                             element_action,
                             new_ty,
@@ -808,7 +1171,8 @@ where
                     }
                 }
                 mapping::Action::StructAlloc => {
-                    let object = alloc_obj(new_ty.clone());
+                    let object = alloc_obj(new_ty.clone())
+                        .expect("failed to allocate memory for new object");
 
                     // We want to return a pointer to the `ObjectInfo`, to be used as handle.
                     let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
@@ -836,15 +1200,17 @@ where
                     // Map heap-allocated struct to in-memory struct
                     map_struct(
                         new_allocations,
+                        visited,
                         conversions,
                         &conversion.field_mapping,
                         // SAFETY: pointer is guaranteed to be valid
-                        unsafe { object.as_ref().data.ptr },
+                        unsafe { NonNull::new_unchecked(object.as_ref().field_ptr_unchecked(0)) },
                         dest,
                     );
                 }
                 mapping::Action::StructMapFromValue { old_ty, old_offset } => {
-                    let object = alloc_obj(new_ty.clone());
+                    let object = alloc_obj(new_ty.clone())
+                        .expect("failed to allocate memory for new object");
 
                     let conversion = conversions.get(old_ty).unwrap_or_else(|| {
                         panic!(
@@ -855,11 +1221,12 @@ where
                     // Map in-memory struct to heap-allocated struct
                     map_struct(
                         new_allocations,
+                        visited,
                         conversions,
                         &conversion.field_mapping,
                         unsafe { get_field_ptr(src, *old_offset) },
                         // SAFETY: pointer is guaranteed to be valid
-                        unsafe { object.as_ref().data.ptr },
+                        unsafe { NonNull::new_unchecked(object.as_ref().field_ptr_unchecked(0)) },
                     );
 
                     // We want to return a pointer to the `ObjectInfo`, to be used as handle.
@@ -880,21 +1247,23 @@ where
 
                     map_struct(
                         new_allocations,
+                        visited,
                         conversions,
                         &conversion.field_mapping,
                         unsafe { get_field_ptr(src, *old_offset) },
                         dest,
                     );
                 }
-                mapping::Action::ZeroInitialize => {
-                    // Use previously zero-initialized memory
-                }
+                mapping::Action::ZeroInit { size } => unsafe {
+                    std::ptr::write_bytes(dest.as_ptr(), 0, *size);
+                },
             }
         }
 
         #[allow(clippy::mutable_key_type)]
         fn map_struct(
             new_allocations: &mut Vec<Pin<Box<ObjectInfo>>>,
+            visited: &mut HashSet<GcPtr>,
             conversions: &HashMap<Type, StructMapping>,
             mapping: &[FieldMapping],
             src: NonNull<u8>,
@@ -909,6 +1278,7 @@ where
                 let field_dest = unsafe { get_field_ptr(dest, *new_offset) };
                 map_type(
                     new_allocations,
+                    visited,
                     conversions,
                     src,
                     field_dest,
@@ -918,6 +1288,10 @@ where
             }
         }
 
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("map_memory", conversions = mapping.struct_mappings.len())
+            .entered();
+
         let mut objects = self.objects.write();
 
         // Determine which types are still allocated with deleted types
@@ -941,14 +1315,17 @@ where
                             ptr: unsafe { object_info.data.ptr },
                         },
                         roots: object_info.roots,
+                        pins: object_info.pins,
                         color: object_info.color,
                         ty: new_ty.clone(),
+                        region: object_info.region.clone(),
                     });
                 }
             }
         }
 
         let mut new_allocations = Vec::new();
+        let mut visited = HashSet::new();
 
         // Map struct types
         objects
@@ -956,7 +1333,6 @@ where
             .filter(|object_info| object_info.ty.is_struct())
             .for_each(|object_info| {
                 if let Some(conversion) = mapping.struct_mappings.get(&object_info.ty) {
-                    let old_layout = object_info.ty.value_layout();
                     let src = unsafe { object_info.data.ptr };
                     let dest = unsafe {
                         NonNull::new_unchecked(std::alloc::alloc_zeroed(
@@ -966,28 +1342,35 @@ where
 
                     map_struct(
                         &mut new_allocations,
+                        &mut visited,
                         &mapping.struct_mappings,
                         &conversion.field_mapping,
                         src,
                         dest,
                     );
 
-                    unsafe { std::alloc::dealloc(src.as_ptr(), old_layout) };
+                    unsafe { object_info.free_data() };
 
                     object_info.set(ObjectInfo {
                         data: ObjectInfoData { ptr: dest },
                         roots: object_info.roots,
+                        pins: object_info.pins,
                         color: object_info.color,
                         ty: conversion.new_ty.clone(),
+                        region: None,
                     });
                 }
             });
 
-        // Map rooted array types
+        // Map rooted array types that were not already mapped through a field
         objects
-            .values_mut()
-            .filter(|object_info| object_info.ty.is_array())
-            .for_each(|object_info| {
+            .iter_mut()
+            .filter(|(_, object_info)| object_info.ty.is_array())
+            .for_each(|(handle, object_info)| {
+                if !visited.insert(*handle) {
+                    return;
+                }
+
                 let mut ty = object_info.ty.clone();
                 let mut stack = Vec::new();
 
@@ -1015,6 +1398,7 @@ where
 
                         map_array(
                             &mut new_allocations,
+                            &mut visited,
                             &mapping.struct_mappings,
                             unsafe {
                                 NonNull::new_unchecked(
@@ -1068,8 +1452,12 @@ enum Color {
 struct ObjectInfo {
     pub data: ObjectInfoData,
     pub roots: u32,
+    pub pins: u32,
     pub color: Color,
     pub ty: Type,
+    /// The region that holds the object's data, if it was moved there by
+    /// [`MarkSweep::compact`]. Otherwise the data was allocated separately.
+    pub region: Option<Arc<Region>>,
 }
 
 #[repr(C)]
@@ -1113,16 +1501,62 @@ impl From<NonNull<ObjectInfo>> for GcPtr {
     }
 }
 
+/// A contiguous block of memory that holds the data of multiple objects, as
+/// created by [`MarkSweep::compact`]. The block is freed once the last object
+/// that refers to it is freed or moved elsewhere.
+struct Region {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+/// A `Region` is only accessed through the objects that refer to it.
+unsafe impl Send for Region {}
+
+unsafe impl Sync for Region {}
+
+impl Region {
+    /// Allocates a new region with the specified `layout`. Returns `None` if
+    /// the layout is empty or the memory could not be allocated.
+    fn new(layout: Layout) -> Option<Self> {
+        if layout.size() == 0 {
+            return None;
+        }
+
+        let ptr = NonNull::new(unsafe { std::alloc::alloc(layout) })?;
+        Some(Region { ptr, layout })
+    }
+}
+
+impl Drop for Region {
+    fn drop(&mut self) {
+        unsafe { std::alloc::dealloc(self.ptr.as_ptr(), self.layout) };
+    }
+}
+
 impl ObjectInfo {
+    /// Releases the memory that holds the object's data. If the data is part
+    /// of a [`Region`], the region is freed once no other object refers to
+    /// it.
+    ///
+    /// # Safety
+    ///
+    /// The object's data must not be accessed afterwards, until it points to
+    /// new memory.
+    unsafe fn free_data(&mut self) {
+        if self.region.take().is_none() {
+            std::alloc::dealloc(self.data.ptr.as_ptr(), self.layout());
+        }
+    }
+
     /// Returns the layout of the data pointed to by data
     pub fn layout(&self) -> Layout {
         match self.ty.kind() {
             TypeKind::Struct(_) | TypeKind::Primitive(_) | TypeKind::Pointer(_) => {
                 self.ty.value_layout()
             }
-            TypeKind::Array(array) => {
+            TypeKind::Array(_) => {
                 let elem_count = unsafe { self.data.array.as_ref().capacity };
-                let elem_layout = repeat_layout(array.element_type().value_layout(), elem_count)
+                let elem_layout = repeat_layout(self.array_element_layout(), elem_count)
                     .expect("unable to determine layout of array elements");
                 let (layout, _) = Layout::new::<ArrayHeader>()
                     .extend(elem_layout)
@@ -1131,4 +1565,98 @@ impl ObjectInfo {
             }
         }
     }
+
+    /// Returns a pointer to the memory at `offset` bytes into the object's
+    /// data.
+    ///
+    /// # Safety
+    ///
+    /// The object must not be an array and `offset` must lie within the
+    /// object's memory. This is only checked in debug builds.
+    pub unsafe fn field_ptr_unchecked(&self, offset: usize) -> *mut u8 {
+        self.field_ptr_unchecked_as::<()>(offset).cast()
+    }
+
+    /// Returns a pointer to the value of type `U` at `offset` bytes into the
+    /// object's data.
+    ///
+    /// # Safety
+    ///
+    /// The object must not be an array and a `U` at `offset` must lie within
+    /// the object's memory. This is only checked in debug builds.
+    pub unsafe fn field_ptr_unchecked_as<U>(&self, offset: usize) -> *mut U {
+        debug_assert!(!self.ty.is_array(), "arrays do not have fields");
+        debug_assert!(
+            offset + std::mem::size_of::<U>() <= self.ty.value_layout().size(),
+            "field at offset {offset} exceeds the size of type `{}`",
+            self.ty.name()
+        );
+        self.data.ptr.as_ptr().add(offset).cast()
+    }
+
+    /// Returns a pointer to the first element of the array's data.
+    ///
+    /// # Safety
+    ///
+    /// The object must be an array.
+    unsafe fn array_data_ptr(&self) -> *mut u8 {
+        // Determine the offset of the data relative from the start of the array
+        // pointer. This the header and the extra alignment padding between the
+        // header and the data.
+        let element_layout = self.array_element_layout();
+        let header_layout = Layout::new::<ArrayHeader>();
+        let (_, padded_header_size) = header_layout
+            .extend(element_layout)
+            .expect("error creating combined layout of header and element");
+
+        self.data
+            .array
+            .as_ptr()
+            .cast::<u8>()
+            .add(padded_header_size)
+    }
+
+    /// Returns the layout of an element stored in the array. For garbage
+    /// collected element types, this is the layout of a reference.
+    fn array_element_layout(&self) -> Layout {
+        self.ty
+            .as_array()
+            .expect("object is not an array")
+            .element_type()
+            .reference_layout()
+    }
+
+    /// Returns a pointer to the element at `index` of the array.
+    ///
+    /// # Safety
+    ///
+    /// The object must be an array and `index` must be smaller than the
+    /// length of the array. The bounds are only checked in debug builds.
+    pub unsafe fn element_ptr(&self, index: usize) -> *mut u8 {
+        self.element_ptr_as::<()>(index).cast()
+    }
+
+    /// Returns a pointer to the element of type `U` at `index` of the array.
+    ///
+    /// # Safety
+    ///
+    /// The object must be an array of `U` elements and `index` must be smaller
+    /// than the length of the array. The bounds are only checked in debug
+    /// builds.
+    pub unsafe fn element_ptr_as<U>(&self, index: usize) -> *mut U {
+        let element_layout = self.array_element_layout();
+        debug_assert!(
+            index < self.data.array.as_ref().length,
+            "index {index} is out of bounds for an array of length {}",
+            self.data.array.as_ref().length
+        );
+        debug_assert!(
+            std::mem::size_of::<U>() <= element_layout.size(),
+            "elements of type `{}` are smaller than `{}`",
+            self.ty.name(),
+            std::any::type_name::<U>()
+        );
+        let stride = element_layout.pad_to_align().size();
+        self.array_data_ptr().add(index * stride).cast()
+    }
 }
@@ -55,7 +55,23 @@ impl From<RawGcPtr> for GcPtr {
     }
 }
 
+impl Default for GcPtr {
+    fn default() -> Self {
+        GcPtr::null()
+    }
+}
+
 impl GcPtr {
+    /// Constructs a `GcPtr` that doesn't reference any object.
+    pub const fn null() -> Self {
+        GcPtr(std::ptr::null())
+    }
+
+    /// Returns true if this `GcPtr` doesn't reference any object.
+    pub fn is_null(self) -> bool {
+        self.0.is_null()
+    }
+
     pub(crate) fn as_ptr(self) -> RawGcPtr {
         self.0
     }
@@ -11,6 +11,7 @@ mod cast;
 pub mod diff;
 pub mod gc;
 pub mod mapping;
+pub mod reflect;
 mod r#type;
 pub mod type_table;
 use mun_abi as abi;
@@ -22,6 +22,83 @@ pub struct Mapping {
     pub identical: Vec<(Type, Type)>,
 }
 
+/// A builder for a [`Mapping`] that ensures that every old type is mapped at
+/// most once.
+#[derive(Debug, Default)]
+pub struct MappingBuilder {
+    deletions: HashSet<Type>,
+    struct_mappings: HashMap<Type, StructMapping>,
+    identical: Vec<(Type, Type)>,
+    /// All old types that have been mapped so far
+    mapped: HashSet<Type>,
+    duplicates: Vec<Type>,
+}
+
+/// An error that can occur when building a [`Mapping`].
+#[derive(Debug, thiserror::Error)]
+pub enum MappingBuildError {
+    /// The old type was added to the builder more than once, either to the
+    /// same or to different categories (identical, conversion, or deletion).
+    #[error("type '{}' was mapped more than once", .0.name())]
+    DuplicateType(Type),
+}
+
+impl MappingBuilder {
+    /// Constructs a new, empty `MappingBuilder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a type that didn't change.
+    pub fn add_identical(mut self, old: Type, new: Type) -> Self {
+        if self.mark_mapped(&old) {
+            self.identical.push((old, new));
+        }
+        self
+    }
+
+    /// Adds a struct type whose fields changed.
+    pub fn add_conversion(mut self, old: Type, conversion: StructMapping) -> Self {
+        if self.mark_mapped(&old) {
+            self.struct_mappings.insert(old, conversion);
+        }
+        self
+    }
+
+    /// Adds a type that was deleted.
+    pub fn add_deletion(mut self, ty: Type) -> Self {
+        if self.mark_mapped(&ty) {
+            self.deletions.insert(ty);
+        }
+        self
+    }
+
+    /// Finishes building the [`Mapping`], returning an error if any type was
+    /// mapped more than once.
+    pub fn build(mut self) -> Result<Mapping, MappingBuildError> {
+        if let Some(ty) = self.duplicates.pop() {
+            return Err(MappingBuildError::DuplicateType(ty));
+        }
+
+        Ok(Mapping {
+            deletions: self.deletions,
+            struct_mappings: self.struct_mappings,
+            identical: self.identical,
+        })
+    }
+
+    /// Marks the old type `ty` as mapped. Returns `false` and records a
+    /// duplicate if it was already mapped.
+    fn mark_mapped(&mut self, ty: &Type) -> bool {
+        if self.mapped.insert(ty.clone()) {
+            true
+        } else {
+            self.duplicates.push(ty.clone());
+            false
+        }
+    }
+}
+
 /// The struct mapping needed to convert an old into a new struct of unique and
 /// ordered fields.
 #[derive(Debug)]
@@ -78,8 +155,11 @@ pub enum Action {
     StructMapFromValue { old_ty: Type, old_offset: usize },
     /// Map a value struct in-place.
     StructMapInPlace { old_ty: Type, old_offset: usize },
-    /// Ensure the memory is zero-initialized.
-    ZeroInitialize,
+    /// Zero-initialize the memory.
+    ZeroInit {
+        /// Size in bytes
+        size: usize,
+    },
 }
 
 impl Mapping {
@@ -87,16 +167,13 @@ impl Mapping {
     pub fn new(old: &[Type], new: &[Type]) -> Self {
         let diff = compute_struct_diff(old, new);
 
-        let mut conversions = HashMap::new();
-        let mut deletions = HashSet::new();
+        let mut builder = MappingBuilder::new();
         let mut insertions = HashSet::new();
 
-        let mut identical = Vec::new();
-
         for diff in diff.iter() {
             match diff {
                 StructDiff::Delete { ty, .. } => {
-                    deletions.insert(ty.clone());
+                    builder = builder.add_deletion(ty.clone());
                 }
                 StructDiff::Edit {
                     diff,
@@ -104,7 +181,7 @@ impl Mapping {
                     new_ty,
                     ..
                 } => {
-                    conversions.insert(old_ty.clone(), unsafe {
+                    builder = builder.add_conversion(old_ty.clone(), unsafe {
                         field_mapping(old_ty, new_ty, diff)
                     });
                 }
@@ -112,7 +189,7 @@ impl Mapping {
                     insertions.insert(ty.clone());
                 }
                 StructDiff::Move { old_ty, new_ty, .. } => {
-                    identical.push((old_ty.clone(), new_ty.clone()));
+                    builder = builder.add_identical(old_ty.clone(), new_ty.clone());
                 }
             }
         }
@@ -133,10 +210,10 @@ impl Mapping {
             // Filter non-struct types
             .filter(|ty| ty.is_struct())
             // Filter deleted structs
-            .filter(|ty| !deletions.contains(*ty))
+            .filter(|ty| !builder.deletions.contains(*ty))
             // Filter edited types
             .filter(|ty| {
-                if let Some(conversion) = conversions.get(*ty) {
+                if let Some(conversion) = builder.struct_mappings.get(*ty) {
                     // Remove its new counterpart too
                     new_candidates.remove(&conversion.new_ty);
                     false
@@ -149,7 +226,7 @@ impl Mapping {
 
         // Remove moved types from the candidates, since we already know they are
         // identical
-        for (old_ty, new_ty) in identical.iter() {
+        for (old_ty, new_ty) in builder.identical.iter() {
             old_candidates.remove(old_ty);
             new_candidates.remove(new_ty);
         }
@@ -157,17 +234,15 @@ impl Mapping {
         // Find matching (old_ty, new_ty) pairs
         for old_ty in old_candidates {
             let new_ty = new_candidates.take(&old_ty).unwrap();
-            identical.push((old_ty, new_ty));
+            builder = builder.add_identical(old_ty, new_ty);
         }
 
         // We should have matched all remaining candidates
         debug_assert!(new_candidates.is_empty());
 
-        Self {
-            deletions,
-            struct_mappings: conversions,
-            identical,
-        }
+        builder
+            .build()
+            .expect("A struct diff maps every old type at most once.")
     }
 }
 
@@ -240,7 +315,9 @@ pub unsafe fn field_mapping(old_ty: &Type, new_ty: &Type, diff: &[FieldDiff]) ->
                 } else if new_type.is_array() {
                     Action::ArrayAlloc
                 } else {
-                    Action::ZeroInitialize
+                    Action::ZeroInit {
+                        size: new_type.reference_layout().size(),
+                    }
                 },
             )),
             FieldDiff::Move {
@@ -338,7 +415,9 @@ fn resolve_primitive_edit(
         }
         TypeKind::Struct(s) => {
             if s.is_value_struct() {
-                Action::ZeroInitialize
+                Action::ZeroInit {
+                    size: new_ty.reference_layout().size(),
+                }
             } else {
                 Action::StructAlloc
             }
@@ -382,7 +461,9 @@ fn resolve_primitive_to_array_edit(
 
 fn resolve_struct_edit(old_ty: &Type, new_ty: &Type, old_offset: usize) -> Action {
     match &new_ty.kind() {
-        TypeKind::Primitive(_) => Action::ZeroInitialize,
+        TypeKind::Primitive(_) => Action::ZeroInit {
+            size: new_ty.reference_layout().size(),
+        },
         TypeKind::Struct(_) => resolve_struct_to_struct_edit(old_ty, new_ty, old_offset),
         TypeKind::Pointer(_) => unreachable!(),
         TypeKind::Array(new_array) => resolve_struct_to_array_edit(old_ty, new_array, old_offset),
@@ -410,7 +491,9 @@ pub fn resolve_struct_to_struct_edit(old_ty: &Type, new_ty: &Type, old_offset: u
                 old_offset,
             }
         } else {
-            Action::ZeroInitialize
+            Action::ZeroInit {
+                size: new_ty.reference_layout().size(),
+            }
         }
     } else if old_ty.is_value_type() {
         // struct(value) -> struct(gc)
@@ -430,7 +513,9 @@ pub fn resolve_struct_to_struct_edit(old_ty: &Type, new_ty: &Type, old_offset: u
                 old_offset,
             }
         } else {
-            Action::ZeroInitialize
+            Action::ZeroInit {
+                size: new_ty.reference_layout().size(),
+            }
         }
     } else {
         // struct(gc) -> struct(gc)
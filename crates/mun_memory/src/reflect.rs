@@ -0,0 +1,123 @@
+//! Functions to read and write the fields of garbage collected structs without
+//! knowing their type at compile time.
+
+use crate::{
+    gc::{GcPtr, GcRuntime, HasIndirectionPtr},
+    HasStaticType, Type,
+};
+
+/// An error that can occur when reflecting on the fields of an object.
+#[derive(Debug, thiserror::Error)]
+pub enum ReflectError {
+    /// The object pointer was null
+    #[error("cannot reflect on a null pointer")]
+    NullPointer,
+    /// The object's type does not have a field with the requested name
+    #[error("type `{type_name}` does not have a field `{field_name}`")]
+    UnknownField {
+        type_name: String,
+        field_name: String,
+    },
+    /// The field's type differs from the requested type
+    #[error("mismatched types for field `{field_name}`: expected `{expected}`, found `{found}`")]
+    TypeMismatch {
+        field_name: String,
+        expected: Type,
+        found: Type,
+    },
+}
+
+/// Returns a pointer to the memory of the field `field_name` of the struct
+/// referenced by `ptr`, checking that it is of type `T`.
+fn field_ptr<T: HasStaticType, G: GcRuntime + ?Sized>(
+    gc: &G,
+    ptr: GcPtr,
+    field_name: &str,
+) -> Result<*mut T, ReflectError> {
+    if ptr.is_null() {
+        return Err(ReflectError::NullPointer);
+    }
+
+    let ty = gc.ptr_type(ptr);
+    let field = ty
+        .as_struct()
+        .and_then(|s| s.fields().find_by_name(field_name))
+        .ok_or_else(|| ReflectError::UnknownField {
+            type_name: ty.name().to_owned(),
+            field_name: field_name.to_owned(),
+        })?;
+
+    let field_ty = field.ty();
+    if &field_ty != T::type_info() {
+        return Err(ReflectError::TypeMismatch {
+            field_name: field_name.to_owned(),
+            expected: T::type_info().clone(),
+            found: field_ty,
+        });
+    }
+
+    // Safety: the handle is not null and the field is part of the object's type
+    Ok(unsafe { ptr.deref::<u8>().add(field.offset()).cast::<T>().cast_mut() })
+}
+
+/// Reads the value of the field `field_name` of the struct referenced by
+/// `ptr`.
+pub fn read_field<T: HasStaticType + Copy, G: GcRuntime + ?Sized>(
+    gc: &G,
+    ptr: GcPtr,
+    field_name: &str,
+) -> Result<T, ReflectError> {
+    field_ptr::<T, G>(gc, ptr, field_name).map(|field| unsafe { field.read() })
+}
+
+/// Writes `value` to the field `field_name` of the struct referenced by
+/// `ptr`.
+pub fn write_field<T: HasStaticType + Copy, G: GcRuntime + ?Sized>(
+    gc: &G,
+    ptr: GcPtr,
+    field_name: &str,
+    value: T,
+) -> Result<(), ReflectError> {
+    field_ptr::<T, G>(gc, ptr, field_name).map(|field| unsafe { field.write(value) })
+}
+
+macro_rules! primitive_field_accessors {
+    ($($ty:ty => $read:ident, $write:ident),+ $(,)?) => {
+        $(
+            #[doc = concat!("Reads the `", stringify!($ty), "` field `field_name` of the struct referenced by `ptr`.")]
+            pub fn $read<G: GcRuntime + ?Sized>(
+                gc: &G,
+                ptr: GcPtr,
+                field_name: &str,
+            ) -> Result<$ty, ReflectError> {
+                read_field(gc, ptr, field_name)
+            }
+
+            #[doc = concat!("Writes the `", stringify!($ty), "` field `field_name` of the struct referenced by `ptr`.")]
+            pub fn $write<G: GcRuntime + ?Sized>(
+                gc: &G,
+                ptr: GcPtr,
+                field_name: &str,
+                value: $ty,
+            ) -> Result<(), ReflectError> {
+                write_field(gc, ptr, field_name, value)
+            }
+        )+
+    };
+}
+
+primitive_field_accessors!(
+    bool => read_field_bool, write_field_bool,
+    i8 => read_field_i8, write_field_i8,
+    i16 => read_field_i16, write_field_i16,
+    i32 => read_field_i32, write_field_i32,
+    i64 => read_field_i64, write_field_i64,
+    i128 => read_field_i128, write_field_i128,
+    u8 => read_field_u8, write_field_u8,
+    u16 => read_field_u16, write_field_u16,
+    u32 => read_field_u32, write_field_u32,
+    u64 => read_field_u64, write_field_u64,
+    u128 => read_field_u128, write_field_u128,
+    f32 => read_field_f32, write_field_f32,
+    f64 => read_field_f64, write_field_f64,
+);
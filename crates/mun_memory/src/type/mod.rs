@@ -346,11 +346,24 @@ impl Drop for Type {
 
 impl PartialEq for Type {
     fn eq(&self, other: &Self) -> bool {
-        self.inner() == other.inner()
+        self.inner == other.inner || self.inner() == other.inner()
     }
 }
 
 impl Eq for Type {}
+
+impl PartialOrd for Type {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Type {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.inner().cmp(other.inner())
+    }
+}
+
 impl Hash for Type {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.inner().hash(state);
@@ -496,14 +509,53 @@ impl TypeData {
     }
 }
 
+/// Two types are considered equal if they have the same identity. For
+/// primitives and structs this is their GUID, which means that two versions of
+/// the same struct, for instance from different assemblies, compare equal even
+/// if their fields differ.
 impl PartialEq for TypeData {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.layout == other.layout && self.data == other.data
+        self.data == other.data
     }
 }
 
 impl Eq for TypeData {}
 
+impl PartialOrd for TypeData {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TypeData {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        fn discriminant(kind: &TypeDataKind) -> u8 {
+            match kind {
+                TypeDataKind::Primitive(_) => 0,
+                TypeDataKind::Struct(_) => 1,
+                TypeDataKind::Pointer(_) => 2,
+                TypeDataKind::Array(_) => 3,
+                TypeDataKind::Uninitialized => 4,
+            }
+        }
+
+        match (&self.data, &other.data) {
+            (TypeDataKind::Primitive(a), TypeDataKind::Primitive(b)) => a.cmp(b),
+            (TypeDataKind::Struct(a), TypeDataKind::Struct(b)) => a.guid.cmp(&b.guid),
+            (TypeDataKind::Pointer(a), TypeDataKind::Pointer(b)) => {
+                // Safety: referenced types are kept alive as long as this type is alive
+                unsafe { a.pointee.as_ref().cmp(b.pointee.as_ref()) }
+                    .then(a.mutable.cmp(&b.mutable))
+            }
+            (TypeDataKind::Array(a), TypeDataKind::Array(b)) => {
+                // Safety: referenced types are kept alive as long as this type is alive
+                unsafe { a.element_ty.as_ref().cmp(b.element_ty.as_ref()) }
+            }
+            (a, b) => discriminant(a).cmp(&discriminant(b)),
+        }
+    }
+}
+
 unsafe impl Send for TypeData {}
 unsafe impl Sync for TypeData {}
 
@@ -589,6 +641,39 @@ impl<'t> StructType<'t> {
             store: self.store,
         }
     }
+
+    /// Returns the offsets in bytes of all fields from the start of the
+    /// struct, in declaration order.
+    pub fn field_offsets(&self) -> Vec<usize> {
+        self.fields().iter().map(|field| field.offset()).collect()
+    }
+
+    /// Returns the offset in bytes of the field called `name` from the start of
+    /// the struct, if it exists.
+    pub fn field_offset(&self, name: impl AsRef<str>) -> Option<usize> {
+        self.fields().find_by_name(name).map(|field| field.offset())
+    }
+
+    /// Returns the alignment in bytes of the struct. Like `#[repr(C)]`
+    /// structs, this is the largest alignment of any of its fields.
+    pub fn alignment(&self) -> usize {
+        self.fields()
+            .iter()
+            .map(|field| field.ty().reference_layout().align())
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Returns the size in bytes of the struct, including the tail padding
+    /// required to make the size a multiple of its alignment.
+    pub fn total_size(&self) -> usize {
+        self.fields()
+            .iter()
+            .map(|field| field.offset() + field.ty().reference_layout().size())
+            .max()
+            .unwrap_or(0)
+            .next_multiple_of(self.alignment())
+    }
 }
 
 impl Display for StructType<'_> {
@@ -633,6 +718,13 @@ impl<'t> Fields<'t> {
         self.iter().find(|field| field.name() == field_name)
     }
 
+    /// Returns the index of the field with the given name, or `None` if no
+    /// such field exists.
+    pub fn find_index_by_name(&self, name: impl AsRef<str>) -> Option<usize> {
+        let field_name = name.as_ref();
+        self.iter().position(|field| field.name() == field_name)
+    }
+
     /// Returns an iterator over all fields
     pub fn iter(&self) -> FieldsIterator<'t> {
         FieldsIterator {
@@ -672,7 +764,7 @@ impl<'t> Iterator for FieldsIterator<'t> {
 
 /// A linked version of [`mun_abi::PointerInfo`] that has resolved all
 /// occurrences of `TypeId` with `TypeInfo`.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug)]
 struct PointerData {
     /// The type to which is pointed
     pub pointee: NonNull<TypeData>,
@@ -680,6 +772,22 @@ struct PointerData {
     pub mutable: bool,
 }
 
+impl Hash for PointerData {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Safety: referenced types are kept alive as long as this type is alive
+        unsafe { self.pointee.as_ref() }.hash(state);
+        self.mutable.hash(state);
+    }
+}
+
+impl PartialEq for PointerData {
+    fn eq(&self, other: &Self) -> bool {
+        // Safety: referenced types are kept alive as long as this type is alive
+        self.mutable == other.mutable && unsafe { self.pointee.as_ref() == other.pointee.as_ref() }
+    }
+}
+impl Eq for PointerData {}
+
 /// Reference information of a pointer
 #[derive(Copy, Clone)]
 pub struct PointerType<'t> {
@@ -728,11 +836,26 @@ impl PartialEq for StructData {
 }
 impl Eq for StructData {}
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug)]
 struct ArrayData {
     pub element_ty: NonNull<TypeData>,
 }
 
+impl Hash for ArrayData {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Safety: referenced types are kept alive as long as this type is alive
+        unsafe { self.element_ty.as_ref() }.hash(state);
+    }
+}
+
+impl PartialEq for ArrayData {
+    fn eq(&self, other: &Self) -> bool {
+        // Safety: referenced types are kept alive as long as this type is alive
+        unsafe { self.element_ty.as_ref() == other.element_ty.as_ref() }
+    }
+}
+impl Eq for ArrayData {}
+
 /// Reference information of an array
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -1132,15 +1255,9 @@ impl StructTypeBuilder {
 
     /// Adds a field to the struct
     pub fn add_field(mut self, name: impl Into<String>, ty: Type) -> Self {
-        let field_layout = if ty.is_value_type() {
-            ty.value_layout()
-        } else {
-            Layout::new::<std::ffi::c_void>()
-        };
-
         let (new_layout, offset) = self
             .layout
-            .extend(field_layout)
+            .extend(ty.reference_layout())
             .expect("cannot extend struct layout");
         self.fields.push((name.into(), ty, offset));
         self.layout = new_layout;
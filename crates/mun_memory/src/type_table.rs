@@ -28,6 +28,27 @@ impl TypeTable {
         }
     }
 
+    /// Returns an iterator over all registered types and their GUIDs, in
+    /// arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Guid, &Type)> {
+        self.concrete.iter()
+    }
+
+    /// Returns an iterator over all registered types, in arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &Type> {
+        self.concrete.values()
+    }
+
+    /// Returns the number of registered types.
+    pub fn len(&self) -> usize {
+        self.concrete.len()
+    }
+
+    /// Returns `true` if no types are registered.
+    pub fn is_empty(&self) -> bool {
+        self.concrete.is_empty()
+    }
+
     /// Inserts `type_info` into the type table for a type that has static type
     /// info.
     ///
@@ -79,6 +100,34 @@ impl TypeTable {
         self.type_name_to_type_info.remove(ty.name())
     }
 
+    /// Removes and returns the [`Type`] referenced by the given
+    /// [`abi::TypeId`], if it exists.
+    ///
+    /// Only concrete types are stored in the type table, so pointer and array
+    /// type IDs never match.
+    pub fn remove<'abi>(&mut self, type_id: &'abi abi::TypeId<'abi>) -> Option<Type> {
+        match type_id {
+            abi::TypeId::Concrete(guid) => {
+                let ty = self.concrete.remove(guid)?;
+                self.type_name_to_type_info.remove(ty.name());
+                Some(ty)
+            }
+            abi::TypeId::Pointer(_) | abi::TypeId::Array(_) => None,
+        }
+    }
+
+    /// Removes all types defined in the given module, e.g. when the assembly
+    /// that contains it is unloaded. Types defined elsewhere are unaffected.
+    ///
+    /// Returns the number of types that were removed.
+    pub fn remove_module<'abi>(&mut self, module: &'abi abi::ModuleInfo<'abi>) -> usize {
+        module
+            .types()
+            .iter()
+            .filter(|type_info| self.remove_type_by_type_info(type_info).is_some())
+            .count()
+    }
+
     /// Removes and returns the `TypeInfo` corresponding to `name`, if it
     /// exists.
     pub fn remove_type_by_name<S: AsRef<str>>(&mut self, name: S) -> Option<Type> {
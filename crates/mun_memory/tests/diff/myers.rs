@@ -41,3 +41,35 @@ fn remove2() {
     let diff = myers::compute_diff(&old, &new);
     assert_eq!(apply_myers_diff(&old, diff), new);
 }
+
+#[test]
+fn single_change_in_large_set() {
+    let old: Vec<usize> = (0..1000).collect();
+    let mut new = old.clone();
+    new[500] = 1000;
+
+    let diff = myers::compute_diff(&old, &new);
+    assert_eq!(
+        diff,
+        vec![
+            myers::Diff::Delete {
+                index: 500,
+                ty: 500
+            },
+            myers::Diff::Insert {
+                index: 500,
+                ty: 1000
+            },
+        ]
+    );
+    assert_eq!(myers::compute_full_diff(&old, &new), diff);
+    assert_eq!(apply_myers_diff(&old, diff), new);
+}
+
+#[test]
+fn common_prefix_and_suffix() {
+    let old = vec!["a", "b", "c", "b", "a"];
+    let new = vec!["a", "b", "d", "e", "b", "a"];
+    let diff = myers::compute_diff(&old, &new);
+    assert_eq!(apply_myers_diff(&old, diff), new);
+}
@@ -95,6 +95,33 @@ fn replace() {
     assert_eq_struct(&apply_diff(old, diff), &[struct2]);
 }
 
+#[test]
+fn affects_type() {
+    let type_table = TypeTable::default();
+
+    let struct1 = fake_struct!(type_table, "struct1",
+        "a" => i64, "b" => f64
+    );
+    let struct2 = fake_struct!(type_table, "struct2",
+        "c" => f64, "d" => i64
+    );
+    let struct3 = fake_struct!(type_table, "struct3",
+        "e" => i32
+    );
+
+    let diff = compute_struct_diff(
+        &[struct1.clone(), struct3.clone()],
+        &[struct2.clone(), struct3.clone()],
+    );
+    assert!(diff.iter().any(|d| d.affects_type(&struct1)));
+    assert!(diff.iter().any(|d| d.affects_type(&struct2)));
+    assert!(!diff.iter().any(|d| d.affects_type(&struct3)));
+    assert!(!diff.iter().any(|d| d.affects_type(i64::type_info())));
+
+    let diff = compute_struct_diff(&[struct3.clone()], &[struct3]);
+    assert!(diff.is_empty());
+}
+
 #[test]
 fn swap() {
     let type_table = TypeTable::default();
@@ -528,3 +555,57 @@ fn rename_field2() {
     );
     assert_eq_struct(&apply_diff(old, diff), &[struct2]);
 }
+
+#[test]
+fn explain_field_diff() {
+    let type_table = TypeTable::default();
+
+    let old_struct = fake_struct!(type_table, "Foo",
+        "a" => i64, "b" => f64, "c" => i32, "d" => bool
+    );
+    let new_struct = fake_struct!(type_table, "Foo",
+        "c" => i32, "b" => i64, "e" => i64, "f" => u8
+    );
+
+    let explain = |diff: FieldDiff| diff.explain(&old_struct, &new_struct);
+
+    assert_eq!(
+        explain(FieldDiff::Insert {
+            index: 3,
+            new_type: u8::type_info().clone(),
+        }),
+        "field 'f' of type core::u8 was added to struct 'Foo'"
+    );
+    assert_eq!(
+        explain(FieldDiff::Delete { index: 3 }),
+        "field 'd' was removed from struct 'Foo'"
+    );
+    assert_eq!(
+        explain(FieldDiff::Move {
+            ty: i32::type_info().clone(),
+            old_index: 2,
+            new_index: 0,
+        }),
+        "field 'c' was moved from position 2 to 0 in struct 'Foo'"
+    );
+    assert_eq!(
+        explain(FieldDiff::Edit {
+            old_type: f64::type_info().clone(),
+            new_type: i64::type_info().clone(),
+            old_index: None,
+            new_index: 1,
+            kind: FieldEditKind::ChangedTyped,
+        }),
+        "field 'b' changed type from core::f64 to core::i64"
+    );
+    assert_eq!(
+        explain(FieldDiff::Edit {
+            old_type: i64::type_info().clone(),
+            new_type: i64::type_info().clone(),
+            old_index: Some(0),
+            new_index: 2,
+            kind: FieldEditKind::RenamedField,
+        }),
+        "field 'a' was renamed to 'e' and moved from position 0 to 2"
+    );
+}
@@ -1,8 +1,12 @@
 use std::sync::Arc;
 
+use mun_abi::{ArrayTypeId, PrimitiveType, TypeId};
 use mun_memory::{
-    gc::{Event, GcRootPtr, GcRuntime, MarkSweep},
-    HasStaticType,
+    gc::{
+        self, AllocError, Array, Event, GcPtr, GcRootPtr, GcRuntime, HasIndirectionPtr, MarkSweep,
+        MemoryLayoutError, Stats,
+    },
+    HasStaticType, Type,
 };
 
 use super::util::EventAggregator;
@@ -19,6 +23,48 @@ fn alloc() {
     assert_eq!(events.next(), None);
 }
 
+#[test]
+fn batch_alloc() {
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let handles = runtime.batch_alloc(i64::type_info(), 3);
+
+    assert_eq!(handles.len(), 3);
+    for handle in handles.iter() {
+        assert_eq!(&runtime.ptr_type(*handle), i64::type_info());
+    }
+    assert_eq!(
+        runtime.stats().allocated_memory,
+        3 * std::mem::size_of::<i64>()
+    );
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::BatchAllocation(3)));
+    assert_eq!(events.next(), None);
+
+    // Allocating no objects does not emit an event
+    assert!(runtime.batch_alloc(i64::type_info(), 0).is_empty());
+    assert!(runtime.observer().take_all().is_empty());
+
+    let stats = runtime.collect();
+    assert_eq!(stats.objects_freed, 3);
+    assert_eq!(runtime.stats().allocated_memory, 0);
+}
+
+#[test]
+fn alloc_dyn() {
+    fn alloc_through(runtime: &dyn gc::DynGcRuntime, ty: &Type) -> GcPtr {
+        runtime.alloc(ty)
+    }
+
+    let runtime: Box<dyn gc::DynGcRuntime> =
+        Box::new(MarkSweep::<EventAggregator<Event>>::default());
+    let handle = alloc_through(runtime.as_ref(), i64::type_info());
+    assert_eq!(&runtime.ptr_type(handle), i64::type_info());
+
+    let array = runtime.alloc_array(&i64::type_info().array_type(), 4);
+    assert_eq!(runtime.ptr_type(array), i64::type_info().array_type());
+}
+
 #[test]
 fn collect_simple() {
     let runtime = MarkSweep::<EventAggregator<Event>>::default();
@@ -68,3 +114,276 @@ fn collect_rooted() {
     assert_eq!(events.next(), Some(Event::End));
     assert_eq!(events.next(), None);
 }
+
+#[test]
+fn root_leak_detection() {
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    runtime.assert_no_roots();
+
+    let int = runtime.alloc(i64::type_info());
+    let float = runtime.alloc(f64::type_info());
+    runtime.root(int);
+    runtime.root(int);
+    runtime.root(float);
+
+    assert_eq!(runtime.root_count(), 3);
+    let mut rooted = runtime.rooted_objects();
+    rooted.sort_by_key(|handle| runtime.ptr_type(*handle).name().to_owned());
+    assert_eq!(rooted, [float, int]);
+
+    let message = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        runtime.assert_no_roots();
+    }))
+    .expect_err("rooted objects should be reported")
+    .downcast::<String>()
+    .expect("panic message should be a string");
+    assert!(message.contains("2 object(s) are still rooted (3 roots in total)"));
+    assert!(message.contains("1x core::f64"));
+    assert!(message.contains("1x core::i64"));
+
+    runtime.unroot(int);
+    runtime.unroot(int);
+    runtime.unroot(float);
+    assert_eq!(runtime.root_count(), 0);
+    assert!(runtime.rooted_objects().is_empty());
+    runtime.assert_no_roots();
+}
+
+#[test]
+fn reset() {
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let handles = (0..100)
+        .map(|_| runtime.alloc(i64::type_info()))
+        .collect::<Vec<_>>();
+    runtime.root(handles[0]);
+    let _ = runtime.alloc_array(&i64::type_info().array_type(), 8);
+    assert_ne!(runtime.stats().allocated_memory, 0);
+    runtime.observer().take_all();
+
+    unsafe { runtime.reset() };
+
+    assert_eq!(runtime.stats(), Stats::default());
+    assert_eq!(runtime.root_count(), 0);
+
+    let events = runtime.observer().take_all();
+    assert_eq!(events.len(), 101);
+    for handle in handles {
+        assert!(events.contains(&Event::Deallocation(handle)));
+    }
+
+    // The runtime can still be used after a reset
+    let handle = runtime.alloc(i64::type_info());
+    assert_eq!(runtime.stats().allocated_memory, 8);
+    assert_eq!(&runtime.ptr_type(handle), i64::type_info());
+}
+
+#[test]
+fn move_to_stack() {
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let mut handle = runtime.alloc(i64::type_info());
+    unsafe { *handle.deref_mut::<i64>() = 1234 };
+    let _ = runtime.alloc(i64::type_info());
+    runtime.observer().take_all();
+
+    let mut value = 0i64;
+    unsafe { runtime.move_to_stack(handle, (&mut value as *mut i64).cast()) };
+    assert_eq!(value, 1234);
+
+    // The object is deallocated immediately
+    assert_eq!(runtime.stats().allocated_memory, 8);
+    assert_eq!(
+        runtime.observer().take_all(),
+        vec![Event::Deallocation(handle)]
+    );
+}
+
+#[test]
+fn move_array_to_stack() {
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let array_type = i64::type_info().array_type();
+    let handle = runtime.alloc_array(&array_type, 4).as_raw();
+    let allocated_memory = runtime.stats().allocated_memory;
+
+    let mut value = 0i64;
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| unsafe {
+        runtime.move_to_stack(handle, (&mut value as *mut i64).cast());
+    }));
+    assert!(result.is_err());
+
+    // The array should still be allocated
+    assert_eq!(runtime.stats().allocated_memory, allocated_memory);
+    assert_eq!(runtime.ptr_type(handle), array_type);
+    runtime.collect();
+    assert_eq!(runtime.stats().allocated_memory, 0);
+}
+
+#[test]
+fn null_handle() {
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let handle = runtime.alloc(i64::type_info());
+
+    assert!(GcPtr::null().is_null());
+    assert!(GcPtr::default().is_null());
+    assert!(!handle.is_null());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "cannot root a null handle"))]
+fn root_null_handle() {
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    runtime.root(GcPtr::null());
+}
+
+#[test]
+fn collect_stats() {
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+    runtime.alloc(i32::type_info());
+    runtime.alloc(i32::type_info());
+
+    let stats = runtime.collect();
+    assert_eq!(stats.objects_visited, 1);
+    assert_eq!(stats.objects_freed, 2);
+    assert_eq!(stats.bytes_freed, 2 * std::mem::size_of::<i32>());
+
+    drop(rooted);
+
+    let stats = runtime.collect();
+    assert_eq!(stats.objects_visited, 0);
+    assert_eq!(stats.objects_freed, 1);
+    assert_eq!(stats.bytes_freed, std::mem::size_of::<i64>());
+}
+
+#[test]
+fn try_alloc() {
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let handle = runtime
+        .try_alloc(i64::type_info())
+        .expect("allocation should succeed");
+    assert!(!handle.is_null());
+
+    let array_type = i64::type_info().array_type();
+    let array = runtime
+        .try_alloc_array(&array_type, 4)
+        .expect("allocation should succeed");
+    assert_eq!(array.length(), 4);
+
+    assert!(matches!(
+        runtime.try_alloc_array(&array_type, usize::MAX),
+        Err(AllocError::Layout(MemoryLayoutError::OutOfBounds))
+    ));
+}
+
+#[test]
+fn collection_threshold() {
+    let size = std::mem::size_of::<i64>();
+    let runtime = Arc::new(MarkSweep::with_collection_threshold(
+        EventAggregator::<Event>::default(),
+        2 * size,
+    ));
+    assert_eq!(runtime.collection_threshold(), 2 * size);
+
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+    runtime.alloc(i64::type_info());
+    assert_eq!(runtime.stats().allocated_memory, 2 * size);
+
+    // Exceeding the threshold collects the unrooted object, but not the newly
+    // allocated one
+    let handle = runtime.alloc(i64::type_info());
+    assert_eq!(runtime.stats().allocated_memory, 2 * size);
+    assert_eq!(&runtime.ptr_type(handle), i64::type_info());
+    assert_eq!(&runtime.ptr_type(rooted.handle()), i64::type_info());
+
+    // A batch that exceeds the threshold survives the collection it triggers,
+    // which frees the unrooted `handle`
+    let handles = runtime.batch_alloc(i64::type_info(), 3);
+    assert_eq!(runtime.stats().allocated_memory, 4 * size);
+    for handle in handles {
+        assert_eq!(&runtime.ptr_type(handle), i64::type_info());
+    }
+    runtime.collect();
+    assert_eq!(runtime.stats().allocated_memory, size);
+
+    // Disabling the threshold stops automatic collection
+    runtime.set_collection_threshold(usize::MAX);
+    runtime.alloc(i64::type_info());
+    runtime.alloc(i64::type_info());
+    assert_eq!(runtime.stats().allocated_memory, 3 * size);
+}
+
+#[test]
+fn allocated_for_type() {
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    let _rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+    runtime.alloc(i64::type_info());
+    runtime.alloc(i32::type_info());
+    runtime.alloc_array(&i64::type_info().array_type(), 4);
+
+    let i64_id = TypeId::from(*<i64 as PrimitiveType>::guid());
+    let i32_id = TypeId::from(*<i32 as PrimitiveType>::guid());
+    let f64_id = TypeId::from(*<f64 as PrimitiveType>::guid());
+    let array_id = TypeId::from(ArrayTypeId { element: &i64_id });
+
+    assert_eq!(runtime.allocated_object_count_for_type(&i64_id), 2);
+    assert_eq!(
+        runtime.allocated_bytes_for_type(&i64_id),
+        2 * std::mem::size_of::<i64>()
+    );
+    assert_eq!(runtime.allocated_object_count_for_type(&i32_id), 1);
+    assert_eq!(runtime.allocated_object_count_for_type(&array_id), 1);
+    assert!(runtime.allocated_bytes_for_type(&array_id) >= 4 * std::mem::size_of::<i64>());
+    assert_eq!(runtime.allocated_object_count_for_type(&f64_id), 0);
+    assert_eq!(runtime.allocated_bytes_for_type(&f64_id), 0);
+
+    // Only the rooted object survives a collection
+    runtime.collect();
+    assert_eq!(runtime.allocated_object_count_for_type(&i64_id), 1);
+    assert_eq!(
+        runtime.allocated_bytes_for_type(&i64_id),
+        std::mem::size_of::<i64>()
+    );
+    assert_eq!(runtime.allocated_object_count_for_type(&i32_id), 0);
+    assert_eq!(runtime.allocated_object_count_for_type(&array_id), 0);
+}
+
+#[test]
+fn stats_arithmetic() {
+    let nursery = Stats {
+        allocated_memory: 64,
+        compact_count: 1,
+        bytes_moved: 16,
+    };
+    let tenured = Stats {
+        allocated_memory: 128,
+        compact_count: 2,
+        bytes_moved: 8,
+    };
+
+    let total = nursery.clone() + tenured.clone();
+    assert_eq!(
+        total,
+        Stats {
+            allocated_memory: 192,
+            compact_count: 3,
+            bytes_moved: 24,
+        }
+    );
+    assert_eq!(total.clone() - tenured.clone(), nursery);
+
+    // Subtraction saturates at zero
+    assert_eq!(
+        nursery.clone() - tenured.clone(),
+        Stats {
+            allocated_memory: 0,
+            compact_count: 0,
+            bytes_moved: 8,
+        }
+    );
+
+    let mut stats = Stats::default();
+    stats += nursery.clone();
+    stats += tenured;
+    assert_eq!(stats, total);
+    stats -= total;
+    assert_eq!(stats, Stats::default());
+}
@@ -0,0 +1,149 @@
+use std::{alloc::Layout, sync::Arc};
+
+use mun_memory::{
+    gc::{Array, Event, GcPtr, GcRootPtr, GcRuntime, MarkSweep, TypeTrace},
+    type_table::TypeTable,
+    HasStaticType,
+};
+
+use super::util::EventAggregator;
+use crate::{assert_variant, fake_struct};
+
+#[test]
+fn trace_array() {
+    let mut type_table = TypeTable::default();
+
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64);
+    type_table.insert_type(bar_type_info.clone());
+    let array_type_info = bar_type_info.array_type();
+
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let array = runtime.alloc_array(&array_type_info, 3);
+    let first = runtime.alloc(&bar_type_info);
+    let last = runtime.alloc(&bar_type_info);
+
+    // Leave the middle element null
+    let mut elements = array.elements();
+    unsafe {
+        *elements.next().unwrap().cast::<GcPtr>().as_mut() = first;
+        elements.next();
+        *elements.next().unwrap().cast::<GcPtr>().as_mut() = last;
+    }
+
+    // Trace the array to see if we get both elements back
+    let mut trace = array_type_info.trace(array.as_raw());
+    assert_eq!(trace.next(), Some(first));
+    assert_eq!(trace.next(), Some(last));
+    assert_eq!(trace.next(), None);
+}
+
+#[test]
+fn trace_array_collect() {
+    let mut type_table = TypeTable::default();
+
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64);
+    type_table.insert_type(bar_type_info.clone());
+    let array_type_info = bar_type_info.array_type();
+
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    let array = runtime.alloc_array(&array_type_info, 3);
+    let array_ptr = GcRootPtr::new(&runtime, array.as_raw());
+    let first = runtime.alloc(&bar_type_info);
+    let last = runtime.alloc(&bar_type_info);
+
+    // Only the array is rooted, the elements are kept alive through it
+    let mut elements = array.elements();
+    unsafe {
+        *elements.next().unwrap().cast::<GcPtr>().as_mut() = first;
+        elements.next();
+        *elements.next().unwrap().cast::<GcPtr>().as_mut() = last;
+    }
+
+    // Collect garbage, nothing should be collected since the array is rooted
+    runtime.collect();
+
+    // Drop the array
+    let array_handle = array_ptr.unroot();
+
+    // Collect garbage, the array and its elements should be collected
+    runtime.collect();
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(array_handle)));
+    assert_eq!(events.next(), Some(Event::Allocation(first)));
+    assert_eq!(events.next(), Some(Event::Allocation(last)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_variant!(events.next(), Some(Event::Deallocation(..))); // Don't care about the order
+    assert_variant!(events.next(), Some(Event::Deallocation(..)));
+    assert_variant!(events.next(), Some(Event::Deallocation(..)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
+#[test]
+fn copy_array() {
+    let array_type_info = i64::type_info().array_type();
+
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let array = runtime.alloc_array(&array_type_info, 3);
+    for (idx, element) in array.elements().enumerate() {
+        unsafe { *element.cast::<i64>().as_mut() = idx as i64 };
+    }
+
+    let copy = runtime
+        .array(runtime.copy(array.as_raw()))
+        .expect("copy should be an array");
+    assert_eq!(copy.length(), 3);
+
+    unsafe { *copy.elements().next().unwrap().cast::<i64>().as_mut() = 42 };
+
+    let original_values: Vec<i64> = array
+        .elements()
+        .map(|element| unsafe { *element.cast::<i64>().as_ref() })
+        .collect();
+    let copy_values: Vec<i64> = copy
+        .elements()
+        .map(|element| unsafe { *element.cast::<i64>().as_ref() })
+        .collect();
+    assert_eq!(original_values, vec![0, 1, 2]);
+    assert_eq!(copy_values, vec![42, 1, 2]);
+}
+
+#[test]
+fn compact_array() {
+    let mut type_table = TypeTable::default();
+
+    // The struct is larger than a reference, arrays only store references to it
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64, "b" => i64, "c" => i64);
+    type_table.insert_type(bar_type_info.clone());
+    let array_type_info = bar_type_info.array_type();
+
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    let array = runtime.alloc_array(&array_type_info, 3);
+    let _array_ptr = GcRootPtr::new(&runtime, array.as_raw());
+    let element = runtime.alloc(&bar_type_info);
+
+    for element_ptr in array.elements() {
+        unsafe { *element_ptr.cast::<GcPtr>().as_mut() = element };
+    }
+
+    runtime.compact();
+
+    // The elements should have been copied along with the array
+    let array = runtime.array(array.as_raw()).unwrap();
+    assert_eq!(array.length(), 3);
+    for element_ptr in array.elements() {
+        assert_eq!(unsafe { *element_ptr.cast::<GcPtr>().as_ref() }, element);
+    }
+
+    // The array header is followed by three references
+    let (array_layout, _) = Layout::new::<[usize; 2]>()
+        .extend(Layout::array::<GcPtr>(3).unwrap())
+        .unwrap();
+    assert_eq!(
+        runtime.stats().bytes_moved,
+        array_layout.size() + bar_type_info.value_layout().size()
+    );
+}
@@ -0,0 +1,72 @@
+use mun_memory::{
+    gc::{Event, GcPtr, GcRuntime, MarkSweep},
+    reflect::{self, ReflectError},
+    type_table::TypeTable,
+    HasStaticType,
+};
+
+use super::util::EventAggregator;
+use crate::{assert_variant, fake_struct};
+
+#[test]
+fn read_write_field() {
+    let type_table = TypeTable::default();
+    let foo_type_info = fake_struct!(type_table, "core::Foo", "a" => i32, "b" => f64, "c" => bool);
+
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let foo = runtime.alloc(&foo_type_info);
+
+    // Memory is zero-initialized
+    assert_eq!(reflect::read_field_i32(&runtime, foo, "a").unwrap(), 0);
+
+    reflect::write_field_i32(&runtime, foo, "a", 1234).unwrap();
+    reflect::write_field_f64(&runtime, foo, "b", 3.5).unwrap();
+    reflect::write_field_bool(&runtime, foo, "c", true).unwrap();
+
+    assert_eq!(reflect::read_field_i32(&runtime, foo, "a").unwrap(), 1234);
+    assert_eq!(reflect::read_field_f64(&runtime, foo, "b").unwrap(), 3.5);
+    assert!(reflect::read_field_bool(&runtime, foo, "c").unwrap());
+    assert_eq!(
+        reflect::read_field::<f64, _>(&runtime, foo, "b").unwrap(),
+        3.5
+    );
+}
+
+#[test]
+fn reflect_errors() {
+    let type_table = TypeTable::default();
+    let foo_type_info = fake_struct!(type_table, "core::Foo", "a" => i32);
+
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let foo = runtime.alloc(&foo_type_info);
+
+    assert_variant!(
+        reflect::read_field_i32(&runtime, GcPtr::null(), "a"),
+        Err(ReflectError::NullPointer)
+    );
+    assert_variant!(
+        reflect::read_field_i32(&runtime, foo, "b"),
+        Err(ReflectError::UnknownField { .. })
+    );
+
+    let err = reflect::write_field_f32(&runtime, foo, "a", 1.0).unwrap_err();
+    match err {
+        ReflectError::TypeMismatch {
+            field_name,
+            expected,
+            found,
+        } => {
+            assert_eq!(field_name, "a");
+            assert_eq!(&expected, f32::type_info());
+            assert_eq!(&found, i32::type_info());
+        }
+        _ => panic!("unexpected error: {err}"),
+    }
+
+    // Primitive objects don't have fields
+    let primitive = runtime.alloc(i32::type_info());
+    assert_variant!(
+        reflect::read_field_i32(&runtime, primitive, "a"),
+        Err(ReflectError::UnknownField { .. })
+    );
+}
@@ -120,3 +120,180 @@ fn trace_cycle() {
     assert_eq!(events.next(), Some(Event::End));
     assert_eq!(events.next(), None);
 }
+
+#[test]
+fn compact() {
+    let mut type_table = TypeTable::default();
+
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64);
+    type_table.insert_type(bar_type_info.clone());
+
+    let foo_type_info = fake_struct!(type_table, "core::Foo", "bar" => Bar);
+    type_table.insert_type(foo_type_info.clone());
+
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    let mut foo_ptr = GcRootPtr::new(&runtime, runtime.alloc(&foo_type_info));
+    let mut bar = runtime.alloc(&bar_type_info);
+
+    // Assign bar to foo.bar and give bar a value
+    unsafe {
+        (*foo_ptr.deref_mut::<FooObject>()).bar = bar;
+        *bar.deref_mut::<i64>() = 1234;
+    }
+
+    runtime.collect();
+    runtime.compact();
+
+    // The handles and the values they refer to should be unchanged
+    unsafe {
+        assert_eq!((*foo_ptr.deref::<FooObject>()).bar, bar);
+        assert_eq!(*bar.deref::<i64>(), 1234);
+    }
+
+    let stats = runtime.stats();
+    assert_eq!(stats.compact_count, 1);
+    assert_eq!(
+        stats.bytes_moved,
+        foo_type_info.value_layout().size() + bar_type_info.value_layout().size()
+    );
+}
+
+#[test]
+fn compact_contiguous() {
+    let mut type_table = TypeTable::default();
+
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64);
+    type_table.insert_type(bar_type_info.clone());
+
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+
+    // Interleave live objects with garbage to fragment the heap
+    let mut live = Vec::new();
+    for value in 0..8i64 {
+        let mut bar = GcRootPtr::new(&runtime, runtime.alloc(&bar_type_info));
+        unsafe { *bar.deref_mut::<i64>() = value };
+        live.push(bar);
+        runtime.alloc(&bar_type_info);
+    }
+    runtime.collect();
+    runtime.compact();
+
+    // All live objects should be packed next to each other
+    let size = bar_type_info.value_layout().size();
+    let mut addresses = live
+        .iter()
+        .map(|bar| unsafe { bar.deref::<i64>() } as usize)
+        .collect::<Vec<_>>();
+    addresses.sort_unstable();
+    for window in addresses.windows(2) {
+        assert_eq!(window[1] - window[0], size);
+    }
+
+    for (value, bar) in live.iter().enumerate() {
+        assert_eq!(unsafe { *bar.deref::<i64>() }, value as i64);
+    }
+    assert_eq!(runtime.stats().bytes_moved, live.len() * size);
+
+    // Compacting again moves the objects into a new region and frees the old
+    // one, after which freeing the objects releases the new region
+    runtime.compact();
+    for (value, bar) in live.iter().enumerate() {
+        assert_eq!(unsafe { *bar.deref::<i64>() }, value as i64);
+    }
+    drop(live);
+    runtime.collect();
+    assert_eq!(runtime.stats().allocated_memory, 0);
+}
+
+#[test]
+fn compact_pinned() {
+    let mut type_table = TypeTable::default();
+
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64);
+    type_table.insert_type(bar_type_info.clone());
+
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    let mut pinned = GcRootPtr::new(&runtime, runtime.alloc(&bar_type_info));
+    let mut moved = GcRootPtr::new(&runtime, runtime.alloc(&bar_type_info));
+
+    unsafe {
+        *pinned.deref_mut::<i64>() = 1234;
+        *moved.deref_mut::<i64>() = 5678;
+    }
+
+    // Pin the object twice, pins are reference counted
+    runtime.pin_object(pinned.handle());
+    runtime.pin_object(pinned.handle());
+
+    let pinned_addr = unsafe { pinned.deref::<i64>() };
+    runtime.compact();
+
+    // The pinned object should not have moved
+    unsafe {
+        assert_eq!(pinned.deref::<i64>(), pinned_addr);
+        assert_eq!(*pinned.deref::<i64>(), 1234);
+        assert_eq!(*moved.deref::<i64>(), 5678);
+    }
+    assert_eq!(
+        runtime.stats().bytes_moved,
+        bar_type_info.value_layout().size()
+    );
+
+    // The object is still pinned after a single unpin
+    runtime.unpin_object(pinned.handle());
+    runtime.compact();
+    assert_eq!(unsafe { pinned.deref::<i64>() }, pinned_addr);
+
+    runtime.unpin_object(pinned.handle());
+    runtime.compact();
+    assert_eq!(unsafe { *pinned.deref::<i64>() }, 1234);
+    assert_eq!(
+        runtime.stats().bytes_moved,
+        4 * bar_type_info.value_layout().size()
+    );
+}
+
+#[test]
+fn copy() {
+    #[repr(C)]
+    struct BazObject {
+        a: i64,
+        bar: GcPtr,
+    }
+
+    let mut type_table = TypeTable::default();
+
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64);
+    type_table.insert_type(bar_type_info.clone());
+
+    let baz_type_info = fake_struct!(type_table, "core::Baz", "a" => i64, "bar" => Bar);
+    type_table.insert_type(baz_type_info.clone());
+
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    let mut original = GcRootPtr::new(&runtime, runtime.alloc(&baz_type_info));
+    let bar = runtime.alloc(&bar_type_info);
+    unsafe {
+        (*original.deref_mut::<BazObject>()).a = 1234;
+        (*original.deref_mut::<BazObject>()).bar = bar;
+    }
+
+    let mut copy = GcRootPtr::new(&runtime, runtime.copy(original.handle()));
+    assert_ne!(copy.handle(), original.handle());
+    assert_eq!(&runtime.ptr_type(copy.handle()), &baz_type_info);
+
+    // Modify the copy
+    unsafe {
+        assert_eq!((*copy.deref::<BazObject>()).a, 1234);
+        (*copy.deref_mut::<BazObject>()).a = 5678;
+    }
+
+    unsafe {
+        // The original is unchanged
+        assert_eq!((*original.deref::<BazObject>()).a, 1234);
+        assert_eq!((*copy.deref::<BazObject>()).a, 5678);
+
+        // Referenced objects are not copied
+        assert_eq!((*original.deref::<BazObject>()).bar, bar);
+        assert_eq!((*copy.deref::<BazObject>()).bar, bar);
+    }
+}
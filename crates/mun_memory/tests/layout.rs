@@ -0,0 +1,161 @@
+use std::{
+    alloc::Layout,
+    error::Error,
+    ffi::c_void,
+    mem::{align_of, offset_of, size_of},
+};
+
+use mun_abi::StructMemoryKind;
+use mun_memory::{gc::MemoryLayoutError, HasStaticType, StructTypeBuilder};
+
+#[test]
+fn struct_field_offsets() {
+    #[repr(C)]
+    struct Reference {
+        a: u8,
+        b: u32,
+        c: u16,
+        d: u64,
+        e: bool,
+    }
+
+    let ty = StructTypeBuilder::new("Reference")
+        .add_field("a", u8::type_info().clone())
+        .add_field("b", u32::type_info().clone())
+        .add_field("c", u16::type_info().clone())
+        .add_field("d", u64::type_info().clone())
+        .add_field("e", bool::type_info().clone())
+        .finish();
+    let s = ty.as_struct().expect("expected a struct type");
+
+    assert_eq!(
+        s.field_offsets(),
+        vec![
+            offset_of!(Reference, a),
+            offset_of!(Reference, b),
+            offset_of!(Reference, c),
+            offset_of!(Reference, d),
+            offset_of!(Reference, e),
+        ]
+    );
+    assert_eq!(s.field_offset("d"), Some(offset_of!(Reference, d)));
+    assert_eq!(s.field_offset("f"), None);
+}
+
+#[test]
+fn struct_gc_field_layout() {
+    #[repr(C)]
+    struct Reference {
+        a: u8,
+        b: *const c_void,
+        c: u16,
+    }
+
+    let gc_struct = StructTypeBuilder::new("Gc")
+        .set_memory_kind(StructMemoryKind::Gc)
+        .add_field("a", u8::type_info().clone())
+        .finish();
+
+    let ty = StructTypeBuilder::new("Reference")
+        .set_memory_kind(StructMemoryKind::Value)
+        .add_field("a", u8::type_info().clone())
+        .add_field("b", gc_struct)
+        .add_field("c", u16::type_info().clone())
+        .finish();
+    let s = ty.as_struct().expect("expected a struct type");
+
+    // GC fields are stored as pointers to the referenced object
+    assert_eq!(
+        s.field_offsets(),
+        vec![
+            offset_of!(Reference, a),
+            offset_of!(Reference, b),
+            offset_of!(Reference, c),
+        ]
+    );
+    assert_eq!(ty.value_layout().pad_to_align(), Layout::new::<Reference>());
+}
+
+#[test]
+fn struct_find_field() {
+    let ty = StructTypeBuilder::new("Foo")
+        .add_field("a", u8::type_info().clone())
+        .add_field("b", u32::type_info().clone())
+        .finish();
+    let fields = ty.as_struct().expect("expected a struct type").fields();
+
+    assert_eq!(fields.find_index_by_name("a"), Some(0));
+    assert_eq!(fields.find_index_by_name("b"), Some(1));
+    assert_eq!(fields.find_index_by_name("c"), None);
+    assert_eq!(
+        fields.find_by_name("b").map(|field| field.ty()),
+        Some(u32::type_info().clone())
+    );
+}
+
+#[test]
+fn memory_layout_error() {
+    let layout_error = Layout::from_size_align(1, 3).unwrap_err();
+    let layout_message = layout_error.to_string();
+    let error: Box<dyn Error + Send + Sync> = Box::new(MemoryLayoutError::from(layout_error));
+
+    assert_eq!(error.to_string(), "invalid memory layout");
+    assert_eq!(
+        error.source().map(ToString::to_string),
+        Some(layout_message)
+    );
+    assert!(MemoryLayoutError::OutOfBounds.source().is_none());
+}
+
+#[test]
+fn struct_total_size() {
+    #[repr(C)]
+    struct TailPadding {
+        a: u64,
+        b: u8,
+    }
+
+    #[repr(C)]
+    struct Mixed {
+        a: u8,
+        b: u32,
+        c: u16,
+    }
+
+    #[repr(C)]
+    struct Single {
+        a: u8,
+    }
+
+    #[repr(C)]
+    struct Empty {}
+
+    let tail_padding = StructTypeBuilder::new("TailPadding")
+        .add_field("a", u64::type_info().clone())
+        .add_field("b", u8::type_info().clone())
+        .finish();
+    let mixed = StructTypeBuilder::new("Mixed")
+        .add_field("a", u8::type_info().clone())
+        .add_field("b", u32::type_info().clone())
+        .add_field("c", u16::type_info().clone())
+        .finish();
+    let single = StructTypeBuilder::new("Single")
+        .add_field("a", u8::type_info().clone())
+        .finish();
+    let empty = StructTypeBuilder::new("Empty").finish();
+
+    for (ty, size, align) in [
+        (
+            tail_padding,
+            size_of::<TailPadding>(),
+            align_of::<TailPadding>(),
+        ),
+        (mixed, size_of::<Mixed>(), align_of::<Mixed>()),
+        (single, size_of::<Single>(), align_of::<Single>()),
+        (empty, size_of::<Empty>(), align_of::<Empty>()),
+    ] {
+        let struct_ty = ty.as_struct().unwrap();
+        assert_eq!(struct_ty.total_size(), size, "size of {}", ty.name());
+        assert_eq!(struct_ty.alignment(), align, "alignment of {}", ty.name());
+    }
+}
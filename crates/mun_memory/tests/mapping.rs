@@ -0,0 +1,48 @@
+use mun_memory::{
+    mapping::{Action, Mapping, MappingBuildError, MappingBuilder},
+    HasStaticType, StructTypeBuilder,
+};
+
+#[test]
+fn mapping_builder() {
+    let foo = StructTypeBuilder::new("Foo").finish();
+    let bar = StructTypeBuilder::new("Bar").finish();
+
+    let mapping = MappingBuilder::new()
+        .add_identical(foo.clone(), foo.clone())
+        .add_deletion(bar.clone())
+        .build()
+        .expect("mapping should be valid");
+    assert_eq!(mapping.identical, vec![(foo.clone(), foo.clone())]);
+    assert!(mapping.deletions.contains(&bar));
+    assert!(mapping.struct_mappings.is_empty());
+
+    let result = MappingBuilder::new()
+        .add_identical(foo.clone(), foo.clone())
+        .add_deletion(foo.clone())
+        .build();
+    assert!(matches!(result, Err(MappingBuildError::DuplicateType(ty)) if ty == foo));
+}
+
+#[test]
+fn mapping_zero_init_inserted_field() {
+    let old = StructTypeBuilder::new("Foo")
+        .add_field("a", i64::type_info().clone())
+        .finish();
+    let new = StructTypeBuilder::new("Foo")
+        .add_field("a", i64::type_info().clone())
+        .add_field("b", f32::type_info().clone())
+        .finish();
+
+    let mapping = Mapping::new(&[old.clone()], &[new]);
+    let struct_mapping = mapping
+        .struct_mappings
+        .get(&old)
+        .expect("struct should be mapped");
+    assert_eq!(
+        struct_mapping.field_mapping[1].action,
+        Action::ZeroInit {
+            size: std::mem::size_of::<f32>()
+        }
+    );
+}
@@ -0,0 +1,42 @@
+use std::alloc::Layout;
+
+use mun_abi::{Guid, StructMemoryKind};
+use mun_memory::{HasStaticType, Type};
+
+#[test]
+fn struct_equality_by_guid() {
+    let guid = Guid::from_str("Foo");
+    let foo_v1 = Type::new_struct(
+        "Foo",
+        Layout::new::<i32>(),
+        guid,
+        [("a".to_owned(), i32::type_info().clone(), 0)],
+        StructMemoryKind::Gc,
+    );
+    let foo_v2 = Type::new_struct(
+        "Foo",
+        Layout::new::<(i32, f64)>(),
+        guid,
+        [
+            ("a".to_owned(), i32::type_info().clone(), 0),
+            ("b".to_owned(), f64::type_info().clone(), 8),
+        ],
+        StructMemoryKind::Gc,
+    );
+
+    assert_eq!(foo_v1, foo_v2);
+    assert_eq!(foo_v1.cmp(&foo_v2), std::cmp::Ordering::Equal);
+    assert_eq!(foo_v1.pointer_type(true), foo_v2.pointer_type(true));
+    assert_ne!(foo_v1.pointer_type(true), foo_v2.pointer_type(false));
+    assert_eq!(foo_v1.array_type(), foo_v2.array_type());
+
+    let bar = Type::new_struct(
+        "Bar",
+        Layout::new::<i32>(),
+        Guid::from_str("Bar"),
+        [("a".to_owned(), i32::type_info().clone(), 0)],
+        StructMemoryKind::Gc,
+    );
+    assert_ne!(foo_v1, bar);
+    assert_ne!(foo_v1.cmp(&bar), std::cmp::Ordering::Equal);
+}
@@ -0,0 +1,91 @@
+use std::ffi::CString;
+
+use mun_abi::{
+    self as abi,
+    test_utils::{
+        fake_module_info, fake_struct_definition, fake_type_definition, FAKE_MODULE_PATH,
+    },
+    StructMemoryKind,
+};
+use mun_memory::type_table::TypeTable;
+
+#[macro_use]
+mod util;
+
+#[test]
+fn remove() {
+    let mut type_table = TypeTable::default();
+
+    let foo = fake_struct!(type_table, "core::Foo", "a" => i64);
+    type_table.insert_type(foo.clone());
+    let bar = fake_struct!(type_table, "core::Bar", "a" => f64);
+    type_table.insert_type(bar.clone());
+
+    let foo_id = abi::TypeId::Concrete(*foo.as_concrete().unwrap());
+    assert_eq!(type_table.remove(&foo_id), Some(foo.clone()));
+    assert_eq!(type_table.remove(&foo_id), None);
+
+    // The removed type can no longer be found
+    assert_eq!(type_table.find_type_info_by_id(&foo_id), None);
+    assert_eq!(type_table.find_type_info_by_name(foo.name()), None);
+
+    // Other types are unaffected
+    let bar_id = abi::TypeId::Concrete(*bar.as_concrete().unwrap());
+    assert_eq!(type_table.find_type_info_by_id(&bar_id), Some(bar.clone()));
+    assert_eq!(type_table.find_type_info_by_name(bar.name()), Some(bar));
+}
+
+#[test]
+fn remove_module() {
+    let mut type_table = TypeTable::default();
+
+    let foo = fake_struct!(type_table, "core::Foo", "a" => i64);
+    type_table.insert_type(foo.clone());
+    let bar = fake_struct!(type_table, "core::Bar", "a" => f64);
+    type_table.insert_type(bar.clone());
+
+    // A module that defines `core::Foo`
+    let foo_name = CString::new(foo.name()).unwrap();
+    let mut foo_definition =
+        unsafe { fake_struct_definition(&foo_name, &[], &[], &[], StructMemoryKind::default()) };
+    foo_definition.guid = *foo.as_concrete().unwrap();
+    let types = [fake_type_definition(
+        &foo_name,
+        0,
+        1,
+        abi::TypeDefinitionData::Struct(foo_definition),
+    )];
+    let module_path = CString::new(FAKE_MODULE_PATH).unwrap();
+    let module = fake_module_info(&module_path, &[], &types);
+
+    assert_eq!(type_table.remove_module(&module), 1);
+    assert_eq!(type_table.remove_module(&module), 0);
+    assert_eq!(type_table.find_type_info_by_name(foo.name()), None);
+
+    // Types from other modules are unaffected
+    assert_eq!(type_table.find_type_info_by_name(bar.name()), Some(bar));
+}
+
+#[test]
+fn iter() {
+    let mut type_table = TypeTable::default();
+    let num_primitives = type_table.len();
+    assert!(!type_table.is_empty());
+
+    let foo = fake_struct!(type_table, "core::Foo", "a" => i64);
+    type_table.insert_type(foo.clone());
+    assert_eq!(type_table.len(), num_primitives + 1);
+    assert_eq!(type_table.iter().count(), type_table.len());
+
+    let (guid, ty) = type_table
+        .iter()
+        .find(|(_, ty)| ty.name() == foo.name())
+        .expect("inserted type must be iterated");
+    assert_eq!(guid, foo.as_concrete().unwrap());
+    assert_eq!(ty, &foo);
+    assert!(type_table.values().any(|ty| ty == &foo));
+
+    type_table.remove_type(&foo);
+    assert_eq!(type_table.len(), num_primitives);
+    assert!(!type_table.values().any(|ty| ty == &foo));
+}
@@ -12,7 +12,7 @@ use crate::{
     garbage_collector::GcRootPtr,
     marshal::Marshal,
     reflection::{ArgumentReflection, ReturnTypeReflection},
-    GarbageCollector, Runtime,
+    GarbageCollector, Runtime, RuntimeError,
 };
 
 /// Represents a Mun struct pointer.
@@ -77,7 +77,7 @@ impl<'s> StructRef<'s> {
     pub fn get<T: ReturnTypeReflection + Marshal<'s> + 's>(
         &self,
         field_name: &str,
-    ) -> Result<T, String> {
+    ) -> Result<T, RuntimeError> {
         let type_info = self.type_info();
 
         // Safety: `as_struct` is guaranteed to return `Some` for `StructRef`s.
@@ -86,22 +86,18 @@ impl<'s> StructRef<'s> {
         let field_info = struct_info
             .fields()
             .find_by_name(field_name)
-            .ok_or_else(|| {
-                format!(
-                    "Struct `{}` does not contain field `{}`.",
-                    type_info.name(),
-                    field_name
-                )
+            .ok_or_else(|| RuntimeError::FieldNotFound {
+                struct_name: type_info.name().to_owned(),
+                field_name: field_name.to_owned(),
             })?;
 
         if !T::accepts_type(&field_info.ty()) {
-            return Err(format!(
-                "Mismatched types for `{}::{}`. Expected: `{}`. Found: `{}`.",
-                type_info.name(),
-                field_name,
-                T::type_hint(),
-                field_info.ty().name(),
-            ));
+            return Err(RuntimeError::FieldTypeMismatch {
+                struct_name: type_info.name().to_owned(),
+                field_name: field_name.to_owned(),
+                expected: T::type_hint().to_string(),
+                actual: field_info.ty().name().to_owned(),
+            });
         };
 
         // SAFETY: The offset in the ABI is always valid.
@@ -119,7 +115,7 @@ impl<'s> StructRef<'s> {
         &mut self,
         field_name: &str,
         value: T,
-    ) -> Result<T, String> {
+    ) -> Result<T, RuntimeError> {
         let type_info = self.type_info();
 
         // Safety: `as_struct` is guaranteed to return `Some` for `StructRef`s.
@@ -128,23 +124,19 @@ impl<'s> StructRef<'s> {
         let field_info = struct_info
             .fields()
             .find_by_name(field_name)
-            .ok_or_else(|| {
-                format!(
-                    "Struct `{}` does not contain field `{}`.",
-                    type_info.name(),
-                    field_name
-                )
+            .ok_or_else(|| RuntimeError::FieldNotFound {
+                struct_name: type_info.name().to_owned(),
+                field_name: field_name.to_owned(),
             })?;
 
         let value_type = value.type_info(self.runtime);
         if field_info.ty() != value_type {
-            return Err(format!(
-                "Mismatched types for `{}::{}`. Expected: `{}`. Found: `{}`.",
-                type_info.name(),
-                field_name,
-                value_type.name(),
-                field_info.ty()
-            ));
+            return Err(RuntimeError::FieldTypeMismatch {
+                struct_name: type_info.name().to_owned(),
+                field_name: field_name.to_owned(),
+                expected: value_type.name().to_owned(),
+                actual: field_info.ty().name().to_owned(),
+            });
         }
 
         // SAFETY: The offset in the ABI is always valid.
@@ -159,7 +151,7 @@ impl<'s> StructRef<'s> {
         &mut self,
         field_name: &str,
         value: T,
-    ) -> Result<(), String> {
+    ) -> Result<(), RuntimeError> {
         let type_info = self.type_info();
 
         // Safety: `as_struct` is guaranteed to return `Some` for `StructRef`s.
@@ -168,23 +160,19 @@ impl<'s> StructRef<'s> {
         let field_info = struct_info
             .fields()
             .find_by_name(field_name)
-            .ok_or_else(|| {
-                format!(
-                    "Struct `{}` does not contain field `{}`.",
-                    type_info.name(),
-                    field_name
-                )
+            .ok_or_else(|| RuntimeError::FieldNotFound {
+                struct_name: type_info.name().to_owned(),
+                field_name: field_name.to_owned(),
             })?;
 
         let value_type = value.type_info(self.runtime);
         if field_info.ty() != value_type {
-            return Err(format!(
-                "Mismatched types for `{}::{}`. Expected: `{}`. Found: `{}`.",
-                type_info.name(),
-                field_name,
-                value_type.name(),
-                field_info.ty()
-            ));
+            return Err(RuntimeError::FieldTypeMismatch {
+                struct_name: type_info.name().to_owned(),
+                field_name: field_name.to_owned(),
+                expected: value_type.name().to_owned(),
+                actual: field_info.ty().name().to_owned(),
+            });
         }
 
         // SAFETY: The offset in the ABI is always valid.
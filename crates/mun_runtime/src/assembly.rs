@@ -27,6 +27,8 @@ pub enum LoadError {
     FailedToLoadSharedLibrary(#[from] mun_libloader::InitError),
     #[error("ABI version mismatch. munlib is `{actual}` but runtime is `{expected}`")]
     MismatchedAbiVersions { expected: u32, actual: u32 },
+    #[error("Invalid assembly: {}", .0.iter().join("; "))]
+    InvalidAssembly(Vec<abi::AssemblyError>),
     #[error(transparent)]
     Other(#[from] io::Error),
 }
@@ -83,6 +85,12 @@ pub enum LinkFunctionsError {
         /// Function names for which dependencies were missing
         functions: Vec<String>,
     },
+    /// An assembly defines a function that is provided as an extern function
+    #[error("Cannot redefine extern function `{fn_name}`; extern functions cannot be remapped")]
+    ExternFunctionRedefined {
+        /// Function name
+        fn_name: String,
+    },
 }
 
 /// An assembly is a hot reloadable compilation unit, consisting of one or more
@@ -98,6 +106,9 @@ impl Assembly {
     /// Loads an assembly and its information for the shared library at
     /// `library_path`. The resulting `Assembly` is ensured to be linkable.
     ///
+    /// The assembly's information is validated with
+    /// [`abi::AssemblyInfo::validate`] before the allocator handle is set.
+    ///
     /// # Safety
     ///
     /// A munlib is simply a shared object. When a library is loaded,
@@ -115,19 +126,21 @@ impl Assembly {
     pub unsafe fn load(library_path: &Path, gc: Arc<GarbageCollector>) -> Result<Self, LoadError> {
         let mut library = MunLibrary::new(library_path)?;
 
-        let version = library.get_abi_version();
-        if abi::ABI_VERSION != version {
-            return Err(LoadError::MismatchedAbiVersions {
-                expected: abi::ABI_VERSION,
-                actual: version,
-            });
-        }
+        abi::check_abi_version(library.get_abi_version()).map_err(|e| {
+            LoadError::MismatchedAbiVersions {
+                expected: e.expected,
+                actual: e.actual,
+            }
+        })?;
+
+        let info = library.get_info();
+        info.validate().map_err(LoadError::InvalidAssembly)?;
 
         let allocator_ptr = Arc::into_raw(gc.clone()) as *mut std::ffi::c_void;
         library.set_allocator_handle(allocator_ptr);
 
         let assembly = Assembly {
-            info: library.get_info(),
+            info,
             library_path: library_path.to_path_buf(),
             library: library.into_inner(),
             allocator: gc,
@@ -180,7 +193,7 @@ impl Assembly {
                 // Get the types of the function arguments
                 let fn_proto_arg_type_infos = fn_prototype
                     .signature
-                    .arg_types()
+                    .arg_type_ids()
                     .iter()
                     .enumerate()
                     .map(|(idx, fn_arg_type_id)| {
@@ -196,10 +209,10 @@ impl Assembly {
 
                 // Get the return type info
                 let fn_proto_ret_type_info = type_table
-                    .find_type_info_by_id(&fn_prototype.signature.return_type)
+                    .find_type_info_by_id(fn_prototype.signature.return_type_id())
                     .ok_or_else(|| LinkFunctionsError::UnresolvedResult {
                         fn_name: fn_prototype.name().to_string(),
-                        type_id: fn_prototype.signature.return_type.to_string(),
+                        type_id: fn_prototype.signature.return_type_id().to_string(),
                     })?;
 
                 // Ensure that the function is in the runtime dispatch table
@@ -289,7 +302,7 @@ impl Assembly {
 
         // Insert all assemblies' functions into the dispatch table
         for assembly in assemblies.iter() {
-            dispatch_table.insert_module(&assembly.info().symbols, &type_table);
+            dispatch_table.insert_module(&assembly.info().symbols, &type_table)?;
         }
 
         let functions_to_link = assemblies
@@ -401,7 +414,7 @@ impl Assembly {
             }
 
             // Insert the new assembly's functions into the dispatch table
-            dispatch_table.insert_module(&new_assembly.info.symbols, &type_table);
+            dispatch_table.insert_module(&new_assembly.info.symbols, &type_table)?;
 
             let functions_to_link = new_assembly
                 .info_mut()
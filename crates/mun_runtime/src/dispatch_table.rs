@@ -4,7 +4,7 @@ use mun_abi as abi;
 use mun_memory::type_table::TypeTable;
 use rustc_hash::FxHashMap;
 
-use crate::function_info::FunctionDefinition;
+use crate::{assembly::LinkFunctionsError, function_info::FunctionDefinition};
 
 /// A runtime dispatch table that maps full paths to function and struct
 /// information.
@@ -25,6 +25,16 @@ impl DispatchTable {
         self.functions.keys().map(String::as_str)
     }
 
+    /// Retrieves all available functions.
+    pub fn get_fns(&self) -> impl Iterator<Item = &FunctionDefinition> {
+        self.functions.values().map(AsRef::as_ref)
+    }
+
+    /// Returns whether a function exists for `fn_path`.
+    pub fn contains_fn(&self, fn_path: &str) -> bool {
+        self.functions.contains_key(fn_path)
+    }
+
     /// Inserts the `fn_info` for `fn_path` into the dispatch table.
     ///
     /// If the dispatch table already contained this `fn_path`, the value is
@@ -56,12 +66,29 @@ impl DispatchTable {
 
     /// Add the function definitions from the given assembly from this dispatch
     /// table.
-    pub fn insert_module(&mut self, assembly: &abi::ModuleInfo<'_>, type_table: &TypeTable) {
+    ///
+    /// Returns an error if the assembly defines a function that is already
+    /// provided as an extern function, since those cannot be remapped.
+    pub fn insert_module(
+        &mut self,
+        assembly: &abi::ModuleInfo<'_>,
+        type_table: &TypeTable,
+    ) -> Result<(), LinkFunctionsError> {
         for fn_def in assembly.functions() {
             let fn_def = FunctionDefinition::try_from_abi(fn_def, type_table)
                 .expect("All types from a loaded assembly must exist in the type table.");
 
+            if let Some(existing) = self.functions.get(&fn_def.prototype.name) {
+                if existing.is_extern && existing.fn_ptr != fn_def.fn_ptr {
+                    return Err(LinkFunctionsError::ExternFunctionRedefined {
+                        fn_name: fn_def.prototype.name,
+                    });
+                }
+            }
+
             self.insert_fn(fn_def.prototype.name.clone(), Arc::new(fn_def));
         }
+
+        Ok(())
     }
 }
@@ -11,6 +11,9 @@ pub struct FunctionDefinition {
     pub prototype: FunctionPrototype,
     /// Function pointer
     pub fn_ptr: *const c_void,
+    /// Whether the function is implemented outside of Mun and linked by the
+    /// host
+    pub is_extern: bool,
 }
 
 impl FunctionDefinition {
@@ -21,6 +24,7 @@ impl FunctionDefinition {
             arg_types: vec![],
             return_type: <()>::type_info().clone(),
             fn_ptr: ptr::null(),
+            is_extern: false,
         }
     }
 }
@@ -39,6 +43,7 @@ impl FunctionDefinition {
         Ok(Self {
             prototype,
             fn_ptr: fn_def.fn_ptr,
+            is_extern: fn_def.is_extern,
         })
     }
 }
@@ -85,7 +90,7 @@ impl FunctionSignature {
         type_table: &TypeTable,
     ) -> Result<Self, TryFromAbiError<'abi>> {
         let arg_types: Vec<Type> = fn_sig
-            .arg_types()
+            .arg_type_ids()
             .iter()
             .map(|type_id| {
                 type_table
@@ -95,8 +100,8 @@ impl FunctionSignature {
             .collect::<Result<_, _>>()?;
 
         let return_type = type_table
-            .find_type_info_by_id(&fn_sig.return_type)
-            .ok_or_else(|| TryFromAbiError::UnknownTypeId(fn_sig.return_type.clone()))?;
+            .find_type_info_by_id(fn_sig.return_type_id())
+            .ok_or_else(|| TryFromAbiError::UnknownTypeId(fn_sig.return_type_id().clone()))?;
 
         Ok(Self {
             arg_types,
@@ -128,7 +133,8 @@ macro_rules! into_function_info_impl {
                                 arg_types: vec![$(<$T as mun_memory::HasStaticType>::type_info().clone(),)*],
                                 return_type: <R as mun_memory::HasStaticType>::type_info().clone(),
                             }
-                        }
+                        },
+                        is_extern: true,
                     }
                 }
             }
@@ -157,6 +163,7 @@ pub struct FunctionDefinitionBuilder {
     arg_types: Vec<Type>,
     return_type: Type,
     fn_ptr: *const c_void,
+    is_extern: bool,
 }
 
 impl FunctionDefinitionBuilder {
@@ -186,6 +193,12 @@ impl FunctionDefinitionBuilder {
         self
     }
 
+    /// Sets whether the function is implemented outside of Mun
+    pub fn set_extern(mut self, is_extern: bool) -> Self {
+        self.is_extern = is_extern;
+        self
+    }
+
     /// Construct the [`FunctionDefinition`]
     pub fn finish(self) -> Arc<FunctionDefinition> {
         Arc::new(FunctionDefinition {
@@ -197,6 +210,7 @@ impl FunctionDefinitionBuilder {
                 },
             },
             fn_ptr: self.fn_ptr,
+            is_extern: self.is_extern,
         })
     }
 }
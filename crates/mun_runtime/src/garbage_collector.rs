@@ -1,6 +1,31 @@
 use mun_memory::gc;
 
 /// Defines the garbage collector used by the `Runtime`.
-pub type GarbageCollector = gc::MarkSweep<gc::NoopObserver<gc::Event>>;
+pub type GarbageCollector = gc::MarkSweep<GcObserver>;
 
 pub type GcRootPtr = gc::GcRootPtr<GarbageCollector>;
+
+/// The observer of the `Runtime`'s garbage collector, which forwards all events
+/// to the observer set through [`crate::RuntimeBuilder::with_gc_observer`], if
+/// any.
+#[derive(Default)]
+pub struct GcObserver {
+    observer: Option<Box<dyn gc::Observer<Event = gc::Event>>>,
+}
+
+impl GcObserver {
+    /// Constructs a `GcObserver` that forwards events to `observer`.
+    pub fn new(observer: Option<Box<dyn gc::Observer<Event = gc::Event>>>) -> Self {
+        Self { observer }
+    }
+}
+
+impl gc::Observer for GcObserver {
+    type Event = gc::Event;
+
+    fn event(&self, event: gc::Event) {
+        if let Some(observer) = &self.observer {
+            observer.event(event);
+        }
+    }
+}
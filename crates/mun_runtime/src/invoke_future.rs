@@ -0,0 +1,114 @@
+use std::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use log::error;
+
+use crate::{InvokeArgs, Marshal, ReturnTypeReflection, Runtime};
+
+/// The interval at which an [`InvokeFuture`] checks for updates of the
+/// runtime after a failed invocation.
+const RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A future that invokes a Mun function, retrying the invocation whenever the
+/// runtime has been updated until it succeeds. Created by
+/// [`Runtime::invoke_async`].
+///
+/// In between retries the future yields to the executor using
+/// [`tokio::time::sleep`], so it must be polled from within a Tokio runtime.
+pub struct InvokeFuture<'runtime, 'name, ReturnType, ArgTypes> {
+    /// A raw pointer to the runtime instead of a mutable reference, so the
+    /// output can borrow the runtime once the invocation succeeds.
+    runtime: *mut Runtime,
+    function_name: &'name str,
+    arguments: Option<ArgTypes>,
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    _runtime: PhantomData<&'runtime mut Runtime>,
+    _return_type: PhantomData<fn() -> ReturnType>,
+}
+
+// Safety: The future logically holds a `&'runtime mut Runtime`, which is `Send`
+// because the `Runtime` is `Send`. All other retry state is owned.
+unsafe impl<ReturnType, ArgTypes: Send> Send for InvokeFuture<'_, '_, ReturnType, ArgTypes> {}
+
+// Safety: None of the fields are structurally pinned; the sleep future is boxed.
+impl<ReturnType, ArgTypes> Unpin for InvokeFuture<'_, '_, ReturnType, ArgTypes> {}
+
+impl<'runtime, ReturnType, ArgTypes> Future for InvokeFuture<'runtime, '_, ReturnType, ArgTypes>
+where
+    ReturnType: ReturnTypeReflection + Marshal<'runtime> + 'runtime,
+    ArgTypes: InvokeArgs,
+{
+    type Output = ReturnType;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            if let Some(sleep) = this.sleep.as_mut() {
+                if sleep.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+
+                // Safety: The future holds the only borrow of the runtime
+                // until the invocation succeeds.
+                if !unsafe { (*this.runtime).update() } {
+                    sleep
+                        .as_mut()
+                        .reset(tokio::time::Instant::now() + RETRY_INTERVAL);
+                    continue;
+                }
+                this.sleep = None;
+            }
+
+            let arguments = this
+                .arguments
+                .take()
+                .expect("`InvokeFuture` polled after completion");
+
+            // Safety: The returned value only contains a shared borrow of the
+            // runtime, after which the future is complete and no longer
+            // mutably accesses the runtime.
+            let runtime: &'runtime Runtime = unsafe { &*this.runtime };
+            match runtime.invoke(this.function_name, arguments) {
+                Ok(output) => return Poll::Ready(output),
+                Err(e) => {
+                    error!("{}", e.error);
+                    this.arguments = Some(e.arguments);
+                    this.sleep = Some(Box::pin(tokio::time::sleep(RETRY_INTERVAL)));
+                }
+            }
+        }
+    }
+}
+
+impl Runtime {
+    /// Invokes the Mun function called `function_name` with the specified
+    /// `arguments`, returning a future that resolves once the invocation
+    /// succeeds.
+    ///
+    /// Unlike [`InvokeErr::wait`](crate::InvokeErr::wait), which blocks the
+    /// calling thread until the runtime has been updated, the future
+    /// asynchronously waits in between checking for updates.
+    pub fn invoke_async<'runtime, 'name, ReturnType, ArgTypes>(
+        &'runtime mut self,
+        function_name: &'name str,
+        arguments: ArgTypes,
+    ) -> InvokeFuture<'runtime, 'name, ReturnType, ArgTypes>
+    where
+        ReturnType: ReturnTypeReflection + Marshal<'runtime> + 'runtime,
+        ArgTypes: InvokeArgs,
+    {
+        InvokeFuture {
+            runtime: self,
+            function_name,
+            arguments: Some(arguments),
+            sleep: None,
+            _runtime: PhantomData,
+            _return_type: PhantomData,
+        }
+    }
+}
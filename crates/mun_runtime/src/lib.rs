@@ -11,13 +11,15 @@ mod adt;
 mod array;
 mod dispatch_table;
 mod function_info;
+#[cfg(feature = "async")]
+mod invoke_future;
 mod marshal;
 mod reflection;
 mod utils;
 
 use std::{
     cmp,
-    collections::{BTreeMap, HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     ffi,
     ffi::c_void,
     fmt::{Debug, Display, Formatter},
@@ -28,11 +30,13 @@ use std::{
         mpsc::{channel, Receiver},
         Arc,
     },
+    time::{Duration, Instant},
 };
 
 use assembly::LoadError;
 use dispatch_table::DispatchTable;
-use garbage_collector::GarbageCollector;
+use garbage_collector::{GarbageCollector, GcObserver};
+use itertools::Itertools;
 use log::{debug, error, info};
 use mun_abi as abi;
 use mun_memory::{
@@ -44,6 +48,8 @@ pub use mun_memory::{Field, FieldData, HasStaticType, PointerType, StructType, T
 use mun_project::LOCKFILE_NAME;
 use notify::{event::ModifyKind, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 
+#[cfg(feature = "async")]
+pub use crate::invoke_future::InvokeFuture;
 pub use crate::{
     adt::{RootedStruct, StructRef},
     array::{ArrayRef, RawArray, RootedArray},
@@ -63,6 +69,12 @@ pub struct RuntimeOptions {
     pub type_table: TypeTable,
     /// Custom user injected functions
     pub user_functions: Vec<FunctionDefinition>,
+    /// The interval at which the file watcher polls for changes, if it falls
+    /// back to polling. When `None`, the watcher's default is used. This has no
+    /// effect on native watchers, such as inotify on Linux.
+    pub watch_interval: Option<Duration>,
+    /// An observer that receives all events of the garbage collector
+    pub gc_observer: Option<Box<dyn gc::Observer<Event = gc::Event>>>,
 }
 
 /// Retrieve the allocator using the provided handle.
@@ -140,10 +152,36 @@ impl RuntimeBuilder {
                 library_path: library_path.into(),
                 type_table: TypeTable::default(),
                 user_functions: Vec::default(),
+                watch_interval: None,
+                gc_observer: None,
             },
         }
     }
 
+    /// Sets the path of the shared library that is loaded on construction.
+    pub fn library_path<P: Into<PathBuf>>(mut self, library_path: P) -> Self {
+        self.options.library_path = library_path.into();
+        self
+    }
+
+    /// Sets the interval at which the file watcher polls for changes, in case
+    /// the platform's native file notifications are unavailable. Native
+    /// watchers, such as inotify on Linux, ignore this interval.
+    pub fn watch_interval(mut self, interval: Duration) -> Self {
+        self.options.watch_interval = Some(interval);
+        self
+    }
+
+    /// Sets an observer that receives all events of the garbage collector, e.g.
+    /// to track allocations.
+    pub fn with_gc_observer<O: gc::Observer<Event = gc::Event> + 'static>(
+        mut self,
+        observer: O,
+    ) -> Self {
+        self.options.gc_observer = Some(Box::new(observer));
+        self
+    }
+
     /// Adds a custom user function to the dispatch table.
     pub fn insert_fn<S: Into<String>, F: IntoFunctionDefinition>(
         mut self,
@@ -170,9 +208,19 @@ impl RuntimeBuilder {
     /// unloaded.
     ///
     /// See [`Assembly::load`] for more information.
-    pub unsafe fn finish(self) -> Result<Runtime, InitError> {
+    pub unsafe fn build(self) -> Result<Runtime, InitError> {
         Runtime::new(self.options)
     }
+
+    /// Constructs a [`Runtime`] with the builder's options. This is equivalent
+    /// to [`RuntimeBuilder::build`].
+    ///
+    /// # Safety
+    ///
+    /// See [`RuntimeBuilder::build`].
+    pub unsafe fn finish(self) -> Result<Runtime, InitError> {
+        self.build()
+    }
 }
 
 /// An error that occurs upon construction of a [`Runtime`].
@@ -186,6 +234,76 @@ pub enum InitError {
     Watcher(#[from] notify::Error),
 }
 
+/// An error that occurs when watching or unwatching a directory.
+#[derive(Debug, thiserror::Error)]
+pub enum WatchError {
+    /// The directory is not being watched
+    #[error("directory '{}' is not being watched", .0.display())]
+    NotWatched(PathBuf),
+    /// Failed to resolve the path of the directory
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Failed to update the watcher
+    #[error(transparent)]
+    Watcher(#[from] notify::Error),
+}
+
+/// An error that occurs when a function invocation did not succeed before a
+/// deadline.
+#[derive(Debug, thiserror::Error)]
+#[error("invoking function `{function_name}` did not succeed within {timeout:?}")]
+pub struct TimeoutError {
+    /// The name of the function that was invoked
+    pub function_name: String,
+    /// The time that was spent retrying
+    pub timeout: Duration,
+}
+
+/// An error that occurs when interacting with a [`Runtime`].
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum RuntimeError {
+    /// The requested function does not exist
+    #[error(
+        "failed to obtain function '{name}', no such function exists.{}",
+        .suggestion.as_ref().map(|suggestion| format!(" There is a function with a similar name: {suggestion}")).unwrap_or_default()
+    )]
+    FunctionNotFound {
+        /// The name of the requested function
+        name: String,
+        /// The name of an existing function with a similar name, if any
+        suggestion: Option<String>,
+    },
+    /// The signature of a function does not match the provided arguments or
+    /// return type
+    #[error("mismatched function signature. Expected: `{expected}`. Found: `{actual}`.")]
+    SignatureMismatch {
+        /// The signature of the function
+        expected: String,
+        /// The signature that was provided
+        actual: String,
+    },
+    /// The requested struct field does not exist
+    #[error("Struct `{struct_name}` does not contain field `{field_name}`.")]
+    FieldNotFound {
+        /// The name of the struct
+        struct_name: String,
+        /// The name of the requested field
+        field_name: String,
+    },
+    /// The type of a struct field does not match the provided type
+    #[error("Mismatched types for `{struct_name}::{field_name}`. Expected: `{expected}`. Found: `{actual}`.")]
+    FieldTypeMismatch {
+        /// The name of the struct
+        struct_name: String,
+        /// The name of the field
+        field_name: String,
+        /// The type that was requested or provided
+        expected: String,
+        /// The type of the field
+        actual: String,
+    },
+}
+
 /// A runtime for the Mun language.
 ///
 /// # Logging
@@ -205,6 +323,8 @@ pub struct Runtime {
     type_table: TypeTable,
     watcher: RecommendedWatcher,
     watcher_rx: Receiver<notify::Result<Event>>,
+    watched_directories: HashSet<PathBuf>,
+    watch_interval: Duration,
     renamed_files: HashMap<usize, PathBuf>,
     gc: Arc<GarbageCollector>,
 }
@@ -260,9 +380,21 @@ impl Runtime {
             dispatch_table.insert_fn(fn_def.prototype.name.clone(), Arc::new(fn_def));
         });
 
-        let watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
-            tx.send(res).expect("Failed to send filesystem event.");
-        })?;
+        let watcher_config = options
+            .watch_interval
+            .map_or_else(notify::Config::default, |interval| {
+                notify::Config::default().with_poll_interval(interval)
+            });
+        let watch_interval = watcher_config.poll_interval();
+        let watcher = RecommendedWatcher::new(
+            move |res| {
+                tx.send(res).expect("Failed to send filesystem event.");
+            },
+            watcher_config,
+        )?;
+
+        let gc = GarbageCollector::with_observer(GcObserver::new(options.gc_observer));
+
         let mut runtime = Runtime {
             assemblies: HashMap::new(),
             assemblies_to_relink: BTreeMap::new(),
@@ -270,15 +402,19 @@ impl Runtime {
             type_table,
             watcher,
             watcher_rx: rx,
+            watched_directories: HashSet::new(),
+            watch_interval,
             renamed_files: HashMap::new(),
-            gc: Arc::new(self::garbage_collector::GarbageCollector::default()),
+            gc: Arc::new(gc),
         };
 
         runtime.add_assembly(&options.library_path)?;
         Ok(runtime)
     }
 
-    /// Adds an assembly corresponding to the library at `library_path`.
+    /// Adds an assembly corresponding to the library at `library_path`, and its
+    /// dependencies. The directory of each loaded assembly is watched for
+    /// changes, so assemblies can be hot reloaded from multiple directories.
     ///
     /// # Safety
     ///
@@ -294,7 +430,7 @@ impl Runtime {
     /// unloaded.
     ///
     /// See [`Assembly::load`] for more information.
-    unsafe fn add_assembly(&mut self, library_path: &Path) -> Result<(), LinkError> {
+    pub unsafe fn add_assembly(&mut self, library_path: &Path) -> Result<(), LinkError> {
         let library_path = library_path
             .canonicalize()
             .map_err(|e| LinkError::LoadAssembly(LoadError::Other(e)))?;
@@ -340,8 +476,7 @@ impl Runtime {
             Assembly::link_all(loaded.values_mut(), &self.dispatch_table, &self.type_table)?;
 
         for (library_path, assembly) in loaded {
-            self.watcher
-                .watch(library_path.parent().unwrap(), RecursiveMode::NonRecursive)
+            self.watch_directory(library_path.parent().unwrap())
                 .expect("Path must exist as we just loaded the library");
 
             self.assemblies.insert(library_path, assembly);
@@ -350,6 +485,44 @@ impl Runtime {
         Ok(())
     }
 
+    /// Starts watching the directory at `path` for changes. Changes to
+    /// assemblies in any of the watched directories are processed by
+    /// [`Runtime::update`].
+    ///
+    /// The directories of all loaded assemblies are watched automatically.
+    /// Watching a directory that is already being watched has no effect.
+    pub fn watch_directory(&mut self, path: &Path) -> Result<(), WatchError> {
+        let path = path.canonicalize()?;
+        if self.watched_directories.contains(&path) {
+            return Ok(());
+        }
+
+        self.watcher.watch(&path, RecursiveMode::NonRecursive)?;
+        self.watched_directories.insert(path);
+        Ok(())
+    }
+
+    /// Stops watching the directory at `path` for changes.
+    ///
+    /// Note that this also stops hot reloading of assemblies that are located
+    /// in the directory.
+    pub fn unwatch_directory(&mut self, path: &Path) -> Result<(), WatchError> {
+        let path = path.canonicalize()?;
+        if !self.watched_directories.contains(&path) {
+            return Err(WatchError::NotWatched(path));
+        }
+
+        self.watcher.unwatch(&path)?;
+        self.watched_directories.remove(&path);
+        Ok(())
+    }
+
+    /// Returns an iterator over all directories that are being watched for
+    /// changes.
+    pub fn watched_directories(&self) -> impl Iterator<Item = &Path> {
+        self.watched_directories.iter().map(PathBuf::as_path)
+    }
+
     /// Retrieves the function definition corresponding to `function_name`, if
     /// available.
     pub fn get_function_definition(&self, function_name: &str) -> Option<Arc<FunctionDefinition>> {
@@ -358,6 +531,24 @@ impl Runtime {
         self.dispatch_table.get_fn(function_name)
     }
 
+    /// Returns an iterator over the names of all functions that are available
+    /// in the runtime, across all loaded assemblies.
+    pub fn function_names(&self) -> impl Iterator<Item = &str> {
+        self.dispatch_table.get_fn_names()
+    }
+
+    /// Returns an iterator over the definitions of all functions that are
+    /// available in the runtime, across all loaded assemblies.
+    pub fn functions(&self) -> impl Iterator<Item = &FunctionDefinition> {
+        self.dispatch_table.get_fns()
+    }
+
+    /// Returns whether a function called `function_name` is available in the
+    /// runtime.
+    pub fn has_function(&self, function_name: &str) -> bool {
+        self.dispatch_table.contains_fn(function_name)
+    }
+
     /// For a given `fn_name`, find the most similar name in `fn_names`
     fn find_best_match_for_fn_name<'a>(
         fn_name: &'a str,
@@ -415,60 +606,6 @@ impl Runtime {
             path.file_name().expect("Invalid file path.") == LOCKFILE_NAME
         }
 
-        unsafe fn relink_assemblies(
-            runtime: &mut Runtime,
-        ) -> Result<(DispatchTable, TypeTable), LinkError> {
-            let mut loaded = HashMap::new();
-            let to_load = &mut runtime.assemblies_to_relink;
-
-            info!("Relinking assemblies:");
-            for (old_path, new_path) in to_load.iter() {
-                info!(
-                    "{} -> {}",
-                    old_path.to_string_lossy(),
-                    new_path.to_string_lossy()
-                );
-            }
-
-            // Load all assemblies and their dependencies
-            while let Some((old_path, new_path)) = to_load.pop_first() {
-                // A dependency can be added by multiple dependants, so check that we didn't
-                // load it yet
-                if loaded.contains_key(&old_path) {
-                    continue;
-                }
-
-                let assembly = Assembly::load(&new_path, runtime.gc.clone())?;
-
-                let parent = new_path.parent().expect("Invalid library path");
-                let extension = new_path.extension();
-
-                let dependencies: Vec<String> =
-                    assembly.info().dependencies().map(From::from).collect();
-                loaded.insert(old_path.clone(), assembly);
-
-                for dependency in dependencies {
-                    let mut library_path = parent.join(dependency);
-                    if let Some(extension) = extension {
-                        library_path = library_path.with_extension(extension);
-                    }
-
-                    if !loaded.contains_key(&library_path)
-                        && !runtime.assemblies.contains_key(&library_path)
-                    {
-                        to_load.insert(old_path.clone(), library_path);
-                    }
-                }
-            }
-
-            Assembly::relink_all(
-                &mut loaded,
-                &mut runtime.assemblies,
-                &runtime.dispatch_table,
-                &runtime.type_table,
-            )
-        }
-
         let mut requires_relink = false;
         while let Ok(Ok(event)) = self.watcher_rx.try_recv() {
             for path in event.paths {
@@ -511,14 +648,9 @@ impl Runtime {
             if self.assemblies_to_relink.is_empty() {
                 debug!("The compiler didn't write a munlib.");
             } else {
-                match relink_assemblies(self) {
-                    Ok((dispatch_table, type_table)) => {
+                match self.relink_assemblies() {
+                    Ok(()) => {
                         info!("Succesfully reloaded assemblies.");
-
-                        self.dispatch_table = dispatch_table;
-                        self.type_table = type_table;
-                        self.assemblies_to_relink.clear();
-
                         return true;
                     }
                     Err(e) => error!("Failed to relink assemblies: {e}"),
@@ -529,6 +661,100 @@ impl Runtime {
         false
     }
 
+    /// Unconditionally reloads all loaded assemblies from disk, regardless of
+    /// whether they changed, and returns the number of reloaded assemblies.
+    ///
+    /// This uses the same code path as [`Runtime::update`], so existing memory
+    /// is mapped to the reloaded types.
+    ///
+    /// # Safety
+    ///
+    /// A munlib is simply a shared object. When a library is loaded,
+    /// initialisation routines contained within it are executed. For the
+    /// purposes of safety, the execution of these routines is conceptually
+    /// the same calling an unknown foreign function and may impose
+    /// arbitrary requirements on the caller for the call to be sound.
+    ///
+    /// Additionally, the callers of this function must also ensure that
+    /// execution of the termination routines contained within the library
+    /// is safe as well. These routines may be executed when the library is
+    /// unloaded.
+    ///
+    /// See [`Assembly::load`] for more information.
+    pub unsafe fn reload_forced(&mut self) -> Result<usize, LinkError> {
+        let num_assemblies = self.assemblies.len();
+        for path in self.assemblies.keys() {
+            self.assemblies_to_relink.insert(path.clone(), path.clone());
+        }
+
+        self.relink_assemblies()?;
+        info!("Succesfully force reloaded {num_assemblies} assemblies.");
+
+        Ok(num_assemblies)
+    }
+
+    /// Loads all assemblies that are queued to be relinked, and relinks them
+    /// with the remaining assemblies. On success, the runtime's dispatch table
+    /// and type table are replaced, and the queue is cleared.
+    ///
+    /// # Safety
+    ///
+    /// See [`Runtime::update`].
+    unsafe fn relink_assemblies(&mut self) -> Result<(), LinkError> {
+        let mut loaded = HashMap::new();
+        let to_load = &mut self.assemblies_to_relink;
+
+        info!("Relinking assemblies:");
+        for (old_path, new_path) in to_load.iter() {
+            info!(
+                "{} -> {}",
+                old_path.to_string_lossy(),
+                new_path.to_string_lossy()
+            );
+        }
+
+        // Load all assemblies and their dependencies
+        while let Some((old_path, new_path)) = to_load.pop_first() {
+            // A dependency can be added by multiple dependants, so check that we didn't
+            // load it yet
+            if loaded.contains_key(&old_path) {
+                continue;
+            }
+
+            let assembly = Assembly::load(&new_path, self.gc.clone())?;
+
+            let parent = new_path.parent().expect("Invalid library path");
+            let extension = new_path.extension();
+
+            let dependencies: Vec<String> =
+                assembly.info().dependencies().map(From::from).collect();
+            loaded.insert(old_path.clone(), assembly);
+
+            for dependency in dependencies {
+                let mut library_path = parent.join(dependency);
+                if let Some(extension) = extension {
+                    library_path = library_path.with_extension(extension);
+                }
+
+                if !loaded.contains_key(&library_path)
+                    && !self.assemblies.contains_key(&library_path)
+                {
+                    to_load.insert(old_path.clone(), library_path);
+                }
+            }
+        }
+
+        (self.dispatch_table, self.type_table) = Assembly::relink_all(
+            &mut loaded,
+            &mut self.assemblies,
+            &self.dispatch_table,
+            &self.type_table,
+        )?;
+        self.assemblies_to_relink.clear();
+
+        Ok(())
+    }
+
     /// Returns a shared reference to the runtime's garbage collector.
     ///
     /// We cannot return an `Arc` here, because the lifetime of data contained
@@ -537,10 +763,16 @@ impl Runtime {
         self.gc.as_ref()
     }
 
+    /// Returns the interval at which the file watcher polls for changes, in
+    /// case the platform's native file notifications are unavailable. Native
+    /// watchers, such as inotify on Linux, ignore this interval.
+    pub fn watch_interval(&self) -> Duration {
+        self.watch_interval
+    }
+
     /// Collects all memory that is no longer referenced by rooted objects.
-    /// Returns `true` if memory was reclaimed, `false` otherwise. This
-    /// behavior will likely change in the future.
-    pub fn gc_collect(&self) -> bool {
+    /// Returns statistics about the collection.
+    pub fn gc_collect(&self) -> gc::CollectionStats {
         self.gc.collect()
     }
 
@@ -653,20 +885,33 @@ impl Runtime {
 
 /// An error that might occur when calling a mun function from Rust.
 pub struct InvokeErr<'name, T> {
-    msg: String,
+    error: RuntimeError,
     function_name: &'name str,
     arguments: T,
 }
 
 impl<T> Debug for InvokeErr<'_, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", &self.msg)
+        write!(f, "{}", &self.error)
     }
 }
 
 impl<T> Display for InvokeErr<'_, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", &self.msg)
+        write!(f, "{}", &self.error)
+    }
+}
+
+impl<T> std::error::Error for InvokeErr<'_, T> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&self.error)
+    }
+}
+
+impl<T> InvokeErr<'_, T> {
+    /// Returns the error that caused the invocation to fail.
+    pub fn error(&self) -> &RuntimeError {
+        &self.error
     }
 }
 
@@ -702,6 +947,58 @@ impl<T: InvokeArgs> InvokeErr<'_, T> {
         }
     }
 
+    /// Retries the function invocation until it succeeds or until `timeout`
+    /// has passed, in which case a [`TimeoutError`] is returned.
+    pub fn retry_with_timeout<'r, 'o, Output>(
+        mut self,
+        runtime: &'r mut Runtime,
+        timeout: Duration,
+    ) -> Result<Output, TimeoutError>
+    where
+        Output: 'o + ReturnTypeReflection + Marshal<'o>,
+        'r: 'o,
+    {
+        let deadline = Instant::now() + timeout;
+        loop {
+            // Safety: `runtime` is a mutable reference, so it is valid to dereference.
+            if !unsafe { Self::wait_for_update(runtime, Some(deadline)) } {
+                return Err(TimeoutError {
+                    function_name: self.function_name.to_owned(),
+                    timeout,
+                });
+            }
+
+            // Safety: The output of `invoke_impl` is guaranteed to only contain a shared
+            // reference.
+            self = match unsafe { self.invoke_impl(runtime) } {
+                Ok(output) => return Ok(output),
+                Err(e) => e,
+            };
+        }
+    }
+
+    /// Retries the function invocation at most `n` times, resulting in a
+    /// potentially successful invocation.
+    pub fn retry_n_times<'r, 'o, Output>(
+        mut self,
+        runtime: &'r mut Runtime,
+        n: u32,
+    ) -> Result<Output, Self>
+    where
+        Output: 'o + ReturnTypeReflection + Marshal<'o>,
+        'r: 'o,
+    {
+        for _ in 0..n {
+            // Safety: The output of `retry_impl` is guaranteed to only contain a shared
+            // reference.
+            self = match unsafe { self.retry_impl(runtime) } {
+                Ok(output) => return Ok(output),
+                Err(e) => e,
+            };
+        }
+        Err(self)
+    }
+
     /// Inner implementation that retries a function invocation once, resulting
     /// in a potentially successful invocation. This is a workaround for:
     /// <https://doc.rust-lang.org/nomicon/lifetime-mismatch.html>
@@ -715,13 +1012,43 @@ impl<T: InvokeArgs> InvokeErr<'_, T> {
     where
         Output: 'o + ReturnTypeReflection + Marshal<'o>,
     {
+        eprintln!("{}", self.error);
+        Self::wait_for_update(runtime, None);
+        self.invoke_impl(runtime)
+    }
+
+    /// Waits until the runtime has been updated, which might fix the error.
+    /// Returns `false` if the `deadline` passed before that happened.
+    ///
+    /// # Safety
+    ///
+    /// When calling this function, you have to guarantee that `runtime` can be
+    /// dereferenced.
+    unsafe fn wait_for_update(runtime: *mut Runtime, deadline: Option<Instant>) -> bool {
         // Safety: Guaranteed by the caller to be valid to dereference.
         let runtime = &mut *runtime;
 
-        eprintln!("{}", self.msg);
         while !runtime.update() {
-            // Wait until there has been an update that might fix the error
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return false;
+            }
         }
+        true
+    }
+
+    /// Invokes the function once.
+    ///
+    /// # Safety
+    ///
+    /// When calling this function, you have to guarantee that `runtime` can be
+    /// dereferenced and is valid for `'o`. The `Output` value can only
+    /// contain a shared borrow of `runtime`.
+    unsafe fn invoke_impl<'o, Output>(self, runtime: *mut Runtime) -> Result<Output, Self>
+    where
+        Output: 'o + ReturnTypeReflection + Marshal<'o>,
+    {
+        // Safety: Guaranteed by the caller to be valid to dereference.
+        let runtime = &mut *runtime;
 
         runtime.invoke(self.function_name, self.arguments)
     }
@@ -732,7 +1059,11 @@ impl<T: InvokeArgs> InvokeErr<'_, T> {
 pub trait InvokeArgs {
     /// Determines whether the specified function can be called with these
     /// arguments
-    fn can_invoke(&self, runtime: &Runtime, signature: &FunctionSignature) -> Result<(), String>;
+    fn can_invoke(
+        &self,
+        runtime: &Runtime,
+        signature: &FunctionSignature,
+    ) -> Result<(), RuntimeError>;
 
     /// Calls the specified function with these function arguments
     ///
@@ -749,25 +1080,17 @@ seq_macro::seq!(I in 0..N {
     #[allow(clippy::extra_unused_lifetimes)]
     impl<'arg, #(T~I: ArgumentReflection + Marshal<'arg>,)*> InvokeArgs for (#(T~I,)*) {
         #[allow(unused_variables)]
-        fn can_invoke(&self, runtime: &Runtime, signature: &FunctionSignature) -> Result<(), String> {
+        fn can_invoke(&self, runtime: &Runtime, signature: &FunctionSignature) -> Result<(), RuntimeError> {
             let arg_types = &signature.arg_types;
+            let provided_types: [Type; N] = [#(self.I.type_info(runtime),)*];
 
-            // Ensure the number of arguments match
-            #[allow(clippy::len_zero)]
-            if N != arg_types.len() {
-                return Err(format!("Invalid argument count. Expected {} arguments, got {}", arg_types.len(), N))
-            }
-
-            #(
-            if arg_types[I] != self.I.type_info(runtime) {
-                return Err(format!(
-                    "Invalid argument type at index {}. Expected: {}. Found: {}.",
-                    I,
-                    self.I.type_info(runtime).name(),
-                    arg_types[I].name(),
-                ));
+            // Ensure the number and types of the arguments match
+            if arg_types.as_slice() != provided_types.as_slice() {
+                return Err(RuntimeError::SignatureMismatch {
+                    expected: format!("({})", arg_types.iter().map(Type::name).join(", ")),
+                    actual: format!("({})", provided_types.iter().map(Type::name).join(", ")),
+                });
             }
-            )*
 
             Ok(())
         }
@@ -799,48 +1122,41 @@ impl Runtime {
         'runtime: 'ret,
     {
         // Get the function information from the runtime
-        let function_info = match self.get_function_definition(function_name).ok_or_else(|| {
-            format!("failed to obtain function '{function_name}', no such function exists.")
-        }) {
-            Ok(function_info) => function_info,
-            Err(msg) => {
-                let available_names = self.dispatch_table.get_fn_names();
-                let suggested_name =
-                    Self::find_best_match_for_fn_name(function_name, available_names, None);
-
-                let suggested_message = suggested_name.map_or_else(
-                    || msg.clone(),
-                    |name| format!("{msg} There is a function with a similar name: {name}"),
-                );
-
-                return Err(InvokeErr {
-                    msg: suggested_message,
-                    function_name,
-                    arguments,
-                });
-            }
+        let Some(function_info) = self.get_function_definition(function_name) else {
+            let available_names = self.dispatch_table.get_fn_names();
+            let suggestion =
+                Self::find_best_match_for_fn_name(function_name, available_names, None)
+                    .map(ToString::to_string);
+
+            return Err(InvokeErr {
+                error: RuntimeError::FunctionNotFound {
+                    name: function_name.to_owned(),
+                    suggestion,
+                },
+                function_name,
+                arguments,
+            });
         };
 
         // Validate the arguments
-        match arguments.can_invoke(self, &function_info.prototype.signature) {
-            Ok(_) => {}
-            Err(msg) => {
-                return Err(InvokeErr {
-                    msg,
-                    function_name,
-                    arguments,
-                })
-            }
+        if let Err(error) = arguments.can_invoke(self, &function_info.prototype.signature) {
+            return Err(InvokeErr {
+                error,
+                function_name,
+                arguments,
+            });
         };
 
         // Validate the return type
         if !ReturnType::accepts_type(&function_info.prototype.signature.return_type) {
             return Err(InvokeErr {
-                msg: format!(
-                    "unexpected return type, got '{}', expected '{}",
-                    &function_info.prototype.signature.return_type.name(),
-                    ReturnType::type_hint()
-                ),
+                error: RuntimeError::SignatureMismatch {
+                    expected: format!(
+                        "-> {}",
+                        function_info.prototype.signature.return_type.name()
+                    ),
+                    actual: format!("-> {}", ReturnType::type_hint()),
+                },
                 function_name,
                 arguments,
             });
@@ -1,6 +1,7 @@
 #[macro_use]
 mod util;
 
+use mun_runtime::RuntimeError;
 use mun_test::CompileAndRunTestDriver;
 
 #[test]
@@ -24,6 +25,35 @@ fn unknown_function() {
     );
 }
 
+#[test]
+fn mismatched_function_signature() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: Result<i32, _> = driver.runtime.invoke("add", (1i32,));
+    assert_eq!(
+        result.unwrap_err().error(),
+        &RuntimeError::SignatureMismatch {
+            expected: String::from("(core::i32, core::i32)"),
+            actual: String::from("(core::i32)"),
+        }
+    );
+
+    let result: Result<f64, _> = driver.runtime.invoke("add", (1i32, 2i32));
+    assert_eq!(
+        result.unwrap_err().error(),
+        &RuntimeError::SignatureMismatch {
+            expected: String::from("-> core::i32"),
+            actual: String::from("-> core::f64"),
+        }
+    );
+}
+
 #[test]
 fn exact_case_sensitive_match_exists_function() {
     const EXPECTED_FN_NAME: &str = "Foo";
@@ -121,3 +151,31 @@ fn multiple_match_exists_function() {
         )
     );
 }
+
+#[test]
+fn function_names() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn foo() -> i32 { 1 }
+    pub fn bar(a: i32) -> i32 { a }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert!(driver.runtime.has_function("foo"));
+    assert!(driver.runtime.has_function("bar"));
+    assert!(!driver.runtime.has_function("baz"));
+
+    let function_names: Vec<&str> = driver.runtime.function_names().collect();
+    assert!(function_names.contains(&"foo"));
+    assert!(function_names.contains(&"bar"));
+
+    // The runtime also provides the `new` and `new_array` intrinsics
+    assert_eq!(function_names.len(), 4);
+    assert_eq!(function_names.len(), driver.runtime.functions().count());
+    assert!(driver
+        .runtime
+        .functions()
+        .all(|function| driver.runtime.has_function(&function.prototype.name)));
+}
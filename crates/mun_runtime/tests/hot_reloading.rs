@@ -1,8 +1,13 @@
 #[macro_use]
 mod util;
 
+use std::{
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
 use mun_runtime::StructRef;
-use mun_test::CompileAndRunTestDriver;
+use mun_test::{CompileAndRunTestDriver, CompileTestDriver};
 
 #[test]
 fn reloadable_function_single_file() {
@@ -24,6 +29,35 @@ fn reloadable_function_single_file() {
     assert_invoke_eq!(i32, 10, driver, "main");
 }
 
+#[test]
+fn reload_forced() {
+    let mut driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    use package::foo::bar;
+    pub fn main() -> i32 { bar() }
+
+    //- /src/foo.mun
+    pub fn bar() -> i32 { 5 }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    assert_invoke_eq!(i32, 5, driver, "main");
+
+    // Safety: We compiled the library ourselves, therefor reloading the munlib is
+    // safe.
+    let num_reloaded =
+        unsafe { driver.runtime.reload_forced() }.expect("Failed to force reload assemblies");
+    assert_eq!(num_reloaded, 1);
+    assert_invoke_eq!(i32, 5, driver, "main");
+}
+
 #[test]
 fn reloadable_function_multi_file() {
     let mut driver = CompileAndRunTestDriver::from_fixture(
@@ -117,6 +151,69 @@ fn reloadable_struct_decl_single_file() {
     );
 }
 
+#[test]
+fn reloadable_struct_new_field() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Foo {
+        x: f32,
+    }
+
+    pub fn foo_new(x: f32) -> Foo {
+        Foo { x }
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let foo: StructRef<'_> = driver
+        .runtime
+        .invoke("foo_new", (1.5f32,))
+        .expect("Failed to call function");
+    assert_eq!(
+        foo.get::<f32>("x").expect("Failed to get struct field"),
+        1.5
+    );
+
+    let foo = foo.root();
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    pub struct Foo {
+        x: f32,
+        y: f32,
+    }
+
+    pub fn foo_new(x: f32) -> Foo {
+        Foo { x, y: x * 2.0 }
+    }
+    ",
+    );
+
+    // The existing instance is mapped to the new layout
+    let foo = foo.as_ref(&driver.runtime);
+    assert_eq!(
+        foo.get::<f32>("x").expect("Failed to get struct field"),
+        1.5
+    );
+    assert_eq!(
+        foo.get::<f32>("y").expect("Failed to get struct field"),
+        0.0
+    );
+
+    // New instances use the new layout
+    let new_foo: StructRef<'_> = driver
+        .runtime
+        .invoke("foo_new", (2.0f32,))
+        .expect("Failed to call function");
+    assert_eq!(
+        new_foo.get::<f32>("y").expect("Failed to get struct field"),
+        4.0
+    );
+}
+
 #[test]
 fn reloadable_struct_decl_multi_file() {
     let mut driver = CompileAndRunTestDriver::from_fixture(
@@ -190,3 +287,70 @@ fn reloadable_struct_decl_multi_file() {
         1
     );
 }
+
+#[test]
+fn watch_multiple_directories() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i32 { 5 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    // The directory of the loaded assembly is watched automatically
+    assert_eq!(driver.runtime.watched_directories().count(), 1);
+
+    // Load a second assembly from another directory
+    let mut other_driver = CompileTestDriver::from_file(
+        r"
+    pub fn other() -> i32 { 1 }
+    ",
+    );
+    let other_dir = other_driver.lib_path().parent().unwrap().to_path_buf();
+    // Safety: We compiled the library ourselves, therefore loading the munlib is
+    // safe.
+    unsafe { driver.runtime.add_assembly(other_driver.lib_path()) }
+        .expect("Failed to add assembly");
+    driver
+        .runtime
+        .watch_directory(&other_dir)
+        .expect("Watching a directory twice should succeed");
+    assert_eq!(driver.runtime.watched_directories().count(), 2);
+    assert_invoke_eq!(i32, 1, driver, "other");
+
+    // Changes to the assembly in the first directory should be picked up
+    driver.update_file(
+        "mod.mun",
+        r"
+    pub fn main() -> i32 { 10 }
+    ",
+    );
+    assert_invoke_eq!(i32, 10, driver, "main");
+
+    // Changes to the assembly in the second directory should be picked up
+    other_driver.update_file(
+        "mod.mun",
+        r"
+    pub fn other() -> i32 { 2 }
+    ",
+    );
+    let start_time = Instant::now();
+    // Safety: We compiled the library ourselves, therefore updating the runtime is
+    // safe.
+    while !unsafe { driver.runtime.update() } {
+        assert!(
+            start_time.elapsed() < Duration::from_secs(10),
+            "runtime did not update after recompilation within 10 seconds"
+        );
+        sleep(Duration::from_millis(1));
+    }
+    assert_invoke_eq!(i32, 2, driver, "other");
+    assert_invoke_eq!(i32, 10, driver, "main");
+
+    driver
+        .runtime
+        .unwatch_directory(&other_dir)
+        .expect("Failed to unwatch directory");
+    assert_eq!(driver.runtime.watched_directories().count(), 1);
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "async")]
+
+use std::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::Poll,
+};
+
+use mun_runtime::Runtime;
+use mun_test::{CompileAndRunTestDriver, CompileTestDriver};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .expect("failed to create Tokio runtime")
+        .block_on(future)
+}
+
+#[test]
+fn invoke_async() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn sum(a: i32, b: i32) -> i32 { a + b }
+        "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = block_on(driver.runtime.invoke_async("sum", (123i32, 456i32)));
+    assert_eq!(result, 123 + 456);
+}
+
+#[test]
+fn invoke_async_retries_after_reload() {
+    let mut driver = CompileTestDriver::from_file(
+        r#"
+    pub fn foo() -> i32 { 5 }
+        "#,
+    );
+
+    // Safety: We compiled the library ourselves, therefore loading the munlib is
+    // safe.
+    let mut runtime =
+        unsafe { Runtime::builder(driver.lib_path()).finish() }.expect("Failed to load runtime");
+
+    let result: i32 = block_on(async {
+        let mut invoke = runtime.invoke_async("main", ());
+
+        // `main` does not exist yet, so the invocation fails and waits for the
+        // runtime to be updated
+        let poll = poll_fn(|cx| Poll::Ready(Pin::new(&mut invoke).poll(cx))).await;
+        assert!(poll.is_pending());
+
+        driver.update_file(
+            "mod.mun",
+            r#"
+    pub fn main() -> i32 { 10 }
+            "#,
+        );
+        invoke.await
+    });
+    assert_eq!(result, 10);
+}
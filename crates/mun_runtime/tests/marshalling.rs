@@ -1,4 +1,7 @@
-use mun_runtime::{ArgumentReflection, Marshal, ReturnTypeReflection, StructRef};
+use mun_runtime::{
+    ArgumentReflection, InitError, LinkError, LinkFunctionsError, Marshal, ReturnTypeReflection,
+    RuntimeError, StructRef,
+};
 use mun_test::CompileAndRunTestDriver;
 
 #[macro_use]
@@ -553,6 +556,34 @@ fn marshal_struct() {
     assert!(bar_err.is_err());
 }
 
+#[test]
+fn field_type_mismatch() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Foo { a: i32 }
+    pub fn foo_new(a: i32) -> Foo { Foo { a } }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let mut foo: StructRef<'_> = driver.runtime.invoke("foo_new", (1i32,)).unwrap();
+
+    let expected_err = RuntimeError::FieldTypeMismatch {
+        struct_name: String::from("Foo"),
+        field_name: String::from("a"),
+        expected: String::from("core::f64"),
+        actual: String::from("core::i32"),
+    };
+    assert_eq!(foo.get::<f64>("a").unwrap_err(), expected_err);
+    assert_eq!(foo.replace("a", 1f64).unwrap_err(), expected_err);
+    assert_eq!(foo.set("a", 1f64).unwrap_err(), expected_err);
+    assert_eq!(
+        expected_err.to_string(),
+        "Mismatched types for `Foo::a`. Expected: `core::f64`. Found: `core::i32`."
+    );
+}
+
 #[test]
 fn extern_fn() {
     extern "C" fn add_int(a: i32, b: i32) -> i32 {
@@ -625,6 +656,27 @@ fn extern_fn_invalid_sig() {
     assert_invoke_eq!(isize, 16, driver, "main");
 }
 
+#[test]
+fn extern_fn_redefined() {
+    extern "C" fn add_int(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    let result = CompileAndRunTestDriver::new(
+        r#"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    "#,
+        |builder| builder.insert_fn("add", add_int as extern "C" fn(i32, i32) -> i32),
+    );
+
+    assert!(matches!(
+        result,
+        Err(InitError::LinkAssembly(LinkError::Function(
+            LinkFunctionsError::ExternFunctionRedefined { fn_name }
+        ))) if fn_name == "add"
+    ));
+}
+
 #[test]
 fn test_primitive_types() {
     fn test_field<
@@ -34,12 +34,15 @@ fn gc_trace() {
     let value: StructRef<'_> = runtime.invoke("new_foo", ()).unwrap();
     let value = value.root();
 
-    assert!(!runtime.gc_collect());
-    assert!(runtime.gc_stats().allocated_memory > 0);
+    assert_eq!(runtime.gc_collect().objects_freed, 0);
+    let allocated_memory = runtime.gc_stats().allocated_memory;
+    assert!(allocated_memory > 0);
 
     drop(value);
 
-    assert!(runtime.gc_collect());
+    let stats = runtime.gc_collect();
+    assert_eq!(stats.objects_freed, 2);
+    assert_eq!(stats.bytes_freed, allocated_memory);
     assert_eq!(runtime.gc_stats().allocated_memory, 0);
 }
 
@@ -2510,3 +2513,104 @@ fn test_type_table() {
     // These types should be equal
     assert_eq!(foo_bar_field_type, bar_type);
 }
+
+#[test]
+fn map_array_shared_between_structs() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+        pub struct Foo {
+            a: [i32],
+        }
+
+        pub struct Pair {
+            first: Foo,
+            second: Foo,
+        }
+
+        pub fn pair_new(a: i32, b: i32) -> Pair {
+            let values = [a, b];
+            Pair { first: Foo { a: values }, second: Foo { a: values } }
+        }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let a = 5i32;
+    let b = 1i32;
+    let pair: StructRef<'_> = driver.runtime.invoke("pair_new", (a, b)).unwrap();
+    let pair = pair.root();
+
+    driver.update_file(
+        "mod.mun",
+        r#"
+        pub struct Foo {
+            a: [i64],
+        }
+
+        pub struct Pair {
+            first: Foo,
+            second: Foo,
+        }
+    "#,
+    );
+
+    // The array is referenced by both structs, but must only be mapped once
+    for field_name in ["first", "second"] {
+        let foo = pair
+            .as_ref(&driver.runtime)
+            .get::<StructRef<'_>>(field_name)
+            .unwrap();
+        let array = foo.get::<ArrayRef<'_, i64>>("a").unwrap();
+
+        assert_eq!(array.iter().count(), 2);
+        array.iter().zip([a, b]).for_each(|(lhs, rhs)| {
+            assert_eq!(lhs, i64::from(rhs));
+        });
+    }
+}
+
+#[test]
+fn map_self_referential_struct() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+        pub struct Node {
+            value: i32,
+            next: [Node],
+        }
+
+        pub fn node_cycle(a: i32, b: i32) -> Node {
+            let first = Node { value: a, next: [] };
+            let second = Node { value: b, next: [first] };
+            first.next = [second];
+            first
+        }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let a = 5i32;
+    let b = 1i32;
+    let first: StructRef<'_> = driver.runtime.invoke("node_cycle", (a, b)).unwrap();
+    let first = first.root();
+
+    driver.update_file(
+        "mod.mun",
+        r#"
+        pub struct Node {
+            value: i64,
+            next: [Node],
+        }
+    "#,
+    );
+
+    // Follow the cycle twice, ending at the first node again
+    let mut node = first.as_ref(&driver.runtime);
+    for expected in [a, b, a] {
+        assert_eq!(node.get::<i64>("value").unwrap(), i64::from(expected));
+        let next = node.get::<ArrayRef<'_, StructRef<'_>>>("next").unwrap();
+        assert_eq!(next.iter().count(), 1);
+        node = next.iter().next().unwrap();
+    }
+}
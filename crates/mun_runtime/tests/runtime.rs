@@ -1,5 +1,13 @@
-use mun_runtime::LinkFunctionsError;
-use mun_test::CompileAndRunTestDriver;
+use mun_memory::gc::{Event, Observer};
+use mun_runtime::{LinkFunctionsError, Runtime, WatchError};
+use mun_test::{CompileAndRunTestDriver, CompileTestDriver};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 #[macro_use]
 mod util;
@@ -18,6 +26,58 @@ fn invoke() {
     assert_eq!(123 + 456, result);
 }
 
+#[test]
+fn invoke_retry_with_timeout() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn sum(a: i32, b: i32) -> i32 { a + b }
+        "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let err = driver
+        .runtime
+        .invoke::<i32, _>("sum", (123i32,))
+        .expect_err("invocation with invalid arguments should fail");
+    let timeout = Duration::from_millis(50);
+    let result: Result<i32, _> = err.retry_with_timeout(&mut driver.runtime, timeout);
+    let err = result.expect_err("retry should time out");
+    assert_eq!(err.function_name, "sum");
+    assert_eq!(err.timeout, timeout);
+}
+
+#[test]
+fn invoke_retry_n_times() {
+    let mut driver = CompileTestDriver::from_file(
+        r#"
+    pub fn foo() -> i32 { 5 }
+        "#,
+    );
+
+    // Safety: We compiled the library ourselves, therefore loading the munlib is
+    // safe.
+    let mut runtime =
+        unsafe { Runtime::builder(driver.lib_path()).finish() }.expect("Failed to load runtime");
+
+    // `main` does not exist yet, so the invocation fails
+    let err = runtime
+        .invoke::<i32, _>("main", ())
+        .expect_err("invocation of a missing function should fail");
+
+    // Adding `main` allows the retry to succeed
+    driver.update_file(
+        "mod.mun",
+        r#"
+    pub fn main() -> i32 { 10 }
+        "#,
+    );
+    let result: i32 = err
+        .retry_n_times(&mut runtime, 1)
+        .unwrap_or_else(|_| panic!("retry should succeed after recompilation"));
+    assert_eq!(result, 10);
+}
+
 #[test]
 fn arrays_are_collected() {
     let driver = CompileAndRunTestDriver::new(
@@ -30,13 +90,13 @@ fn arrays_are_collected() {
     )
     .expect("Failed to build test driver");
 
-    assert!(!driver.runtime.gc_collect());
+    assert_eq!(driver.runtime.gc_collect().objects_freed, 0);
     let _: () = driver
         .runtime
         .invoke("main", ())
         .expect("error invoking main function");
-    assert!(driver.runtime.gc_collect());
-    assert!(!driver.runtime.gc_collect());
+    assert_eq!(driver.runtime.gc_collect().objects_freed, 1);
+    assert_eq!(driver.runtime.gc_collect().objects_freed, 0);
 }
 
 #[test]
@@ -231,3 +291,104 @@ fn cyclic_struct() {
         .ty();
     assert_eq!(foo_foo_ty, foo_ty);
 }
+
+#[test]
+fn unwatch_unknown_directory() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() {}
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let dir = tempfile::TempDir::new().unwrap();
+    assert!(matches!(
+        driver.runtime.unwatch_directory(dir.path()),
+        Err(WatchError::NotWatched(_))
+    ));
+}
+
+#[test]
+fn builder_options() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() {}
+    ",
+        |builder| builder.watch_interval(Duration::from_millis(10)),
+    )
+    .expect("Failed to build test driver");
+
+    assert_eq!(driver.runtime.watch_interval(), Duration::from_millis(10));
+}
+
+#[test]
+fn builder_library_path() {
+    let driver = CompileTestDriver::from_file(
+        r#"
+    pub fn main() -> i32 { 5 }
+        "#,
+    );
+
+    // Safety: We compiled the library ourselves, therefore loading the munlib is
+    // safe.
+    let runtime = unsafe {
+        Runtime::builder("does/not/exist.munlib")
+            .library_path(driver.lib_path())
+            .build()
+    }
+    .expect("Failed to load runtime");
+
+    let result: i32 = runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 5);
+}
+
+#[test]
+fn runtime_does_not_collect_automatically() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() {}
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_eq!(driver.runtime.gc().collection_threshold(), usize::MAX);
+}
+
+#[test]
+fn builder_gc_observer() {
+    struct AllocationCounter(Arc<AtomicUsize>);
+
+    impl Observer for AllocationCounter {
+        type Event = Event;
+
+        fn event(&self, event: Event) {
+            if let Event::Allocation(_) = event {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    let allocations = Arc::new(AtomicUsize::new(0));
+    let counter = AllocationCounter(allocations.clone());
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    struct Foo { value: i32 }
+
+    pub fn main() -> i32 {
+        let a = Foo { value: 1 };
+        let b = Foo { value: 2 };
+        let c = Foo { value: 3 };
+        a.value + b.value + c.value
+    }
+    ",
+        |builder| builder.with_gc_observer(counter),
+    )
+    .expect("Failed to build test driver");
+
+    let allocations_before = allocations.load(Ordering::Relaxed);
+    let result: i32 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 6);
+    assert_eq!(allocations.load(Ordering::Relaxed) - allocations_before, 3);
+}
@@ -124,7 +124,7 @@ pub unsafe extern "C" fn mun_gc_collect(runtime: Runtime, reclaimed: *mut bool)
         .inner()
         .map_err(|e| format!("invalid argument 'runtime': {e}")));
     let reclaimed = try_deref_mut!(reclaimed);
-    *reclaimed = runtime.gc_collect();
+    *reclaimed = runtime.gc_collect().objects_freed > 0;
     ErrorHandle::default()
 }
 
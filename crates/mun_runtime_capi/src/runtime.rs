@@ -181,6 +181,7 @@ pub unsafe extern "C" fn mun_runtime_create(
                     },
                 },
                 fn_ptr: def.fn_ptr,
+                is_extern: true,
             })
         })
         .collect::<Result<_, _>>());
@@ -189,6 +190,8 @@ pub unsafe extern "C" fn mun_runtime_create(
         library_path: library_path.into(),
         user_functions,
         type_table,
+        watch_interval: None,
+        gc_observer: None,
     };
 
     let runtime = match mun_runtime::Runtime::new(runtime_options) {